@@ -0,0 +1,300 @@
+//! Provides a reader for HTTP downloads that retries transient failures,
+//! resuming from the last byte received when the server supports byte ranges.
+
+use std::io::{self, Read};
+use std::thread::sleep;
+use std::time::Duration;
+
+use super::ArchiveError;
+use attohttpc::header::{HeaderMap, AUTHORIZATION};
+use attohttpc::{RequestBuilder, ResponseReader, StatusCode};
+use hyperx::header::{
+    AcceptRanges, ByteRangeSpec, ContentLength, Header, Range, RangeUnit, TypedHeaders,
+};
+
+/// The number of times a failed request is retried before giving up.
+const MAX_RETRIES: u32 = 3;
+
+/// The delay before the first retry, which doubles for each retry after that.
+#[cfg(not(test))]
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+#[cfg(test)]
+const INITIAL_BACKOFF: Duration = Duration::from_millis(1);
+
+/// An in-progress download of a remote file.
+///
+/// If the connection drops partway through and the server accepts byte
+/// ranges, reading transparently requests the rest of the file with a `Range`
+/// header, so the bytes already received (and written to the staging file)
+/// don't need to be downloaded again.
+pub struct Download {
+    url: String,
+    authorization: Option<String>,
+    response: ResponseReader,
+    content_length: Option<u64>,
+    accepts_byte_ranges: bool,
+    position: u64,
+    retries: u32,
+}
+
+impl Download {
+    /// Starts downloading from the given URL, retrying if the connection fails
+    /// or the server responds with a 5xx status.
+    pub fn start(url: &str, authorization: Option<&str>) -> Result<Self, ArchiveError> {
+        let mut retries = 0;
+        let (headers, response) = loop {
+            match send(url, authorization, None) {
+                Ok(result) => break result,
+                Err(error) if error.is_retryable() && retries < MAX_RETRIES => {
+                    sleep(backoff(retries));
+                    retries += 1;
+                }
+                Err(error) => return Err(error),
+            }
+        };
+
+        Ok(Download {
+            url: url.to_string(),
+            authorization: authorization.map(String::from),
+            response,
+            content_length: content_length(&headers).ok(),
+            accepts_byte_ranges: accepts_byte_ranges(&headers),
+            position: 0,
+            retries,
+        })
+    }
+
+    /// The total length of the file, if the server reported it.
+    pub fn content_length(&self) -> Option<u64> {
+        self.content_length
+    }
+
+    /// Whether the server accepts `Range` requests for the file.
+    pub fn accepts_byte_ranges(&self) -> bool {
+        self.accepts_byte_ranges
+    }
+
+    /// A download can only be resumed if the server accepts byte ranges and
+    /// reported the length, since otherwise a dropped connection can't be
+    /// distinguished from the end of the file.
+    fn can_resume(&self, error: &io::Error) -> bool {
+        self.accepts_byte_ranges
+            && self.content_length.is_some()
+            && self.retries < MAX_RETRIES
+            && is_transient(error)
+    }
+
+    /// Requests the remainder of the file, starting at the current position.
+    fn resume(&mut self) -> Result<(), ArchiveError> {
+        loop {
+            sleep(backoff(self.retries));
+            self.retries += 1;
+
+            match send(
+                &self.url,
+                self.authorization.as_deref(),
+                Some(self.position),
+            ) {
+                Ok((_, response)) => {
+                    self.response = response;
+                    return Ok(());
+                }
+                Err(error) if error.is_retryable() && self.retries < MAX_RETRIES => {}
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+impl Read for Download {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let error = match self.response.read(buf) {
+                Ok(0) if !buf.is_empty() && self.position < self.content_length.unwrap_or(0) => {
+                    io::Error::from(io::ErrorKind::UnexpectedEof)
+                }
+                Ok(read) => {
+                    self.position += read as u64;
+                    return Ok(read);
+                }
+                Err(error) => error,
+            };
+
+            if !self.can_resume(&error) {
+                return Err(error);
+            }
+
+            self.resume()
+                .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+        }
+    }
+}
+
+/// Builds a GET request for the given URL, including the `Authorization` header if provided.
+pub(crate) fn request(
+    url: &str,
+    authorization: Option<&str>,
+) -> Result<RequestBuilder, ArchiveError> {
+    // Compression is disabled so that byte positions refer to the file itself
+    let request = http_client::get(url)?.allow_compression(false);
+    Ok(match authorization {
+        Some(value) => request.header(AUTHORIZATION, value),
+        None => request,
+    })
+}
+
+/// Sends a request for the file, starting from the given byte offset if provided.
+fn send(
+    url: &str,
+    authorization: Option<&str>,
+    from: Option<u64>,
+) -> Result<(HeaderMap, ResponseReader), ArchiveError> {
+    let mut request = request(url, authorization)?;
+    if let Some(offset) = from {
+        let range = Range::Bytes(vec![ByteRangeSpec::AllFrom(offset)]);
+        request = request.header(Range::header_name(), range.to_string());
+    }
+
+    let (status, headers, response) = request
+        .send()
+        .map_err(ArchiveError::from_request_error)?
+        .split();
+
+    if status.is_server_error() {
+        return Err(ArchiveError::ServerError(status));
+    }
+
+    // A server that ignores the range would send the whole file again, so anything other than
+    // partial content can't be used to resume
+    let expected = from.map_or(status.is_success(), |_| {
+        status == StatusCode::PARTIAL_CONTENT
+    });
+    if !expected {
+        return Err(ArchiveError::HttpError(status));
+    }
+
+    Ok((headers, response))
+}
+
+fn backoff(retries: u32) -> Duration {
+    INITIAL_BACKOFF * 2u32.pow(retries)
+}
+
+/// Determines whether an error reading the response might not happen again on a new connection.
+fn is_transient(error: &io::Error) -> bool {
+    match error.kind() {
+        io::ErrorKind::ConnectionReset
+        | io::ErrorKind::ConnectionAborted
+        | io::ErrorKind::BrokenPipe
+        | io::ErrorKind::TimedOut
+        | io::ErrorKind::UnexpectedEof => true,
+        _ => false,
+    }
+}
+
+/// Determines the length of an HTTP response's content in bytes, using
+/// the HTTP `"Content-Length"` header.
+pub(crate) fn content_length(headers: &HeaderMap) -> Result<u64, ArchiveError> {
+    headers
+        .decode::<ContentLength>()
+        .ok()
+        .map(|v| v.0)
+        .ok_or_else(|| ArchiveError::MissingHeaderError(String::from("Content-Length")))
+}
+
+fn accepts_byte_ranges(headers: &HeaderMap) -> bool {
+    headers
+        .decode::<AcceptRanges>()
+        .ok()
+        .map(|v| v.iter().any(|unit| *unit == RangeUnit::Bytes))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::Download;
+    use crate::ArchiveError;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    /// Serves each of the given responses to one connection in turn, as a stand-in for a flaky
+    /// server, and returns the request headers that it received
+    fn serve(responses: Vec<&'static str>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("could not bind listener");
+        let url = format!("http://{}/archive.tar.gz", listener.local_addr().unwrap());
+
+        let server = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for response in responses {
+                let (mut stream, _) = listener.accept().expect("no connection to server");
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request = String::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    request.push_str(&line);
+                }
+                requests.push(request);
+
+                // Dropping the stream closes the connection, even if the body is incomplete
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+
+        (url, server)
+    }
+
+    #[test]
+    fn test_retries_server_errors() {
+        let (url, server) = serve(vec![
+            "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 200 OK\r\nContent-Length: 10\r\nConnection: close\r\n\r\n0123456789",
+        ]);
+
+        let mut contents = String::new();
+        Download::start(&url, None)
+            .expect("download should be retried")
+            .read_to_string(&mut contents)
+            .unwrap();
+
+        assert_eq!(contents, "0123456789");
+        assert_eq!(server.join().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_resumes_dropped_connection() {
+        let (url, server) = serve(vec![
+            "HTTP/1.1 200 OK\r\nAccept-Ranges: bytes\r\nContent-Length: 10\r\nConnection: close\r\n\r\n01234",
+            "HTTP/1.1 206 Partial Content\r\nContent-Range: bytes 5-9/10\r\nContent-Length: 5\r\nConnection: close\r\n\r\n56789",
+        ]);
+
+        let mut contents = String::new();
+        Download::start(&url, None)
+            .unwrap()
+            .read_to_string(&mut contents)
+            .expect("download should be resumed");
+
+        assert_eq!(contents, "0123456789");
+        let requests = server.join().unwrap();
+        assert!(requests[1].to_lowercase().contains("range: bytes=5-"));
+    }
+
+    #[test]
+    fn test_does_not_retry_client_errors() {
+        let (url, server) = serve(vec![
+            "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        ]);
+
+        match Download::start(&url, None) {
+            Err(error @ ArchiveError::HttpError(_)) => assert!(!error.is_retryable()),
+            _ => panic!("expected a fatal HTTP error"),
+        }
+        assert_eq!(server.join().unwrap().len(), 1);
+    }
+}
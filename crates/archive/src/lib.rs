@@ -5,6 +5,7 @@ use std::path::Path;
 
 use thiserror::Error;
 
+mod download;
 mod tarball;
 mod zip;
 
@@ -12,11 +13,21 @@ pub use crate::tarball::Tarball;
 pub use crate::zip::Zip;
 
 /// Error type for this crate
+///
+/// Failures that might not happen again, such as a dropped connection or a 5xx
+/// response, have their own variants so that they can be retried; see
+/// `ArchiveError::is_retryable`.
 #[derive(Error, Debug)]
 pub enum ArchiveError {
     #[error("HTTP failure ({0})")]
     HttpError(::attohttpc::StatusCode),
 
+    #[error("HTTP server failure ({0})")]
+    ServerError(::attohttpc::StatusCode),
+
+    #[error("{0}")]
+    ConnectionError(#[source] attohttpc::Error),
+
     #[error("HTTP header '{0}' not found")]
     MissingHeaderError(String),
 
@@ -29,10 +40,33 @@ pub enum ArchiveError {
     #[error("{0}")]
     AttohttpcError(#[from] attohttpc::Error),
 
+    #[error("{0}")]
+    ClientError(#[from] http_client::ClientError),
+
     #[error("{0}")]
     ZipError(#[from] zip_rs::result::ZipError),
 }
 
+impl ArchiveError {
+    /// Whether the same request might succeed if it were retried.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ArchiveError::ServerError(_) | ArchiveError::ConnectionError(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Classifies an error sending a request: I/O failures (such as a refused
+    /// or reset connection) are retryable, while anything else (such as an
+    /// invalid URL or a TLS failure) is not.
+    fn from_request_error(error: attohttpc::Error) -> Self {
+        match error.kind() {
+            attohttpc::ErrorKind::Io(_) => ArchiveError::ConnectionError(error),
+            _ => ArchiveError::AttohttpcError(error),
+        }
+    }
+}
+
 /// Metadata describing whether an archive comes from a local or remote origin.
 #[derive(Copy, Clone)]
 pub enum Origin {
@@ -2,16 +2,14 @@
 //! tarball in Unix operating systems.
 
 use std::fs::File;
-use std::io::{Read, Seek, SeekFrom};
+use std::io::{self, Read, Seek, SeekFrom};
 use std::path::Path;
 
+use super::download::{content_length, request, Download};
 use super::{Archive, ArchiveError, Origin};
-use attohttpc::header::HeaderMap;
 use flate2::read::GzDecoder;
 use fs_utils::ensure_containing_dir_exists;
-use hyperx::header::{
-    AcceptRanges, ByteRangeSpec, ContentLength, Header, Range, RangeUnit, TypedHeaders,
-};
+use hyperx::header::{ByteRangeSpec, Header, Range};
 use progress_read::ProgressRead;
 use tee::TeeReader;
 
@@ -27,16 +25,6 @@ pub struct Tarball {
     origin: Origin,
 }
 
-/// Determines the length of an HTTP response's content in bytes, using
-/// the HTTP `"Content-Length"` header.
-fn content_length(headers: &HeaderMap) -> Result<u64, ArchiveError> {
-    headers
-        .decode::<ContentLength>()
-        .ok()
-        .map(|v| v.0)
-        .ok_or_else(|| ArchiveError::MissingHeaderError(String::from("Content-Length")))
-}
-
 impl Tarball {
     /// Loads a tarball from the specified file.
     pub fn load(mut source: File) -> Result<Box<dyn Archive>, ArchiveError> {
@@ -54,32 +42,49 @@ impl Tarball {
     /// tarball that can be streamed (and that tees its data to a local
     /// file as it streams).
     pub fn fetch(url: &str, cache_file: &Path) -> Result<Box<dyn Archive>, ArchiveError> {
-        let (status, headers, response) = attohttpc::get(url).send()?.split();
-
-        if !status.is_success() {
-            return Err(ArchiveError::HttpError(status));
-        }
-
-        let compressed_size = content_length(&headers)?;
-        let uncompressed_size = if accepts_byte_ranges(&headers) {
-            fetch_uncompressed_size(url, compressed_size)
-        } else {
-            None
-        };
-
-        ensure_containing_dir_exists(&cache_file)?;
-        let file = File::create(cache_file)?;
-        let data = Box::new(TeeReader::new(response, file));
+        fetch_tarball(url, None, cache_file)
+    }
 
-        Ok(Box::new(Tarball {
-            uncompressed_size,
-            compressed_size,
-            data,
-            origin: Origin::Remote,
-        }))
+    /// Initiate fetching of a tarball from the given URL, sending the given
+    /// value as the `Authorization` header of each request. This is needed
+    /// for tarballs hosted by private registries.
+    pub fn fetch_authorized(
+        url: &str,
+        authorization: &str,
+        cache_file: &Path,
+    ) -> Result<Box<dyn Archive>, ArchiveError> {
+        fetch_tarball(url, Some(authorization), cache_file)
     }
 }
 
+fn fetch_tarball(
+    url: &str,
+    authorization: Option<&str>,
+    cache_file: &Path,
+) -> Result<Box<dyn Archive>, ArchiveError> {
+    let download = Download::start(url, authorization)?;
+
+    let compressed_size = download
+        .content_length()
+        .ok_or_else(|| ArchiveError::MissingHeaderError(String::from("Content-Length")))?;
+    let uncompressed_size = if download.accepts_byte_ranges() {
+        fetch_uncompressed_size(url, authorization, compressed_size)
+    } else {
+        None
+    };
+
+    ensure_containing_dir_exists(&cache_file)?;
+    let file = File::create(cache_file)?;
+    let data = Box::new(TeeReader::new(download, file));
+
+    Ok(Box::new(Tarball {
+        uncompressed_size,
+        compressed_size,
+        data,
+        origin: Origin::Remote,
+    }))
+}
+
 impl Archive for Tarball {
     fn compressed_size(&self) -> u64 {
         self.compressed_size
@@ -95,6 +100,11 @@ impl Archive for Tarball {
         let decoded = GzDecoder::new(self.data);
         let mut tarball = tar::Archive::new(ProgressRead::new(decoded, (), progress));
         tarball.unpack(dest)?;
+
+        // Unpacking stops at the end-of-archive marker, so read any remaining padding as well.
+        // For a remote tarball, this ensures that the whole file has been written to the cache
+        // and can be verified.
+        io::copy(&mut tarball.into_inner(), &mut io::sink())?;
         Ok(())
     }
     fn origin(&self) -> Origin {
@@ -126,9 +136,9 @@ fn unpack_isize(packed: [u8; 4]) -> u64 {
 /// of a gzip file from a URL. This makes two round-trips to the server but avoids
 /// downloading the entire gzip file. For very small files it's unlikely to be
 /// more efficient than simply downloading the entire file up front.
-fn fetch_isize(url: &str, len: u64) -> Result<[u8; 4], ArchiveError> {
+fn fetch_isize(url: &str, authorization: Option<&str>, len: u64) -> Result<[u8; 4], ArchiveError> {
     let range_header = Range::Bytes(vec![ByteRangeSpec::FromTo(len - 4, len - 1)]);
-    let (status, headers, mut response) = attohttpc::get(url)
+    let (status, headers, mut response) = request(url, authorization)?
         .header(Range::header_name(), range_header.to_string())
         .send()?
         .split();
@@ -158,22 +168,14 @@ fn load_isize(file: &mut File) -> Result<[u8; 4], ArchiveError> {
     Ok(buf)
 }
 
-fn accepts_byte_ranges(headers: &HeaderMap) -> bool {
-    headers
-        .decode::<AcceptRanges>()
-        .ok()
-        .map(|v| v.iter().any(|unit| *unit == RangeUnit::Bytes))
-        .unwrap_or(false)
-}
-
 /// Determines the uncompressed size of a gzip file hosted at the specified
 /// URL by fetching just the metadata associated with the file. This makes
 /// an extra round-trip to the server, so it's only more efficient than just
 /// downloading the file if the file is large enough that downloading it is
 /// slower than the extra round trips.
-fn fetch_uncompressed_size(url: &str, len: u64) -> Option<u64> {
+fn fetch_uncompressed_size(url: &str, authorization: Option<&str>, len: u64) -> Option<u64> {
     // if there is an error, we ignore it and return None, instead of failing
-    fetch_isize(url, len).ok().map(unpack_isize)
+    fetch_isize(url, authorization, len).ok().map(unpack_isize)
 }
 
 /// Determines the uncompressed size of the specified gzip file on disk.
@@ -5,6 +5,7 @@ use std::fs::{create_dir_all, File};
 use std::io::copy;
 use std::path::Path;
 
+use crate::download::Download;
 use crate::ArchiveError;
 use progress_read::ProgressRead;
 use verbatim::PathExt;
@@ -34,15 +35,11 @@ impl Zip {
     /// Initiate fetching of a Node zip archive from the given URL, returning
     /// a `Remote` data source.
     pub fn fetch(url: &str, cache_file: &Path) -> Result<Box<dyn Archive>, ArchiveError> {
-        let (status, _, mut response) = attohttpc::get(url).send()?.split();
-
-        if !status.is_success() {
-            return Err(ArchiveError::HttpError(status));
-        }
+        let mut download = Download::start(url, None)?;
 
         {
             let mut file = File::create(cache_file)?;
-            copy(&mut response, &mut file)?;
+            copy(&mut download, &mut file)?;
         }
 
         let file = File::open(cache_file)?;
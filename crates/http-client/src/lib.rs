@@ -0,0 +1,234 @@
+//! This crate provides the shared settings for every HTTP request that Volta
+//! makes, so that downloads work the same way behind a corporate network:
+//!
+//! * Proxies are read from the `HTTP_PROXY`, `HTTPS_PROXY`, and `NO_PROXY`
+//!   environment variables (or their lowercase equivalents).
+//! * Extra CA certificates, such as those of a TLS-inspecting proxy, are read
+//!   from the PEM file named by `VOLTA_EXTRA_CA_CERTS`, or `NODE_EXTRA_CA_CERTS`
+//!   if that is not set, and trusted in addition to the system certificates.
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+
+use attohttpc::{ProxySettings, RequestBuilder, Session};
+use lazy_static::lazy_static;
+use native_tls::Certificate;
+use thiserror::Error;
+
+const BEGIN_CERTIFICATE: &str = "-----BEGIN CERTIFICATE-----";
+const END_CERTIFICATE: &str = "-----END CERTIFICATE-----";
+
+lazy_static! {
+    /// The extra CA certificates, once they have been loaded successfully
+    static ref EXTRA_CERTIFICATES: Mutex<Option<Vec<Certificate>>> = Mutex::new(None);
+}
+
+/// Error type for this crate
+#[derive(Error, Debug)]
+pub enum ClientError {
+    #[error("could not read CA certificates from {}", .file.display())]
+    ReadCertificatesError {
+        file: PathBuf,
+        #[source]
+        error: std::io::Error,
+    },
+
+    #[error("could not parse CA certificate from {}", .file.display())]
+    ParseCertificateError {
+        file: PathBuf,
+        #[source]
+        error: native_tls::Error,
+    },
+
+    #[error("no PEM certificates found in {}", .file.display())]
+    NoCertificatesError { file: PathBuf },
+}
+
+impl ClientError {
+    /// The CA certificate file that caused the error
+    pub fn file(&self) -> &Path {
+        match self {
+            ClientError::ReadCertificatesError { file, .. }
+            | ClientError::ParseCertificateError { file, .. }
+            | ClientError::NoCertificatesError { file } => file,
+        }
+    }
+}
+
+/// Builds an HTTP session with the proxy and CA certificate settings applied.
+pub fn session() -> Result<Session, ClientError> {
+    session_with_proxy(ProxySettings::from_env())
+}
+
+fn session_with_proxy(proxy_settings: ProxySettings) -> Result<Session, ClientError> {
+    let mut session = Session::new();
+    session.proxy_settings(proxy_settings);
+
+    for certificate in cached_extra_certificates()? {
+        session.add_root_certificate(certificate);
+    }
+
+    Ok(session)
+}
+
+/// Starts building a GET request to the specified URL, using the shared settings.
+pub fn get(url: &str) -> Result<RequestBuilder, ClientError> {
+    Ok(session()?.get(url))
+}
+
+/// Starts building a POST request to the specified URL, using the shared settings.
+pub fn post(url: &str) -> Result<RequestBuilder, ClientError> {
+    Ok(session()?.post(url))
+}
+
+/// Determines the file of extra CA certificates, if one is configured.
+fn extra_certificates_file() -> Option<PathBuf> {
+    env::var_os("VOLTA_EXTRA_CA_CERTS")
+        .or_else(|| env::var_os("NODE_EXTRA_CA_CERTS"))
+        .filter(|file| !file.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Loads the extra CA certificates the first time they are needed, so that the file isn't read
+/// again for every request.
+fn cached_extra_certificates() -> Result<Vec<Certificate>, ClientError> {
+    let mut cached = EXTRA_CERTIFICATES
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+
+    if let Some(certificates) = &*cached {
+        return Ok(certificates.clone());
+    }
+
+    let certificates = extra_certificates()?;
+    *cached = Some(certificates.clone());
+    Ok(certificates)
+}
+
+/// Loads the extra CA certificates, if any. A PEM file may contain a bundle of
+/// several certificates, each of which is trusted.
+fn extra_certificates() -> Result<Vec<Certificate>, ClientError> {
+    let file = match extra_certificates_file() {
+        Some(file) => file,
+        None => return Ok(Vec::new()),
+    };
+
+    let contents = match fs::read_to_string(&file) {
+        Ok(contents) => contents,
+        Err(error) => return Err(ClientError::ReadCertificatesError { file, error }),
+    };
+
+    let blocks = pem_blocks(&contents);
+    if blocks.is_empty() {
+        return Err(ClientError::NoCertificatesError { file });
+    }
+
+    blocks
+        .into_iter()
+        .map(|block| {
+            Certificate::from_pem(block.as_bytes()).map_err(|error| {
+                ClientError::ParseCertificateError {
+                    file: file.clone(),
+                    error,
+                }
+            })
+        })
+        .collect()
+}
+
+/// Splits the contents of a PEM file into the individual certificate blocks.
+fn pem_blocks(contents: &str) -> Vec<&str> {
+    let mut blocks = Vec::new();
+    let mut rest = contents;
+
+    while let Some(start) = rest.find(BEGIN_CERTIFICATE) {
+        let end = match rest[start..].find(END_CERTIFICATE) {
+            Some(offset) => start + offset + END_CERTIFICATE.len(),
+            None => break,
+        };
+
+        blocks.push(&rest[start..end]);
+        rest = &rest[end..];
+    }
+
+    blocks
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+    use url::Url;
+
+    #[test]
+    fn test_pem_blocks() {
+        let bundle = "\
+# Corporate root
+-----BEGIN CERTIFICATE-----
+AAAA
+-----END CERTIFICATE-----
+-----BEGIN CERTIFICATE-----
+BBBB
+-----END CERTIFICATE-----
+";
+
+        assert_eq!(
+            pem_blocks(bundle),
+            vec![
+                "-----BEGIN CERTIFICATE-----\nAAAA\n-----END CERTIFICATE-----",
+                "-----BEGIN CERTIFICATE-----\nBBBB\n-----END CERTIFICATE-----",
+            ]
+        );
+        assert!(pem_blocks("not a certificate").is_empty());
+        assert!(pem_blocks("-----BEGIN CERTIFICATE-----\nAAAA").is_empty());
+    }
+
+    #[test]
+    fn test_requests_use_proxy() {
+        // A stand-in for a proxy server, which records the request line and sends a fixed response
+        let listener = TcpListener::bind("127.0.0.1:0").expect("could not bind listener");
+        let proxy_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let proxy = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("no connection to proxy");
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header.trim().is_empty() {
+                    break;
+                }
+            }
+
+            stream
+                .write_all(
+                    b"HTTP/1.1 200 OK\r\nContent-Length: 7\r\nConnection: close\r\n\r\nproxied",
+                )
+                .unwrap();
+            request_line
+        });
+
+        let proxy_settings = ProxySettings::builder()
+            .http_proxy(Url::parse(&proxy_url).unwrap())
+            .build();
+
+        let body = session_with_proxy(proxy_settings)
+            .expect("could not build session")
+            .get("http://volta.invalid/index.json")
+            .send()
+            .and_then(|response| response.text())
+            .expect("request through proxy failed");
+
+        assert_eq!(body, "proxied");
+        assert_eq!(
+            proxy.join().unwrap().trim_end(),
+            "GET http://volta.invalid/index.json HTTP/1.1"
+        );
+    }
+}
@@ -45,6 +45,31 @@ pub enum ErrorKind {
         package: String,
     },
 
+    /// Thrown when a user tries to `volta uninstall` their default version of Node
+    CannotUninstallDefaultNode {
+        version: String,
+    },
+
+    /// Thrown when the checksums for a tool distro could not be downloaded
+    ChecksumFetchError {
+        tool: String,
+        from_url: String,
+    },
+
+    /// Thrown when a downloaded distro does not match its published checksum
+    ChecksumMismatch {
+        tool: String,
+        version: String,
+        expected: String,
+        actual: String,
+    },
+
+    /// Thrown when the published checksums do not include an entry for the distro file
+    ChecksumNotFound {
+        file: String,
+        from_url: String,
+    },
+
     /// Thrown when the Completions out-dir is not a directory
     CompletionsOutFileError {
         path: PathBuf,
@@ -132,6 +157,11 @@ pub enum ErrorKind {
         path: PathBuf,
     },
 
+    /// Thrown when the extra CA certificates could not be loaded
+    ExtraCaCertificatesError {
+        file: PathBuf,
+    },
+
     /// Thrown when a hook command returns a non-zero exit code
     HookCommandFailed {
         command: String,
@@ -148,6 +178,13 @@ pub enum ErrorKind {
         command: String,
     },
 
+    /// Thrown when a downloaded package does not match the integrity published by the registry
+    IntegrityMismatch {
+        package: String,
+        expected: String,
+        actual: String,
+    },
+
     InvalidHookCommand {
         command: String,
     },
@@ -171,6 +208,16 @@ pub enum ErrorKind {
         errors: Vec<String>,
     },
 
+    /// Thrown when a lock file could not be created or locked
+    LockError {
+        file: PathBuf,
+    },
+
+    /// Thrown when `volta list-remote --lts` is used with a tool other than Node
+    LtsOnlyForNode {
+        tool: String,
+    },
+
     /// Thrown when BinConfig (read from file) does not contain Platform info.
     NoBinPlatform {
         binary: String,
@@ -181,9 +228,26 @@ pub enum ErrorKind {
         command: String,
     },
 
+    /// Thrown when pnpm is not set at the command-line
+    NoCommandLinePnpm,
+
     /// Thrown when Yarn is not set at the command-line
     NoCommandLineYarn,
 
+    /// Thrown when default pnpm is not set
+    NoDefaultPnpm,
+
+    /// Thrown when `volta trust` is run in a directory without project hooks
+    NoProjectHooks,
+
+    /// Thrown when pnpm is not set in a project
+    NoProjectPnpm,
+
+    /// Thrown when `volta env --hook` is used with an output format that can't run a hook
+    NoShellHook {
+        shell: String,
+    },
+
     /// Thrown when there is no Node version matching a requested semver specifier.
     NodeVersionNotFound {
         matching: String,
@@ -231,33 +295,25 @@ pub enum ErrorKind {
 
     NoVersionsFound,
 
-    /// Thrown when there is an error running `npm pack`
-    NpmPackFetchError {
-        package: String,
-    },
-
-    /// Thrown when there is issue finding, loading, or unpacking the file downloaded via `npm pack`
-    NpmPackUnpackError {
-        package: String,
-    },
-
     /// Thrown when there is no npm version matching the requested Semver/Tag
     NpmVersionNotFound {
         matching: String,
     },
 
-    /// Thrown when there is an error running `npm view`
-    NpmViewMetadataFetchError {
-        package: String,
+    NpxNotAvailable {
+        version: String,
     },
 
-    /// Thrown when there is an error parsing the metadata from `npm view`
-    NpmViewMetadataParseError {
-        package: String,
+    /// Thrown when a tool needs to be downloaded while Volta is in offline mode
+    OfflineFetchError {
+        tool: String,
     },
 
-    NpxNotAvailable {
-        version: String,
+    /// Thrown when no version of a tool in the local inventory matches while in offline mode
+    OfflineVersionNotFound {
+        tool: String,
+        matching: String,
+        guessed_lts: bool,
     },
 
     /// Thrown when package install command is not successful.
@@ -335,11 +391,26 @@ pub enum ErrorKind {
         tool_spec: String,
     },
 
+    /// Thrown when a toolchain file for `volta import` could not be parsed
+    ParseToolchainFileError {
+        file: PathBuf,
+    },
+
     /// Thrown when persisting an archive to the inventory fails
     PersistInventoryError {
         tool: String,
     },
 
+    /// Thrown when there is no pnpm version matching the requested Semver/Tag
+    PnpmVersionNotFound {
+        matching: String,
+    },
+
+    /// Thrown when pinning a prerelease version of Node without allowing it
+    PrereleasePinError {
+        version: String,
+    },
+
     /// Thrown when executing a project-local binary fails
     ProjectLocalBinaryExecError {
         command: String,
@@ -376,6 +447,11 @@ pub enum ErrorKind {
         dir: PathBuf,
     },
 
+    /// Thrown when there was an error reading the cached versions resolved from `engines`
+    ReadEnginesCacheError {
+        file: PathBuf,
+    },
+
     /// Thrown when there was an error opening a hooks.json file
     ReadHooksError {
         file: PathBuf,
@@ -391,9 +467,19 @@ pub enum ErrorKind {
         file: PathBuf,
     },
 
+    /// Thrown when there was an error reading a `.node-version` or `.nvmrc` file
+    ReadNodeVersionFileError {
+        file: PathBuf,
+    },
+
     /// Thrown when there was an error reading the npm manifest file
     ReadNpmManifestError,
 
+    /// Thrown when there was an error reading an npm configuration file
+    ReadNpmrcError {
+        file: PathBuf,
+    },
+
     /// Thrown when there was an error reading a package configuration file
     ReadPackageConfigError {
         file: PathBuf,
@@ -404,10 +490,25 @@ pub enum ErrorKind {
         file: PathBuf,
     },
 
+    /// Thrown when a toolchain file for `volta import` could not be read
+    ReadToolchainFileError {
+        file: PathBuf,
+    },
+
+    /// Thrown when the record of trusted project hooks could not be read
+    ReadTrustedHooksError {
+        file: PathBuf,
+    },
+
     /// Thrown when unable to read the user Path environment variable from the registry
     #[cfg(windows)]
     ReadUserPathError,
 
+    /// Thrown when there was an error reading a Yarn configuration file (`.yarnrc.yml`)
+    ReadYarnrcError {
+        file: PathBuf,
+    },
+
     /// Thrown when the public registry for Node or Yarn could not be downloaded.
     RegistryFetchError {
         tool: String,
@@ -451,6 +552,11 @@ pub enum ErrorKind {
         feature: String,
     },
 
+    /// Thrown when a user tries to `volta uninstall` a tool without an exact version
+    UninstallRequiresVersion {
+        tool: String,
+    },
+
     /// Thrown when unpacking an archive (tarball or zip) fails
     UnpackArchiveError {
         tool: String,
@@ -482,6 +588,11 @@ pub enum ErrorKind {
         file: PathBuf,
     },
 
+    /// Thrown when there was an error writing the cached versions resolved from `engines`
+    WriteEnginesCacheError {
+        file: PathBuf,
+    },
+
     /// Thrown when there was an error writing the npm launcher
     WriteLauncherError {
         tool: String,
@@ -514,6 +625,16 @@ pub enum ErrorKind {
         file: PathBuf,
     },
 
+    /// Thrown when the toolchain file from `volta export` could not be written
+    WriteToolchainFileError {
+        file: PathBuf,
+    },
+
+    /// Thrown when the record of trusted project hooks could not be written
+    WriteTrustedHooksError {
+        file: PathBuf,
+    },
+
     /// Thrown when unable to write the user PATH environment variable
     #[cfg(windows)]
     WriteUserPathError,
@@ -576,6 +697,46 @@ VOLTA_BYPASS is enabled, please ensure that the command exists on your system or
 Use `npm install` or `yarn add` to select a version of {} for this project.",
                 package
             ),
+            ErrorKind::CannotUninstallDefaultNode { version } => write!(
+                f,
+                "Cannot uninstall {} because it is your default Node version.
+
+Use `volta install node@<version>` to choose a different default first.",
+                tool_version("node", version)
+            ),
+            ErrorKind::ChecksumFetchError { tool, from_url } => write!(
+                f,
+                "Could not download {} checksums
+from {}
+
+Please verify your internet connection.",
+                tool, from_url
+            ),
+            ErrorKind::ChecksumMismatch {
+                tool,
+                version,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Checksum verification failed for {}
+
+Expected SHA-256: {}
+Actual SHA-256:   {}
+
+The download may have been corrupted or tampered with. Please try again.",
+                tool_version(tool, version),
+                expected,
+                actual
+            ),
+            ErrorKind::ChecksumNotFound { file, from_url } => write!(
+                f,
+                "Could not find a checksum for {}
+in {}
+
+Please ensure the checksum file matches the distro being downloaded.",
+                file, from_url
+            ),
             ErrorKind::CompletionsOutFileError { path } => write!(
                 f,
                 "Completions file `{}` already exists.
@@ -731,6 +892,13 @@ Please ensure that the correct command is specified.",
 Please ensure that the file exists and is accessible.",
                 path.display(),
             ),
+            ErrorKind::ExtraCaCertificatesError { file } => write!(
+                f,
+                "Could not load extra CA certificates from {}
+
+Please ensure that VOLTA_EXTRA_CA_CERTS (or NODE_EXTRA_CA_CERTS) is set to a file of PEM certificates.",
+                file.display()
+            ),
             ErrorKind::HookCommandFailed { command } => write!(
                 f,
                 "Hook command '{}' indicated a failure.
@@ -757,6 +925,20 @@ Please include one of 'bin', 'prefix', or 'template'"
 Please ensure that the correct command is specified.",
                 command
             ),
+            ErrorKind::IntegrityMismatch {
+                package,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Integrity check failed for {}
+
+Expected: {}
+Actual:   {}
+
+The downloaded tarball does not match the registry metadata. Please try again.",
+                package, expected, actual
+            ),
             ErrorKind::InvalidHookCommand { command } => write!(
                 f,
                 "Invalid hook command: '{}'
@@ -822,6 +1004,22 @@ To {action} the packages '{name}' and '{version}', please {action} them in separ
                 )
             }
 
+            ErrorKind::LockError { file } => write!(
+                f,
+                "Could not lock the Volta directory
+using {}
+
+{}",
+                file.display(),
+                PERMISSIONS_CTA
+            ),
+            ErrorKind::LtsOnlyForNode { tool } => write!(
+                f,
+                "Only Node has long-term support releases
+
+Remove `--lts` to list the available versions of {}.",
+                tool
+            ),
             ErrorKind::NoBinPlatform { binary } => write!(
                 f,
                 "Platform info for executable `{}` is missing
@@ -836,12 +1034,43 @@ Please uninstall and re-install the package that provides that executable.",
 Please ensure you have a Node version selected with `volta {} node` (see `volta help {0}` for more info).",
                 command
             ),
+            ErrorKind::NoCommandLinePnpm => write!(
+                f,
+                "No pnpm version specified.
+
+Use `volta run --pnpm` to select a version (see `volta help run` for more info)."
+            ),
             ErrorKind::NoCommandLineYarn => write!(
                 f,
                 "No Yarn version specified.
 
 Use `volta run --yarn` to select a version (see `volta help run` for more info)."
             ),
+            ErrorKind::NoDefaultPnpm => write!(
+                f,
+                "pnpm is not available.
+
+Use `volta install pnpm` to select a default version (see `volta help install` for more info)."
+            ),
+            ErrorKind::NoProjectHooks => write!(
+                f,
+                "Could not find any project hooks to trust.
+
+Project hooks are read from `.volta/hooks.json` in the project root."
+            ),
+            ErrorKind::NoProjectPnpm => write!(
+                f,
+                "No pnpm version found in this project.
+
+Use `volta pin pnpm` to select a version (see `volta help pin` for more info)."
+            ),
+            ErrorKind::NoShellHook { shell } => write!(
+                f,
+                "Could not print a hook for {}.
+
+Please use `--shell bash`, `--shell zsh`, or `--shell fish` with `--hook`.",
+                shell
+            ),
             ErrorKind::NodeVersionNotFound { matching } => write!(
                 f,
                 r#"Could not find Node version matching "{}" in the version registry.
@@ -930,20 +1159,6 @@ Use `volta install yarn` to select a default version (see `volta help install` f
             ),
             // No CTA as this error is purely informational
             ErrorKind::NoVersionsFound => write!(f, "No tool versions found"),
-            ErrorKind::NpmPackFetchError { package } => write!(
-                f,
-                "Could not download '{}' via npm pack
-
-Please verify your internet connection and ensure the correct version is specified.",
-                package
-            ),
-            ErrorKind::NpmPackUnpackError { package } => write!(
-                f,
-                "Could not read archive for '{}' from npm pack.
-
-{}",
-                package, PERMISSIONS_CTA
-            ),
             ErrorKind::NpmVersionNotFound { matching } => write!(
                 f,
                 r#"Could not find Node version matching "{}" in the version registry.
@@ -951,20 +1166,6 @@ Please verify your internet connection and ensure the correct version is specifi
 Please verify that the version is correct."#,
                 matching
             ),
-            ErrorKind::NpmViewMetadataFetchError { package } => write!(
-                f,
-                "Could not download package metadata for '{}'
-
-Please ensure the requested package name is correct.",
-                package
-            ),
-            ErrorKind::NpmViewMetadataParseError { package } => write!(
-                f,
-                "Could not parse package metadata for '{}'
-
-Please ensure the requested package name is correct.",
-                package
-            ),
             ErrorKind::NpxNotAvailable { version } => write!(
                 f,
                 "'npx' is only available with npm >= 5.2.0
@@ -973,6 +1174,36 @@ This project is configured to use version {} of npm.",
                 version
             ),
             // Confirming permissions is a Weak CTA in this case, but it seems the most likely error vector
+            ErrorKind::OfflineFetchError { tool } => write!(
+                f,
+                "Could not fetch {} because Volta is in offline mode.
+
+Please fetch it while online, or unset VOLTA_OFFLINE to allow downloads.",
+                tool
+            ),
+            ErrorKind::OfflineVersionNotFound {
+                tool,
+                matching,
+                guessed_lts,
+            } => {
+                write!(
+                    f,
+                    r#"Could not find a version of {} matching "{}" in the local inventory.
+
+Volta is in offline mode, so only tools that have already been fetched can be used.
+Please fetch a matching version while online, or unset VOLTA_OFFLINE."#,
+                    tool, matching
+                )?;
+
+                if *guessed_lts {
+                    write!(
+                        f,
+                        "\n\nThere is no cached Node index, so LTS versions were assumed to be the even major versions."
+                    )?;
+                }
+
+                Ok(())
+            }
             ErrorKind::PackageInstallFailed => write!(
                 f,
                 "Could not install package dependencies.
@@ -1104,6 +1335,14 @@ Please verify the requested package and version.",
 Please supply a spec in the format `<tool name>[@<version>]`.",
                 tool_spec
             ),
+            ErrorKind::ParseToolchainFileError { file } => write!(
+                f,
+                "Could not parse toolchain file
+{}
+
+Please ensure the file was written by `volta export`.",
+                file.display()
+            ),
             ErrorKind::PersistInventoryError { tool } => write!(
                 f,
                 "Could not store {} archive in inventory cache
@@ -1111,6 +1350,20 @@ Please supply a spec in the format `<tool name>[@<version>]`.",
 {}",
                 tool, PERMISSIONS_CTA
             ),
+            ErrorKind::PnpmVersionNotFound { matching } => write!(
+                f,
+                r#"Could not find pnpm version matching "{}" in the version registry.
+
+Please verify that the version is correct."#,
+                matching
+            ),
+            ErrorKind::PrereleasePinError { version } => write!(
+                f,
+                "Could not pin node@{}, because it is a prerelease.
+
+Everyone working on the project would run this version. To pin it anyway, run `volta pin --allow-prerelease node@{}`",
+                version, version
+            ),
             ErrorKind::ProjectLocalBinaryExecError { command } => write!(
                 f,
                 "Could not execute `{}`
@@ -1171,6 +1424,15 @@ from {}
 {}",
                 dir.display(), PERMISSIONS_CTA
             ),
+            ErrorKind::ReadEnginesCacheError { file } => write!(
+                f,
+                "Could not read cached engines versions
+from {}
+
+{}",
+                file.display(),
+                PERMISSIONS_CTA
+            ),
             ErrorKind::ReadHooksError { file } => write!(
                 f,
                 "Could not read hooks file
@@ -1194,6 +1456,15 @@ from {}
                 "Could not read Node index cache expiration
 from {}
 
+{}",
+                file.display(),
+                PERMISSIONS_CTA
+            ),
+            ErrorKind::ReadNodeVersionFileError { file } => write!(
+                f,
+                "Could not read Node version file
+from {}
+
 {}",
                 file.display(),
                 PERMISSIONS_CTA
@@ -1204,6 +1475,15 @@ from {}
 
 Please ensure the version of Node is correct."
             ),
+            ErrorKind::ReadNpmrcError { file } => write!(
+                f,
+                "Could not read npm configuration file
+from {}
+
+{}",
+                file.display(),
+                PERMISSIONS_CTA
+            ),
             ErrorKind::ReadPackageConfigError { file } => write!(
                 f,
                 "Could not read package configuration file
@@ -1218,6 +1498,24 @@ from {}
                 "Could not read default platform file
 from {}
 
+{}",
+                file.display(),
+                PERMISSIONS_CTA
+            ),
+            ErrorKind::ReadToolchainFileError { file } => write!(
+                f,
+                "Could not read toolchain file
+from {}
+
+{}",
+                file.display(),
+                PERMISSIONS_CTA
+            ),
+            ErrorKind::ReadTrustedHooksError { file } => write!(
+                f,
+                "Could not read trusted project hooks
+from {}
+
 {}",
                 file.display(),
                 PERMISSIONS_CTA
@@ -1229,6 +1527,15 @@ from {}
 
 Please ensure you have access to the your environment variables."
             ),
+            ErrorKind::ReadYarnrcError { file } => write!(
+                f,
+                "Could not read Yarn configuration file
+from {}
+
+{}",
+                file.display(),
+                PERMISSIONS_CTA
+            ),
             ErrorKind::RegistryFetchError { tool, from_url } => write!(
                 f,
                 "Could not download {} version registry
@@ -1299,6 +1606,13 @@ at {}
             ErrorKind::Unimplemented { feature } => {
                 write!(f, "{} is not supported yet.", feature)
             }
+            ErrorKind::UninstallRequiresVersion { tool } => write!(
+                f,
+                "Could not determine which version of {} to uninstall.
+
+Please specify an exact version, for example `volta uninstall {}@1.2.3`.",
+                tool, tool
+            ),
             ErrorKind::UnpackArchiveError { tool, version } => write!(
                 f,
                 "Could not unpack {} v{}
@@ -1341,6 +1655,15 @@ to {}
                 "Could not write bundled npm version
 to {}
 
+{}",
+                file.display(),
+                PERMISSIONS_CTA
+            ),
+            ErrorKind::WriteEnginesCacheError { file } => write!(
+                f,
+                "Could not write cached engines versions
+to {}
+
 {}",
                 file.display(),
                 PERMISSIONS_CTA
@@ -1399,6 +1722,24 @@ to {}
                 "Could not save platform settings
 to {}
 
+{}",
+                file.display(),
+                PERMISSIONS_CTA
+            ),
+            ErrorKind::WriteToolchainFileError { file } => write!(
+                f,
+                "Could not write toolchain file
+to {}
+
+{}",
+                file.display(),
+                PERMISSIONS_CTA
+            ),
+            ErrorKind::WriteTrustedHooksError { file } => write!(
+                f,
+                "Could not save trusted project hooks
+to {}
+
 {}",
                 file.display(),
                 PERMISSIONS_CTA
@@ -1438,6 +1779,10 @@ impl ErrorKind {
             ErrorKind::BuildPathError => ExitCode::EnvironmentError,
             ErrorKind::BypassError { .. } => ExitCode::ExecutionFailure,
             ErrorKind::CannotPinPackage { .. } => ExitCode::InvalidArguments,
+            ErrorKind::CannotUninstallDefaultNode { .. } => ExitCode::InvalidArguments,
+            ErrorKind::ChecksumFetchError { .. } => ExitCode::NetworkError,
+            ErrorKind::ChecksumMismatch { .. } => ExitCode::NetworkError,
+            ErrorKind::ChecksumNotFound { .. } => ExitCode::NetworkError,
             ErrorKind::CompletionsOutFileError { .. } => ExitCode::InvalidArguments,
             ErrorKind::ContainingDirError { .. } => ExitCode::FileSystemError,
             ErrorKind::CouldNotDetermineTool => ExitCode::UnknownError,
@@ -1457,17 +1802,26 @@ impl ErrorKind {
             ErrorKind::ExecuteHookError { .. } => ExitCode::ExecutionFailure,
             ErrorKind::ExtensionCycleError { .. } => ExitCode::ConfigurationError,
             ErrorKind::ExtensionPathError { .. } => ExitCode::FileSystemError,
+            ErrorKind::ExtraCaCertificatesError { .. } => ExitCode::ConfigurationError,
             ErrorKind::HookCommandFailed { .. } => ExitCode::ConfigurationError,
             ErrorKind::HookMultipleFieldsSpecified => ExitCode::ConfigurationError,
             ErrorKind::HookNoFieldsSpecified => ExitCode::ConfigurationError,
             ErrorKind::HookPathError { .. } => ExitCode::ConfigurationError,
+            ErrorKind::IntegrityMismatch { .. } => ExitCode::NetworkError,
             ErrorKind::InvalidHookCommand { .. } => ExitCode::ExecutableNotFound,
             ErrorKind::InvalidHookOutput { .. } => ExitCode::ExecutionFailure,
             ErrorKind::InvalidInvocation { .. } => ExitCode::InvalidArguments,
             ErrorKind::InvalidToolName { .. } => ExitCode::InvalidArguments,
+            ErrorKind::LockError { .. } => ExitCode::FileSystemError,
+            ErrorKind::LtsOnlyForNode { .. } => ExitCode::InvalidArguments,
             ErrorKind::NoBinPlatform { .. } => ExitCode::ExecutionFailure,
             ErrorKind::NoBundledNpm { .. } => ExitCode::ConfigurationError,
+            ErrorKind::NoCommandLinePnpm => ExitCode::ConfigurationError,
             ErrorKind::NoCommandLineYarn => ExitCode::ConfigurationError,
+            ErrorKind::NoDefaultPnpm => ExitCode::ConfigurationError,
+            ErrorKind::NoProjectHooks => ExitCode::ConfigurationError,
+            ErrorKind::NoProjectPnpm => ExitCode::ConfigurationError,
+            ErrorKind::NoShellHook { .. } => ExitCode::InvalidArguments,
             ErrorKind::NodeVersionNotFound { .. } => ExitCode::NoVersionMatch,
             ErrorKind::NoGlobalInstalls { .. } => ExitCode::InvalidArguments,
             ErrorKind::NoHomeEnvironmentVar => ExitCode::EnvironmentError,
@@ -1482,12 +1836,10 @@ impl ErrorKind {
             ErrorKind::NotInPackage => ExitCode::ConfigurationError,
             ErrorKind::NoDefaultYarn => ExitCode::ConfigurationError,
             ErrorKind::NoVersionsFound => ExitCode::NoVersionMatch,
-            ErrorKind::NpmPackFetchError { .. } => ExitCode::NetworkError,
-            ErrorKind::NpmPackUnpackError { .. } => ExitCode::FileSystemError,
             ErrorKind::NpmVersionNotFound { .. } => ExitCode::NoVersionMatch,
-            ErrorKind::NpmViewMetadataFetchError { .. } => ExitCode::NetworkError,
-            ErrorKind::NpmViewMetadataParseError { .. } => ExitCode::UnknownError,
             ErrorKind::NpxNotAvailable { .. } => ExitCode::ExecutableNotFound,
+            ErrorKind::OfflineFetchError { .. } => ExitCode::NetworkError,
+            ErrorKind::OfflineVersionNotFound { .. } => ExitCode::NoVersionMatch,
             ErrorKind::PackageInstallFailed => ExitCode::FileSystemError,
             ErrorKind::PackageMetadataFetchError { .. } => ExitCode::NetworkError,
             ErrorKind::PackageNotFound { .. } => ExitCode::InvalidArguments,
@@ -1506,7 +1858,10 @@ impl ErrorKind {
             ErrorKind::ParsePackageConfigError => ExitCode::UnknownError,
             ErrorKind::ParsePackageMetadataError { .. } => ExitCode::UnknownError,
             ErrorKind::ParsePlatformError => ExitCode::ConfigurationError,
+            ErrorKind::ParseToolchainFileError { .. } => ExitCode::ConfigurationError,
             ErrorKind::PersistInventoryError { .. } => ExitCode::FileSystemError,
+            ErrorKind::PnpmVersionNotFound { .. } => ExitCode::NoVersionMatch,
+            ErrorKind::PrereleasePinError { .. } => ExitCode::InvalidArguments,
             ErrorKind::ProjectLocalBinaryExecError { .. } => ExitCode::ExecutionFailure,
             ErrorKind::ProjectLocalBinaryNotFound { .. } => ExitCode::FileSystemError,
             ErrorKind::PublishHookBothUrlAndBin => ExitCode::ConfigurationError,
@@ -1515,14 +1870,20 @@ impl ErrorKind {
             ErrorKind::ReadBinConfigError { .. } => ExitCode::FileSystemError,
             ErrorKind::ReadDefaultNpmError { .. } => ExitCode::FileSystemError,
             ErrorKind::ReadDirError { .. } => ExitCode::FileSystemError,
+            ErrorKind::ReadEnginesCacheError { .. } => ExitCode::FileSystemError,
             ErrorKind::ReadHooksError { .. } => ExitCode::FileSystemError,
             ErrorKind::ReadNodeIndexCacheError { .. } => ExitCode::FileSystemError,
             ErrorKind::ReadNodeIndexExpiryError { .. } => ExitCode::FileSystemError,
+            ErrorKind::ReadNodeVersionFileError { .. } => ExitCode::FileSystemError,
             ErrorKind::ReadNpmManifestError => ExitCode::UnknownError,
+            ErrorKind::ReadNpmrcError { .. } => ExitCode::FileSystemError,
             ErrorKind::ReadPackageConfigError { .. } => ExitCode::FileSystemError,
             ErrorKind::ReadPlatformError { .. } => ExitCode::FileSystemError,
+            ErrorKind::ReadToolchainFileError { .. } => ExitCode::FileSystemError,
+            ErrorKind::ReadTrustedHooksError { .. } => ExitCode::FileSystemError,
             #[cfg(windows)]
             ErrorKind::ReadUserPathError => ExitCode::EnvironmentError,
+            ErrorKind::ReadYarnrcError { .. } => ExitCode::FileSystemError,
             ErrorKind::RegistryFetchError { .. } => ExitCode::NetworkError,
             ErrorKind::RunShimDirectly => ExitCode::InvalidArguments,
             ErrorKind::SetupToolImageError { .. } => ExitCode::FileSystemError,
@@ -1533,6 +1894,7 @@ impl ErrorKind {
             ErrorKind::StringifyPlatformError => ExitCode::UnknownError,
             ErrorKind::StringifyToolchainError => ExitCode::UnknownError,
             ErrorKind::Unimplemented { .. } => ExitCode::UnknownError,
+            ErrorKind::UninstallRequiresVersion { .. } => ExitCode::InvalidArguments,
             ErrorKind::UnpackArchiveError { .. } => ExitCode::UnknownError,
             ErrorKind::UnrecognizedShell { .. } => ExitCode::EnvironmentError,
             ErrorKind::UnspecifiedPostscript => ExitCode::EnvironmentError,
@@ -1540,12 +1902,15 @@ impl ErrorKind {
             ErrorKind::VersionParseError { .. } => ExitCode::NoVersionMatch,
             ErrorKind::WriteBinConfigError { .. } => ExitCode::FileSystemError,
             ErrorKind::WriteDefaultNpmError { .. } => ExitCode::FileSystemError,
+            ErrorKind::WriteEnginesCacheError { .. } => ExitCode::FileSystemError,
             ErrorKind::WriteLauncherError { .. } => ExitCode::FileSystemError,
             ErrorKind::WriteNodeIndexCacheError { .. } => ExitCode::FileSystemError,
             ErrorKind::WriteNodeIndexExpiryError { .. } => ExitCode::FileSystemError,
             ErrorKind::WritePackageConfigError { .. } => ExitCode::FileSystemError,
             ErrorKind::WritePackageShasumError { .. } => ExitCode::FileSystemError,
             ErrorKind::WritePlatformError { .. } => ExitCode::FileSystemError,
+            ErrorKind::WriteToolchainFileError { .. } => ExitCode::FileSystemError,
+            ErrorKind::WriteTrustedHooksError { .. } => ExitCode::FileSystemError,
             #[cfg(windows)]
             ErrorKind::WriteUserPathError => ExitCode::EnvironmentError,
             ErrorKind::YarnLatestFetchError { .. } => ExitCode::NetworkError,
@@ -1,15 +1,22 @@
 //! Events for the sessions in executables and shims and everything
 
 use std::env;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use attohttpc::Response;
+use log::debug;
 use serde::Serialize;
 
 use crate::error::{ExitCode, VoltaError};
 use crate::hook::Publish;
 use crate::monitor::Monitor;
+use crate::offline::is_offline;
 use crate::session::ActivityKind;
 
+/// How long to wait for the publish URL to respond, so that a slow endpoint doesn't hold up the
+/// tool that was being run
+const PUBLISH_TIMEOUT: Duration = Duration::from_secs(2);
+
 // the Event data that is serialized to JSON and sent the plugin
 #[derive(Serialize)]
 pub struct Event {
@@ -130,8 +137,7 @@ impl EventLog {
 
     pub fn publish(&self, plugin: Option<&Publish>) {
         match plugin {
-            // Note: This call to unimplemented is left in, as it's not a Fallible operation that can use ErrorKind::Unimplemented
-            Some(&Publish::Url(_)) => unimplemented!(),
+            Some(&Publish::Url(ref url)) => send_events(url, &self.events),
             Some(&Publish::Bin(ref command)) => {
                 let mut monitor = Monitor::new(command);
                 monitor.send_events(&self.events);
@@ -141,6 +147,36 @@ impl EventLog {
     }
 }
 
+/// POST the events to the given URL as a JSON array
+///
+/// Publishing is best-effort: failures are logged rather than reported, since they shouldn't
+/// affect the outcome of the command the user ran.
+fn send_events(url: &str, events: &[Event]) {
+    if is_offline() {
+        debug!("Skipping publishing events to '{}' in offline mode", url);
+        return;
+    }
+
+    let request = match http_client::post(url) {
+        Ok(request) => request,
+        Err(error) => {
+            debug!("Unable to publish events to '{}'\n{}", url, error);
+            return;
+        }
+    };
+
+    let result = request
+        .timeout(PUBLISH_TIMEOUT)
+        .json(&events)
+        .and_then(|request| request.send())
+        .and_then(Response::error_for_status);
+
+    match result {
+        Ok(_) => debug!("Published events to '{}'", url),
+        Err(error) => debug!("Unable to publish events to '{}'\n{}", url, error),
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
 
@@ -8,6 +8,9 @@ use std::path::Path;
 
 use crate::error::{Context, ErrorKind, Fallible};
 use crate::layout::volta_home;
+use crate::style::progress_spinner;
+use fs2::FileExt;
+use fs_utils::ensure_containing_dir_exists;
 use tempfile::{tempdir_in, NamedTempFile, TempDir};
 
 /// Opens a file, creating it if it doesn't exist
@@ -30,6 +33,14 @@ pub fn ensure_dir_does_not_exist<P: AsRef<Path>>(path: &P) -> Fallible<()> {
     Ok(())
 }
 
+/// This deletes the input file, if it exists
+pub fn ensure_file_does_not_exist<P: AsRef<Path>>(path: &P) -> Fallible<()> {
+    if path.as_ref().exists() {
+        fs::remove_file(path).with_context(delete_file_error(path))?;
+    }
+    Ok(())
+}
+
 pub fn delete_dir_error<P: AsRef<Path>>(directory: &P) -> impl FnOnce() -> ErrorKind {
     let directory = directory.as_ref().to_path_buf();
     || ErrorKind::DeleteDirectoryError { directory }
@@ -105,6 +116,59 @@ pub fn create_staging_dir() -> Fallible<TempDir> {
     })
 }
 
+/// An advisory lock on a file, which is released when it is dropped
+///
+/// Other Volta processes respect the lock, so it prevents them from changing the same part of the
+/// Volta directory at the same time.
+pub struct FileLock {
+    file: File,
+}
+
+impl FileLock {
+    /// Acquires an exclusive lock on the given file, creating it if necessary. If another process
+    /// holds the lock, this waits for it to be released.
+    pub fn acquire(path: &Path) -> Fallible<Self> {
+        let lock_error = || ErrorKind::LockError {
+            file: path.to_owned(),
+        };
+
+        ensure_containing_dir_exists(&path).with_context(lock_error)?;
+        let file = fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .open(path)
+            .with_context(lock_error)?;
+
+        if file.try_lock_exclusive().is_err() {
+            let spinner = progress_spinner("Waiting for another Volta process to finish");
+            let result = file.lock_exclusive();
+            spinner.finish_and_clear();
+            result.with_context(lock_error)?;
+        }
+
+        Ok(FileLock { file })
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        // Closing the file releases the lock anyway, so an error here can be safely ignored
+        let _ = self.file.unlock();
+    }
+}
+
+/// Locks fetching the given version of a tool, so that concurrent processes don't download and
+/// unpack the same version into the same location
+pub fn lock_tool_version(tool: &str, version: &str) -> Fallible<FileLock> {
+    FileLock::acquire(&volta_home()?.tool_lock_file(tool, version))
+}
+
+/// Locks the Volta directory as a whole, for changes that affect more than a single tool
+pub fn lock_volta_home() -> Fallible<FileLock> {
+    FileLock::acquire(volta_home()?.global_lock_file())
+}
+
 /// Create a symlink. The `dst` path will be a symbolic link pointing to the `src` path.
 pub fn symlink_file<S, D>(src: S, dest: D) -> io::Result<()>
 where
@@ -139,3 +203,31 @@ pub fn set_executable(bin: &Path) -> io::Result<()> {
 pub fn set_executable(_bin: &Path) -> io::Result<()> {
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::FileLock;
+    use std::sync::mpsc::channel;
+    use std::thread;
+    use std::time::Duration;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_file_lock_waits_for_release() {
+        let dir = tempdir().expect("could not create temp dir");
+        let path = dir.path().join("locks").join("node-12.16.1.lock");
+        let lock = FileLock::acquire(&path).expect("could not acquire lock");
+
+        let (sender, receiver) = channel();
+        let waiting_path = path.clone();
+        let waiter = thread::spawn(move || {
+            let _lock = FileLock::acquire(&waiting_path).expect("could not acquire lock");
+            sender.send(()).unwrap();
+        });
+
+        assert!(receiver.recv_timeout(Duration::from_millis(200)).is_err());
+        drop(lock);
+        assert!(receiver.recv_timeout(Duration::from_secs(5)).is_ok());
+        waiter.join().unwrap();
+    }
+}
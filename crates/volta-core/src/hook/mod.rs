@@ -4,17 +4,18 @@ use std::borrow::Cow;
 use std::fs::File;
 use std::iter::once;
 use std::marker::PhantomData;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::error::{Context, ErrorKind, Fallible};
 use crate::layout::volta_home;
 use crate::project::Project;
-use crate::tool::{Node, Npm, Package, Tool, Yarn};
+use crate::tool::{Node, Npm, Package, Pnpm, Tool, Yarn};
 use lazycell::LazyCell;
 use log::debug;
 
 pub(crate) mod serial;
 pub mod tool;
+pub mod trust;
 
 /// A hook for publishing Volta events.
 #[derive(PartialEq, Debug)]
@@ -26,6 +27,13 @@ pub enum Publish {
     Bin(String),
 }
 
+/// The location of the hooks file for a project (or workspace) root
+pub fn project_hooks_file(root: &Path) -> PathBuf {
+    let mut path = root.join(".volta");
+    path.push("hooks.json");
+    path
+}
+
 /// Lazily loaded Volta hook configuration
 pub struct LazyHookConfig {
     settings: LazyCell<HookConfig>,
@@ -50,6 +58,7 @@ impl LazyHookConfig {
 pub struct HookConfig {
     node: Option<ToolHooks<Node>>,
     npm: Option<ToolHooks<Npm>>,
+    pnpm: Option<ToolHooks<Pnpm>>,
     yarn: Option<ToolHooks<Yarn>>,
     package: Option<ToolHooks<Package>>,
     events: Option<EventHooks>,
@@ -59,6 +68,8 @@ pub struct HookConfig {
 pub struct ToolHooks<T: Tool> {
     /// The hook for resolving the URL for a distro version
     pub distro: Option<tool::DistroHook>,
+    /// The hook for resolving the URL for the checksums of a distro version
+    pub checksum: Option<tool::DistroHook>,
     /// The hook for resolving the URL for the latest version
     pub latest: Option<tool::MetadataHook>,
     /// The hook for resolving the Tool Index URL
@@ -72,6 +83,7 @@ impl<T: Tool> ToolHooks<T> {
     fn merge(self, other: Self) -> Self {
         Self {
             distro: self.distro.or(other.distro),
+            checksum: self.checksum.or(other.checksum),
             latest: self.latest.or(other.latest),
             index: self.index.or(other.index),
             phantom: PhantomData,
@@ -98,6 +110,10 @@ impl HookConfig {
         self.npm.as_ref()
     }
 
+    pub fn pnpm(&self) -> Option<&ToolHooks<Pnpm>> {
+        self.pnpm.as_ref()
+    }
+
     pub fn yarn(&self) -> Option<&ToolHooks<Yarn>> {
         self.yarn.as_ref()
     }
@@ -112,7 +128,10 @@ impl HookConfig {
 
     /// Returns the current hooks, which are a merge between the user hooks and
     /// the project hooks (if any).
-    fn current(project: Option<&Project>) -> Fallible<Self> {
+    ///
+    /// Project hooks that run commands or change where tools are downloaded from are skipped
+    /// unless they have been approved with `volta trust`.
+    pub(crate) fn current(project: Option<&Project>) -> Fallible<Self> {
         let default_hooks_file = volta_home()?.default_hooks_file();
 
         // Since `from_paths` expects the paths to be sorted in descending precedence order, we
@@ -120,17 +139,19 @@ impl HookConfig {
         // precedence order)
         // See the per-project configuration RFC for more details on the configuration precedence:
         // https://github.com/volta-cli/rfcs/blob/master/text/0033-per-project-config.md#configuration-precedence
-        let paths = project
-            .into_iter()
-            .flat_map(Project::workspace_roots)
-            .map(|root| {
-                let mut path = root.join(".volta");
-                path.push("hooks.json");
-                Cow::Owned(path)
-            })
-            .chain(once(Cow::Borrowed(default_hooks_file)));
+        let mut paths = Vec::new();
+        for root in project.into_iter().flat_map(Project::workspace_roots) {
+            let hooks_file = project_hooks_file(root);
+            if trust::allows(&hooks_file)? {
+                paths.push(Cow::Owned(hooks_file));
+            }
+        }
 
-        Self::from_paths(paths)
+        Self::from_paths(
+            paths
+                .into_iter()
+                .chain(once(Cow::Borrowed(default_hooks_file))),
+        )
     }
 
     /// Returns the merged hooks loaded from an iterator of potential hook files
@@ -166,6 +187,7 @@ impl HookConfig {
                     Self {
                         node: None,
                         npm: None,
+                        pnpm: None,
                         yarn: None,
                         package: None,
                         events: None,
@@ -199,6 +221,7 @@ impl HookConfig {
         Self {
             node: merge_hooks!(self, other, node),
             npm: merge_hooks!(self, other, npm),
+            pnpm: merge_hooks!(self, other, pnpm),
             yarn: merge_hooks!(self, other, yarn),
             package: merge_hooks!(self, other, package),
             events: merge_hooks!(self, other, events),
@@ -224,7 +247,7 @@ impl EventHooks {
 #[cfg(test)]
 pub mod tests {
 
-    use super::{tool, HookConfig, Publish};
+    use super::{serial, tool, HookConfig, Publish};
     use std::path::PathBuf;
 
     fn fixture_path(fixture_dir: &str) -> PathBuf {
@@ -234,6 +257,23 @@ pub mod tests {
         cargo_manifest_dir
     }
 
+    #[test]
+    fn test_needs_trust() {
+        let fixture_dir = fixture_path("hooks");
+        let needs_trust = |file: &str| {
+            let contents = std::fs::read_to_string(fixture_dir.join(file)).unwrap();
+            serde_json::from_str::<serial::RawHookConfig>(&contents)
+                .unwrap()
+                .needs_trust()
+        };
+
+        assert!(needs_trust("bins.json"));
+        assert!(needs_trust("project/.volta/hooks.json"));
+        assert!(needs_trust("templates.json"));
+        assert!(needs_trust("prefixes.json"));
+        assert!(!needs_trust("event_url.json"));
+    }
+
     #[test]
     fn test_from_str_event_url() {
         let fixture_dir = fixture_path("hooks");
@@ -252,6 +292,7 @@ pub mod tests {
         let bin_file = fixture_dir.join("bins.json");
         let hooks = HookConfig::from_file(&bin_file).unwrap().unwrap();
         let node = hooks.node.unwrap();
+        let pnpm = hooks.pnpm.unwrap();
         let yarn = hooks.yarn.unwrap();
 
         assert_eq!(
@@ -275,6 +316,14 @@ pub mod tests {
                 base_path: fixture_dir.clone(),
             })
         );
+        assert_eq!(
+            pnpm.distro,
+            Some(tool::DistroHook::Bin {
+                bin: "/bin/to/pnpm/distro".to_string(),
+                base_path: fixture_dir.clone(),
+            })
+        );
+        assert_eq!(pnpm.latest, None);
         assert_eq!(
             yarn.distro,
             Some(tool::DistroHook::Bin {
@@ -361,6 +410,12 @@ pub mod tests {
                 "http://localhost/node/distro/{{version}}/".to_string()
             ))
         );
+        assert_eq!(
+            node.checksum,
+            Some(tool::DistroHook::Template(
+                "http://localhost/node/checksum/{{version}}/".to_string()
+            ))
+        );
         assert_eq!(
             node.latest,
             Some(tool::MetadataHook::Template(
@@ -4,7 +4,7 @@ use std::path::Path;
 
 use super::tool;
 use crate::error::{ErrorKind, Fallible, VoltaError};
-use crate::tool::{Node, Npm, Package, Tool, Yarn};
+use crate::tool::{Node, Npm, Package, Pnpm, Tool, Yarn};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize)]
@@ -101,6 +101,7 @@ impl TryFrom<RawPublishHook> for super::Publish {
 pub struct RawHookConfig {
     pub node: Option<RawToolHooks<Node>>,
     pub npm: Option<RawToolHooks<Npm>>,
+    pub pnpm: Option<RawToolHooks<Pnpm>>,
     pub yarn: Option<RawToolHooks<Yarn>>,
     pub packages: Option<RawToolHooks<Package>>,
     pub events: Option<RawEventHooks>,
@@ -126,6 +127,7 @@ impl TryFrom<RawEventHooks> for super::EventHooks {
 #[serde(rename = "tool")]
 pub struct RawToolHooks<T: Tool> {
     pub distro: Option<RawResolveHook>,
+    pub checksum: Option<RawResolveHook>,
     pub latest: Option<RawResolveHook>,
     pub index: Option<RawResolveHook>,
 
@@ -134,9 +136,31 @@ pub struct RawToolHooks<T: Tool> {
 }
 
 impl RawHookConfig {
+    /// Determines whether any of the hooks run a command or change where tools are downloaded
+    /// from, rather than only reporting events to a URL
+    pub fn needs_trust(&self) -> bool {
+        let tool_hooks = [
+            self.node.as_ref().map_or(false, RawToolHooks::has_hooks),
+            self.npm.as_ref().map_or(false, RawToolHooks::has_hooks),
+            self.pnpm.as_ref().map_or(false, RawToolHooks::has_hooks),
+            self.yarn.as_ref().map_or(false, RawToolHooks::has_hooks),
+            self.packages
+                .as_ref()
+                .map_or(false, RawToolHooks::has_hooks),
+        ];
+        let publish_bin = self
+            .events
+            .as_ref()
+            .and_then(|events| events.publish.as_ref())
+            .map_or(false, |publish| publish.bin.is_some());
+
+        tool_hooks.iter().any(|&hooks| hooks) || publish_bin
+    }
+
     pub fn into_hook_config(self, base_dir: &Path) -> Fallible<super::HookConfig> {
         let node = self.node.map(|n| n.into_tool_hooks(base_dir)).transpose()?;
         let npm = self.npm.map(|n| n.into_tool_hooks(base_dir)).transpose()?;
+        let pnpm = self.pnpm.map(|p| p.into_tool_hooks(base_dir)).transpose()?;
         let yarn = self.yarn.map(|y| y.into_tool_hooks(base_dir)).transpose()?;
         let package = self
             .packages
@@ -146,6 +170,7 @@ impl RawHookConfig {
         Ok(super::HookConfig {
             node,
             npm,
+            pnpm,
             yarn,
             package,
             events,
@@ -154,11 +179,22 @@ impl RawHookConfig {
 }
 
 impl<T: Tool> RawToolHooks<T> {
+    /// Every tool hook determines a URL that is fetched, or the checksum that verifies it
+    fn has_hooks(&self) -> bool {
+        [&self.distro, &self.checksum, &self.latest, &self.index]
+            .iter()
+            .any(|hook| hook.is_some())
+    }
+
     pub fn into_tool_hooks(self, base_dir: &Path) -> Fallible<super::ToolHooks<T>> {
         let distro = self
             .distro
             .map(|d| d.into_distro_hook(base_dir))
             .transpose()?;
+        let checksum = self
+            .checksum
+            .map(|c| c.into_distro_hook(base_dir))
+            .transpose()?;
         let latest = self
             .latest
             .map(|d| d.into_metadata_hook(base_dir))
@@ -170,6 +206,7 @@ impl<T: Tool> RawToolHooks<T> {
 
         Ok(super::ToolHooks {
             distro,
+            checksum,
             latest,
             index,
             phantom: PhantomData,
@@ -6,7 +6,7 @@ use std::process::Stdio;
 
 use crate::command::create_command;
 use crate::error::{Context, ErrorKind, Fallible};
-use crate::tool::{NODE_DISTRO_ARCH, NODE_DISTRO_OS};
+use crate::tool::{NodeDistro, NODE_DISTRO_OS};
 use cmdline_words_parser::StrExt;
 use dunce::canonicalize;
 use lazy_static::lazy_static;
@@ -37,7 +37,7 @@ impl DistroHook {
         match &self {
             DistroHook::Prefix(prefix) => Ok(format!("{}{}", prefix, filename)),
             DistroHook::Template(template) => Ok(template
-                .replace(ARCH_TEMPLATE, NODE_DISTRO_ARCH)
+                .replace(ARCH_TEMPLATE, &NodeDistro::current().arch())
                 .replace(OS_TEMPLATE, NODE_DISTRO_OS)
                 .replace(VERSION_TEMPLATE, &version.to_string())),
             DistroHook::Bin { bin, base_path } => {
@@ -61,7 +61,7 @@ impl MetadataHook {
         match &self {
             MetadataHook::Prefix(prefix) => Ok(format!("{}{}", prefix, filename)),
             MetadataHook::Template(template) => Ok(template
-                .replace(ARCH_TEMPLATE, NODE_DISTRO_ARCH)
+                .replace(ARCH_TEMPLATE, &NodeDistro::current().arch())
                 .replace(OS_TEMPLATE, NODE_DISTRO_OS)),
             MetadataHook::Bin { bin, base_path } => execute_binary(bin, base_path, None),
         }
@@ -128,7 +128,7 @@ fn execute_binary(bin: &str, base_path: &Path, extra_arg: Option<String>) -> Fal
 #[cfg(test)]
 pub mod tests {
     use super::{DistroHook, MetadataHook};
-    use crate::tool::{NODE_DISTRO_ARCH, NODE_DISTRO_OS};
+    use crate::tool::{NodeDistro, NODE_DISTRO_OS};
     use semver::Version;
 
     #[test]
@@ -154,7 +154,7 @@ pub mod tests {
         let expected = format!(
             "http://localhost/node/{}/{}/{}/node.tar.gz",
             NODE_DISTRO_OS,
-            NODE_DISTRO_ARCH,
+            NodeDistro::current().arch(),
             version.to_string()
         );
 
@@ -183,7 +183,8 @@ pub mod tests {
             MetadataHook::Template("http://localhost/node/{{os}}/{{arch}}/index.json".to_string());
         let expected = format!(
             "http://localhost/node/{}/{}/index.json",
-            NODE_DISTRO_OS, NODE_DISTRO_ARCH
+            NODE_DISTRO_OS,
+            NodeDistro::current().arch()
         );
 
         assert_eq!(
@@ -0,0 +1,100 @@
+//! Provides the record of project hooks that the user has approved with `volta trust`
+//!
+//! Hooks with a `bin` run a command on the user's machine, and tool hooks decide where tools are
+//! downloaded from and how they are verified, so project hooks that contain either are only loaded
+//! once the user has approved the exact contents of the hooks file. Any change to the file after
+//! that requires approval again.
+
+use std::collections::BTreeMap;
+use std::fs::write;
+use std::path::Path;
+
+use super::serial::RawHookConfig;
+use crate::error::{Context, ErrorKind, Fallible};
+use crate::fs::read_file;
+use crate::layout::volta_home;
+use log::warn;
+use sha2::{Digest, Sha256};
+
+/// Determines whether the hooks in a project hooks file may be loaded
+///
+/// Hooks that only publish events to a URL can't affect what is run, so they are always allowed.
+/// A file that can't be parsed is also allowed here, so that loading it reports the parse error.
+pub(crate) fn allows(hooks_file: &Path) -> Fallible<bool> {
+    let contents = match read_file(hooks_file).with_context(|| ErrorKind::ReadHooksError {
+        file: hooks_file.to_owned(),
+    })? {
+        Some(contents) => contents,
+        None => return Ok(true),
+    };
+
+    let needs_trust = serde_json::from_str::<RawHookConfig>(&contents)
+        .map(|raw| raw.needs_trust())
+        .unwrap_or(false);
+    if !needs_trust {
+        return Ok(true);
+    }
+
+    match load()?.get(&key(hooks_file)) {
+        Some(hash) if *hash == content_hash(&contents) => Ok(true),
+        Some(_) => {
+            warn!(
+                "Ignoring the hooks in {}, because they have changed since they were trusted.
+Review the changes, then run `volta trust` to approve them again.",
+                hooks_file.display()
+            );
+            Ok(false)
+        }
+        None => {
+            warn!(
+                "Ignoring the hooks in {}, because they have not been trusted.
+Project hooks can run commands and change where tools are downloaded from. Review the hooks, then run `volta trust` to approve them.",
+                hooks_file.display()
+            );
+            Ok(false)
+        }
+    }
+}
+
+/// Records the current contents of a project hooks file as trusted
+pub fn trust(hooks_file: &Path) -> Fallible<()> {
+    let contents = read_file(hooks_file)
+        .with_context(|| ErrorKind::ReadHooksError {
+            file: hooks_file.to_owned(),
+        })?
+        .ok_or(ErrorKind::NoProjectHooks)?;
+
+    let mut trusted = load()?;
+    trusted.insert(key(hooks_file), content_hash(&contents));
+
+    let trusted_file = volta_home()?.trusted_hooks_file();
+    let src = serde_json::to_string_pretty(&trusted).with_context(|| {
+        ErrorKind::WriteTrustedHooksError {
+            file: trusted_file.to_owned(),
+        }
+    })?;
+    write(trusted_file, src).with_context(|| ErrorKind::WriteTrustedHooksError {
+        file: trusted_file.to_owned(),
+    })
+}
+
+/// Loads the hashes of the trusted hooks files, keyed by path
+fn load() -> Fallible<BTreeMap<String, String>> {
+    let trusted_file = volta_home()?.trusted_hooks_file();
+    let read_error = || ErrorKind::ReadTrustedHooksError {
+        file: trusted_file.to_owned(),
+    };
+
+    match read_file(trusted_file).with_context(read_error)? {
+        Some(contents) => serde_json::from_str(&contents).with_context(read_error),
+        None => Ok(BTreeMap::new()),
+    }
+}
+
+fn key(hooks_file: &Path) -> String {
+    hooks_file.to_string_lossy().into_owned()
+}
+
+fn content_hash(contents: &str) -> String {
+    hex::encode(Sha256::digest(contents.as_bytes()))
+}
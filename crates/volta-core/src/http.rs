@@ -0,0 +1,16 @@
+//! Provides HTTP requests with the network settings from the environment (proxies and extra CA
+//! certificates), so that every download works the same way
+
+use crate::error::{ErrorKind, Fallible, VoltaError};
+use attohttpc::RequestBuilder;
+use http_client::ClientError;
+
+/// Starts building a GET request to the given URL
+pub fn get(url: &str) -> Fallible<RequestBuilder> {
+    http_client::get(url).map_err(client_error)
+}
+
+fn client_error(error: ClientError) -> VoltaError {
+    let file = error.file().to_owned();
+    VoltaError::from_source(error, ErrorKind::ExtraCaCertificatesError { file })
+}
@@ -1,25 +1,32 @@
 //! Provides types for working with Volta's _inventory_, the local repository
 //! of available tool versions.
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::Path;
 
 use crate::error::{Context, ErrorKind, Fallible};
 use crate::fs::read_dir_eager;
 use crate::layout::volta_home;
-use crate::tool::PackageConfig;
+use crate::tool::{NodeDistro, PackageConfig};
 use crate::version::parse_version;
 use log::debug;
 use semver::Version;
 use walkdir::WalkDir;
 
 /// Checks if a given Node version image is available on the local machine
+///
+/// The image only counts if it was built for the flavor of Node distro that runs on this system,
+/// so that an image unpacked for another flavor is replaced rather than reused.
 pub fn node_available(version: &Version) -> Fallible<bool> {
-    volta_home().map(|home| {
-        home.node_image_root_dir()
-            .join(version.to_string())
-            .exists()
-    })
+    let version = version.to_string();
+    let exists = volta_home()?.node_image_dir(&version).exists();
+
+    // Detecting the flavor is too slow to do on every shim call, so the image is only compared
+    // against a flavor that is already known
+    match NodeDistro::known() {
+        Some(distro) if exists => Ok(NodeDistro::of_image(&version)? == distro),
+        _ => Ok(exists),
+    }
 }
 
 /// Collects a set of all Node versions fetched on the local machine
@@ -47,11 +54,43 @@ pub fn yarn_versions() -> Fallible<BTreeSet<Version>> {
     volta_home().and_then(|home| read_versions(home.yarn_image_root_dir()))
 }
 
+/// Checks if a given pnpm version image is available on the local machine
+pub fn pnpm_available(version: &Version) -> Fallible<bool> {
+    volta_home().map(|home| home.pnpm_image_dir(&version.to_string()).exists())
+}
+
+/// Collects a set of all pnpm versions fetched on the local machine
+pub fn pnpm_versions() -> Fallible<BTreeSet<Version>> {
+    volta_home().and_then(|home| read_versions(home.pnpm_image_root_dir()))
+}
+
 /// Checks if a given package version image is available on the local machine
 pub fn package_available(name: &str, version: &Version) -> Fallible<bool> {
     volta_home().map(|home| home.package_image_dir(name, &version.to_string()).exists())
 }
 
+/// Collects the versions of every package image unpacked on the local machine, keyed by name
+pub fn package_versions() -> Fallible<BTreeMap<String, BTreeSet<Version>>> {
+    let root = volta_home()?.package_image_root_dir();
+    let mut packages = BTreeMap::new();
+
+    for name in read_dir_names(root)? {
+        // Scoped packages are nested an extra level, e.g. `@scope/name/1.0.0`
+        if name.starts_with('@') {
+            for scoped_name in read_dir_names(&root.join(&name))? {
+                let full_name = format!("{}/{}", name, scoped_name);
+                let versions = read_versions(&root.join(&full_name))?;
+                packages.insert(full_name, versions);
+            }
+        } else {
+            let versions = read_versions(&root.join(&name))?;
+            packages.insert(name, versions);
+        }
+    }
+
+    Ok(packages)
+}
+
 /// Collects a set of all Package Configs on the local machine
 pub fn package_configs() -> Fallible<BTreeSet<PackageConfig>> {
     let package_dir = volta_home()?.default_package_dir();
@@ -81,6 +120,18 @@ pub fn package_configs() -> Fallible<BTreeSet<PackageConfig>> {
         .collect::<Fallible<BTreeSet<PackageConfig>>>()
 }
 
+/// Reads the contents of a directory and returns the names of all subdirectories
+fn read_dir_names(dir: &Path) -> Fallible<Vec<String>> {
+    let contents = read_dir_eager(dir).with_context(|| ErrorKind::ReadDirError {
+        dir: dir.to_owned(),
+    })?;
+
+    Ok(contents
+        .filter(|(_, metadata)| metadata.is_dir())
+        .map(|(entry, _)| entry.file_name().to_string_lossy().into_owned())
+        .collect())
+}
+
 /// Reads the contents of a directory and returns the set of all versions found
 /// in the directory's listing by parsing the directory names as semantic versions
 fn read_versions(dir: &Path) -> Fallible<BTreeSet<Version>> {
@@ -9,11 +9,13 @@ pub mod error;
 mod event;
 pub mod fs;
 mod hook;
+mod http;
 pub mod inventory;
 pub mod layout;
 pub mod log;
 pub mod manifest;
 pub mod monitor;
+pub mod offline;
 pub mod platform;
 pub mod project;
 pub mod run;
@@ -0,0 +1,222 @@
+//! Provides offline mode, in which versions are resolved only from the local inventory and
+//! nothing is downloaded.
+
+use std::collections::BTreeSet;
+use std::fmt::Display;
+
+use crate::error::{ErrorKind, Fallible};
+use crate::project::opted_in;
+use crate::style::tool_version;
+use crate::version::{VersionSpec, VersionTag};
+use log::debug;
+use semver::{Version, VersionReq};
+
+/// Environment variable that enables offline mode, also set by the `--offline` flag so that it
+/// carries through to any nested shims
+pub const VOLTA_OFFLINE: &str = "VOLTA_OFFLINE";
+
+/// Determines whether Volta is running in offline mode, with `VOLTA_OFFLINE` set to `1` or `true`
+pub fn is_offline() -> bool {
+    opted_in(VOLTA_OFFLINE)
+}
+
+/// Ensures that Volta isn't offline before downloading the given tool
+pub(crate) fn ensure_online<T: Display>(tool: T) -> Fallible<()> {
+    if is_offline() {
+        Err(ErrorKind::OfflineFetchError {
+            tool: tool.to_string(),
+        }
+        .into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Resolves a version of a tool using only the versions available in the local inventory
+///
+/// Node LTS releases are identified from `lts_versions`, the LTS versions in the cached Node
+/// index. Without a cached index, they are assumed to be the even major versions. In both cases
+/// 'latest' refers to the newest available version.
+pub(crate) fn resolve(
+    tool: &str,
+    matching: VersionSpec,
+    available: BTreeSet<Version>,
+    lts_versions: Option<&BTreeSet<Version>>,
+) -> Fallible<Version> {
+    let is_lts = |version: &&Version| {
+        tool == "node"
+            && match lts_versions {
+                Some(lts_versions) => lts_versions.contains(version),
+                None => version.major % 2 == 0,
+            }
+    };
+    let uses_lts = match &matching {
+        VersionSpec::None => tool == "node",
+        VersionSpec::Tag(VersionTag::Lts) | VersionSpec::Tag(VersionTag::LtsRequirement(_)) => true,
+        _ => false,
+    };
+    let found = match &matching {
+        // Exact versions don't need resolving, fetching will report if one isn't available
+        VersionSpec::Exact(version) => Some(version.clone()),
+        VersionSpec::Semver(requirement) => newest_matching(&available, requirement),
+        VersionSpec::Tag(VersionTag::Latest) => available.iter().next_back().cloned(),
+        VersionSpec::None if tool != "node" => available.iter().next_back().cloned(),
+        VersionSpec::None | VersionSpec::Tag(VersionTag::Lts) => {
+            available.iter().rev().find(is_lts).cloned()
+        }
+        VersionSpec::Tag(VersionTag::LtsRequirement(requirement)) => available
+            .iter()
+            .rev()
+            .filter(is_lts)
+            .find(|version| requirement.matches(version))
+            .cloned()
+            .or_else(|| newest_matching(&available, requirement)),
+        VersionSpec::Tag(VersionTag::Custom(_)) => None,
+    };
+
+    match found {
+        Some(version) => {
+            debug!(
+                "Found {} matching '{}' in the local inventory",
+                tool_version(tool, &version),
+                matching
+            );
+            Ok(version)
+        }
+        None => Err(ErrorKind::OfflineVersionNotFound {
+            tool: tool.into(),
+            matching: matching.to_string(),
+            guessed_lts: uses_lts && lts_versions.is_none(),
+        }
+        .into()),
+    }
+}
+
+fn newest_matching(available: &BTreeSet<Version>, requirement: &VersionReq) -> Option<Version> {
+    available
+        .iter()
+        .rev()
+        .find(|version| requirement.matches(version))
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::resolve;
+    use crate::version::{parse_requirements, VersionSpec, VersionTag};
+    use semver::Version;
+    use std::collections::BTreeSet;
+
+    fn available() -> BTreeSet<Version> {
+        ["8.9.10", "10.99.1040", "11.2.0", "12.16.1", "13.1.0"]
+            .iter()
+            .map(|v| v.parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_resolve_semver() {
+        let req = parse_requirements("^10 || ^11").unwrap();
+        assert_eq!(
+            resolve("node", VersionSpec::Semver(req), available(), None).unwrap(),
+            Version::parse("11.2.0").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_resolve_exact() {
+        let version = Version::parse("12.16.1").unwrap();
+        assert_eq!(
+            resolve(
+                "node",
+                VersionSpec::Exact(version.clone()),
+                available(),
+                None
+            )
+            .unwrap(),
+            version
+        );
+    }
+
+    #[test]
+    fn test_resolve_latest_and_lts() {
+        assert_eq!(
+            resolve(
+                "node",
+                VersionSpec::Tag(VersionTag::Latest),
+                available(),
+                None
+            )
+            .unwrap(),
+            Version::parse("13.1.0").unwrap()
+        );
+        assert_eq!(
+            resolve("node", VersionSpec::Tag(VersionTag::Lts), available(), None).unwrap(),
+            Version::parse("12.16.1").unwrap()
+        );
+        assert_eq!(
+            resolve("node", VersionSpec::None, available(), None).unwrap(),
+            Version::parse("12.16.1").unwrap()
+        );
+        assert_eq!(
+            resolve("yarn", VersionSpec::None, available(), None).unwrap(),
+            Version::parse("13.1.0").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_resolve_lts_requirement() {
+        let req = parse_requirements(">=11").unwrap();
+        assert_eq!(
+            resolve(
+                "node",
+                VersionSpec::Tag(VersionTag::LtsRequirement(req)),
+                available(),
+                None
+            )
+            .unwrap(),
+            Version::parse("12.16.1").unwrap()
+        );
+
+        let req = parse_requirements("^13").unwrap();
+        assert_eq!(
+            resolve(
+                "node",
+                VersionSpec::Tag(VersionTag::LtsRequirement(req)),
+                available(),
+                None
+            )
+            .unwrap(),
+            Version::parse("13.1.0").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_resolve_lts_from_index() {
+        let lts_versions = ["8.9.10", "10.99.1040", "11.2.0"]
+            .iter()
+            .map(|v| v.parse().unwrap())
+            .collect();
+        assert_eq!(
+            resolve(
+                "node",
+                VersionSpec::Tag(VersionTag::Lts),
+                available(),
+                Some(&lts_versions)
+            )
+            .unwrap(),
+            Version::parse("11.2.0").unwrap()
+        );
+        assert_eq!(
+            resolve("node", VersionSpec::None, available(), Some(&lts_versions)).unwrap(),
+            Version::parse("11.2.0").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_resolve_missing() {
+        let req = parse_requirements("^14").unwrap();
+        assert!(resolve("node", VersionSpec::Semver(req), available(), None).is_err());
+        assert!(resolve("yarn", VersionSpec::None, BTreeSet::new(), None).is_err());
+    }
+}
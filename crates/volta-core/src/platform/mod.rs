@@ -1,8 +1,10 @@
+use std::env;
 use std::fmt;
 
 use crate::error::{ErrorKind, Fallible};
 use crate::session::Session;
-use crate::tool::{Node, Npm, Yarn};
+use crate::tool::{Node, Npm, Pnpm, Yarn};
+use log::debug;
 use semver::Version;
 
 mod image;
@@ -15,6 +17,10 @@ mod tests;
 pub use image::Image;
 pub use system::System;
 
+/// The environment variable used to pass the versions chosen with `volta run` on to the tools it
+/// runs, so that shims called by those tools (e.g. `node` in an npm script) use the same versions
+pub const VOLTA_CLI_PLATFORM: &str = "VOLTA_CLI_PLATFORM";
+
 /// The source with which a version is associated
 #[derive(Clone, Copy)]
 #[cfg_attr(test, derive(Eq, PartialEq, Debug))]
@@ -132,6 +138,14 @@ impl<T> InheritOption<T> {
         }
     }
 
+    /// Returns `other` if the value is inherited, otherwise keeps the value
+    pub fn or(self, other: Self) -> Self {
+        match self {
+            InheritOption::Inherit => other,
+            value => value,
+        }
+    }
+
     /// Converts the `InheritOption` into a regular `Option` by inheriting from the provided value if needed
     pub fn inherit(self, other: Option<T>) -> Option<T> {
         match self {
@@ -160,6 +174,7 @@ impl<T> Default for InheritOption<T> {
 pub struct PlatformSpec {
     pub node: Version,
     pub npm: Option<Version>,
+    pub pnpm: Option<Version>,
     pub yarn: Option<Version>,
 }
 
@@ -169,6 +184,7 @@ impl PlatformSpec {
         Platform {
             node: Sourced::with_default(self.node.clone()),
             npm: self.npm.clone().map(Sourced::with_default),
+            pnpm: self.pnpm.clone().map(Sourced::with_default),
             yarn: self.yarn.clone().map(Sourced::with_default),
         }
     }
@@ -178,6 +194,7 @@ impl PlatformSpec {
         Platform {
             node: Sourced::with_project(self.node.clone()),
             npm: self.npm.clone().map(Sourced::with_project),
+            pnpm: self.pnpm.clone().map(Sourced::with_project),
             yarn: self.yarn.clone().map(Sourced::with_project),
         }
     }
@@ -187,6 +204,7 @@ impl PlatformSpec {
         Platform {
             node: Sourced::with_binary(self.node.clone()),
             npm: self.npm.clone().map(Sourced::with_binary),
+            pnpm: self.pnpm.clone().map(Sourced::with_binary),
             yarn: self.yarn.clone().map(Sourced::with_binary),
         }
     }
@@ -197,15 +215,95 @@ impl PlatformSpec {
 pub struct CliPlatform {
     pub node: Option<Version>,
     pub npm: InheritOption<Version>,
+    pub pnpm: InheritOption<Version>,
     pub yarn: InheritOption<Version>,
 }
 
 impl CliPlatform {
+    /// Reads the platform exported by an enclosing `volta run`, if any
+    ///
+    /// The value has the form `node=12.16.1;npm=none;yarn=1.22.4`, where `none` disables a
+    /// package manager and a missing entry inherits it, matching the `volta run` options.
+    pub fn from_env() -> Self {
+        let value = match env::var(VOLTA_CLI_PLATFORM) {
+            Ok(value) => value,
+            Err(_) => return CliPlatform::default(),
+        };
+
+        CliPlatform::parse_env_value(&value).unwrap_or_else(|| {
+            debug!("Ignoring invalid {} value: {}", VOLTA_CLI_PLATFORM, value);
+            CliPlatform::default()
+        })
+    }
+
+    fn parse_env_value(value: &str) -> Option<Self> {
+        let mut platform = CliPlatform::default();
+
+        for entry in value.split(';').filter(|entry| !entry.is_empty()) {
+            let mut parts = entry.splitn(2, '=');
+            let tool = parts.next()?;
+            let version = match parts.next()? {
+                "none" => InheritOption::None,
+                version => InheritOption::Some(Version::parse(version).ok()?),
+            };
+
+            match tool {
+                "node" => platform.node = version.into(),
+                "npm" => platform.npm = version,
+                "pnpm" => platform.pnpm = version,
+                "yarn" => platform.yarn = version,
+                _ => return None,
+            }
+        }
+
+        Some(platform)
+    }
+
+    /// Produces the value to export in `VOLTA_CLI_PLATFORM`, or `None` if nothing is overridden
+    pub fn to_env_value(&self) -> Option<String> {
+        fn entry(tool: &str, version: &InheritOption<Version>) -> Option<String> {
+            match version {
+                InheritOption::Some(version) => Some(format!("{}={}", tool, version)),
+                InheritOption::None => Some(format!("{}=none", tool)),
+                InheritOption::Inherit => None,
+            }
+        }
+
+        let entries: Vec<String> = self
+            .node
+            .as_ref()
+            .map(|node| format!("node={}", node))
+            .into_iter()
+            .chain(entry("npm", &self.npm))
+            .chain(entry("pnpm", &self.pnpm))
+            .chain(entry("yarn", &self.yarn))
+            .collect();
+
+        if entries.is_empty() {
+            None
+        } else {
+            Some(entries.join(";"))
+        }
+    }
+
+    /// Fills in the versions that weren't set on this command line from an enclosing `volta run`
+    pub fn inherit_from_env(self) -> Self {
+        let outer = CliPlatform::from_env();
+
+        CliPlatform {
+            node: self.node.or(outer.node),
+            npm: self.npm.or(outer.npm),
+            pnpm: self.pnpm.or(outer.pnpm),
+            yarn: self.yarn.or(outer.yarn),
+        }
+    }
+
     /// Merges the `CliPlatform` with a `Platform`, inheriting from the base where needed
     pub fn merge(self, base: Platform) -> Platform {
         Platform {
             node: self.node.map_or(base.node, Sourced::with_command_line),
             npm: self.npm.map(Sourced::with_command_line).inherit(base.npm),
+            pnpm: self.pnpm.map(Sourced::with_command_line).inherit(base.pnpm),
             yarn: self.yarn.map(Sourced::with_command_line).inherit(base.yarn),
         }
     }
@@ -219,6 +317,7 @@ impl From<CliPlatform> for Option<Platform> {
             Some(node) => Some(Platform {
                 node: Sourced::with_command_line(node),
                 npm: base.npm.map(Sourced::with_command_line).into(),
+                pnpm: base.pnpm.map(Sourced::with_command_line).into(),
                 yarn: base.yarn.map(Sourced::with_command_line).into(),
             }),
         }
@@ -229,6 +328,7 @@ impl From<CliPlatform> for Option<Platform> {
 pub struct Platform {
     pub node: Sourced<Version>,
     pub npm: Option<Sourced<Version>>,
+    pub pnpm: Option<Sourced<Version>>,
     pub yarn: Option<Sourced<Version>>,
 }
 
@@ -237,20 +337,25 @@ impl Platform {
     ///
     /// Active platform is determined by first looking at the Project Platform
     ///
-    /// - If it exists and has a Yarn version, then we use the project platform
-    /// - If it exists but doesn't have a Yarn version, then we merge the two,
-    ///   pulling Yarn from the user default platform, if available
+    /// - If it exists and has versions of all the package managers, then we use the project platform
+    /// - If it exists but is missing a package manager version, then we merge the two, pulling the
+    ///   missing package managers from the user default platform, if available
     /// - If there is no Project platform, then we use the user Default Platform
     pub fn current(session: &mut Session) -> Fallible<Option<Self>> {
         match session.project_platform()? {
             Some(platform) => {
-                if platform.yarn.is_none() || platform.npm.is_none() {
+                if platform.yarn.is_none() || platform.npm.is_none() || platform.pnpm.is_none() {
                     if let Some(default) = session.default_platform()? {
                         let npm = platform
                             .npm
                             .clone()
                             .map(Sourced::with_project)
                             .or_else(|| default.npm.clone().map(Sourced::with_default));
+                        let pnpm = platform
+                            .pnpm
+                            .clone()
+                            .map(Sourced::with_project)
+                            .or_else(|| default.pnpm.clone().map(Sourced::with_default));
                         let yarn = platform
                             .yarn
                             .clone()
@@ -260,6 +365,7 @@ impl Platform {
                         return Ok(Some(Platform {
                             node: Sourced::with_project(platform.node.clone()),
                             npm,
+                            pnpm,
                             yarn,
                         }));
                     }
@@ -274,7 +380,10 @@ impl Platform {
     }
 
     /// Returns the platform created by merging a `CliPartialPlatform` with the currently active platform
+    ///
+    /// Versions exported by an enclosing `volta run` are used for anything not set in `cli`
     pub fn with_cli(cli: CliPlatform, session: &mut Session) -> Fallible<Option<Self>> {
+        let cli = cli.inherit_from_env();
         match Self::current(session)? {
             Some(current) => Ok(Some(cli.merge(current))),
             None => Ok(cli.into()),
@@ -291,6 +400,10 @@ impl Platform {
             Npm::new(version.clone()).ensure_fetched(session)?;
         }
 
+        if let Some(Sourced { value: version, .. }) = &self.pnpm {
+            Pnpm::new(version.clone()).ensure_fetched(session)?;
+        }
+
         if let Some(Sourced { value: version, .. }) = &self.yarn {
             Yarn::new(version.clone()).ensure_fetched(session)?;
         }
@@ -298,6 +411,7 @@ impl Platform {
         Ok(Image {
             node: self.node,
             npm: self.npm,
+            pnpm: self.pnpm,
             yarn: self.yarn,
         })
     }
@@ -33,13 +33,18 @@ fn test_image_path() {
     let yarn_bin = volta_home().unwrap().yarn_image_bin_dir("4.5.7");
     let expected_yarn_bin = yarn_bin.to_str().unwrap();
 
+    let pnpm_bin = volta_home().unwrap().pnpm_image_bin_dir("5.5.1");
+    let expected_pnpm_bin = pnpm_bin.to_str().unwrap();
+
     let v123 = Version::parse("1.2.3").unwrap();
     let v457 = Version::parse("4.5.7").unwrap();
+    let v551 = Version::parse("5.5.1").unwrap();
     let v643 = Version::parse("6.4.3").unwrap();
 
     let only_node = Image {
         node: Sourced::with_default(v123.clone()),
         npm: None,
+        pnpm: None,
         yarn: None,
     };
 
@@ -48,9 +53,25 @@ fn test_image_path() {
         format!("{}:/usr/bin:/blah:/doesnt/matter/bin", expected_node_bin),
     );
 
+    let node_pnpm = Image {
+        node: Sourced::with_default(v123.clone()),
+        npm: None,
+        pnpm: Some(Sourced::with_default(v551)),
+        yarn: None,
+    };
+
+    assert_eq!(
+        node_pnpm.path().unwrap().into_string().unwrap(),
+        format!(
+            "{}:{}:/usr/bin:/blah:/doesnt/matter/bin",
+            expected_pnpm_bin, expected_node_bin
+        ),
+    );
+
     let node_npm = Image {
         node: Sourced::with_default(v123.clone()),
         npm: Some(Sourced::with_default(v643.clone())),
+        pnpm: None,
         yarn: None,
     };
 
@@ -65,6 +86,7 @@ fn test_image_path() {
     let node_yarn = Image {
         node: Sourced::with_default(v123.clone()),
         npm: None,
+        pnpm: None,
         yarn: Some(Sourced::with_default(v457.clone())),
     };
 
@@ -79,6 +101,7 @@ fn test_image_path() {
     let node_npm_yarn = Image {
         node: Sourced::with_default(v123),
         npm: Some(Sourced::with_default(v643)),
+        pnpm: None,
         yarn: Some(Sourced::with_default(v457)),
     };
 
@@ -122,6 +145,7 @@ fn test_image_path() {
     let only_node = Image {
         node: Sourced::with_default(v123.clone()),
         npm: None,
+        pnpm: None,
         yarn: None,
     };
 
@@ -133,6 +157,7 @@ fn test_image_path() {
     let node_npm = Image {
         node: Sourced::with_default(v123.clone()),
         npm: Some(Sourced::with_default(v643.clone())),
+        pnpm: None,
         yarn: None,
     };
 
@@ -147,6 +172,7 @@ fn test_image_path() {
     let node_yarn = Image {
         node: Sourced::with_default(v123.clone()),
         npm: None,
+        pnpm: None,
         yarn: Some(Sourced::with_default(v457.clone())),
     };
 
@@ -161,6 +187,7 @@ fn test_image_path() {
     let node_npm_yarn = Image {
         node: Sourced::with_default(v123),
         npm: Some(Sourced::with_default(v643)),
+        pnpm: None,
         yarn: Some(Sourced::with_default(v457)),
     };
 
@@ -273,6 +300,7 @@ mod cli_platform {
     lazy_static! {
         static ref NODE_VERSION: Version = Version::from((12, 14, 1));
         static ref NPM_VERSION: Version = Version::from((6, 13, 2));
+        static ref PNPM_VERSION: Version = Version::from((5, 5, 1));
         static ref YARN_VERSION: Version = Version::from((1, 17, 0));
     }
 
@@ -285,12 +313,14 @@ mod cli_platform {
             let test = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
             let base = Platform {
                 node: Sourced::with_default(Version::from((10, 10, 10))),
                 npm: None,
+                pnpm: None,
                 yarn: None,
             };
 
@@ -305,12 +335,14 @@ mod cli_platform {
             let test = CliPlatform {
                 node: None,
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
             let base = Platform {
                 node: Sourced::with_default(NODE_VERSION.clone()),
                 npm: None,
+                pnpm: None,
                 yarn: None,
             };
 
@@ -325,12 +357,14 @@ mod cli_platform {
             let test = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::Some(NPM_VERSION.clone()),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
             let base = Platform {
                 node: Sourced::with_default(Version::from((10, 10, 10))),
                 npm: Some(Sourced::with_default(Version::from((5, 6, 3)))),
+                pnpm: None,
                 yarn: None,
             };
 
@@ -346,12 +380,14 @@ mod cli_platform {
             let test = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::Inherit,
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
             let base = Platform {
                 node: Sourced::with_default(Version::from((10, 10, 10))),
                 npm: Some(Sourced::with_default(NPM_VERSION.clone())),
+                pnpm: None,
                 yarn: None,
             };
 
@@ -367,12 +403,14 @@ mod cli_platform {
             let test = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::None,
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
             let base = Platform {
                 node: Sourced::with_default(Version::from((10, 10, 10))),
                 npm: Some(Sourced::with_default(NPM_VERSION.clone())),
+                pnpm: None,
                 yarn: None,
             };
 
@@ -381,17 +419,86 @@ mod cli_platform {
             assert!(merged.npm.is_none());
         }
 
+        #[test]
+        fn uses_pnpm() {
+            let test = CliPlatform {
+                node: Some(NODE_VERSION.clone()),
+                npm: InheritOption::default(),
+                pnpm: InheritOption::Some(PNPM_VERSION.clone()),
+                yarn: InheritOption::default(),
+            };
+
+            let base = Platform {
+                node: Sourced::with_default(Version::from((10, 10, 10))),
+                npm: None,
+                pnpm: Some(Sourced::with_default(Version::from((4, 14, 4)))),
+                yarn: None,
+            };
+
+            let merged = test.merge(base);
+
+            let merged_pnpm = merged.pnpm.unwrap();
+            assert_eq!(merged_pnpm.value, PNPM_VERSION.clone());
+            assert_eq!(merged_pnpm.source, Source::CommandLine);
+        }
+
+        #[test]
+        fn inherits_pnpm() {
+            let test = CliPlatform {
+                node: Some(NODE_VERSION.clone()),
+                npm: InheritOption::default(),
+                pnpm: InheritOption::Inherit,
+                yarn: InheritOption::default(),
+            };
+
+            let base = Platform {
+                node: Sourced::with_default(Version::from((10, 10, 10))),
+                npm: None,
+                pnpm: Some(Sourced::with_default(PNPM_VERSION.clone())),
+                yarn: None,
+            };
+
+            let merged = test.merge(base);
+
+            let merged_pnpm = merged.pnpm.unwrap();
+            assert_eq!(merged_pnpm.value, PNPM_VERSION.clone());
+            assert_eq!(merged_pnpm.source, Source::Default);
+        }
+
+        #[test]
+        fn none_does_not_inherit_pnpm() {
+            let test = CliPlatform {
+                node: Some(NODE_VERSION.clone()),
+                npm: InheritOption::default(),
+                pnpm: InheritOption::None,
+                yarn: InheritOption::default(),
+            };
+
+            let base = Platform {
+                node: Sourced::with_default(Version::from((10, 10, 10))),
+                npm: None,
+                pnpm: Some(Sourced::with_default(PNPM_VERSION.clone())),
+                yarn: None,
+            };
+
+            let merged = test.merge(base);
+
+            assert!(merged.pnpm.is_none());
+        }
+
         #[test]
         fn uses_yarn() {
             let test = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::Some(YARN_VERSION.clone()),
             };
 
             let base = Platform {
                 node: Sourced::with_default(Version::from((10, 10, 10))),
                 npm: None,
+                pnpm: None,
                 yarn: Some(Sourced::with_default(Version::from((1, 10, 3)))),
             };
 
@@ -407,12 +514,14 @@ mod cli_platform {
             let test = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::Inherit,
             };
 
             let base = Platform {
                 node: Sourced::with_default(Version::from((10, 10, 10))),
                 npm: None,
+                pnpm: None,
                 yarn: Some(Sourced::with_default(YARN_VERSION.clone())),
             };
 
@@ -428,12 +537,14 @@ mod cli_platform {
             let test = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::None,
             };
 
             let base = Platform {
                 node: Sourced::with_default(Version::from((10, 10, 10))),
                 npm: None,
+                pnpm: None,
                 yarn: Some(Sourced::with_default(YARN_VERSION.clone())),
             };
 
@@ -452,6 +563,7 @@ mod cli_platform {
             let cli = CliPlatform {
                 node: None,
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
@@ -465,6 +577,7 @@ mod cli_platform {
             let cli = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
@@ -480,6 +593,7 @@ mod cli_platform {
             let cli = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::Some(NPM_VERSION.clone()),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
@@ -495,6 +609,7 @@ mod cli_platform {
             let cli = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::None,
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
@@ -508,6 +623,7 @@ mod cli_platform {
             let cli = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::Inherit,
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::default(),
             };
 
@@ -521,6 +637,7 @@ mod cli_platform {
             let cli = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::Some(YARN_VERSION.clone()),
             };
 
@@ -536,6 +653,7 @@ mod cli_platform {
             let cli = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::None,
             };
 
@@ -549,6 +667,7 @@ mod cli_platform {
             let cli = CliPlatform {
                 node: Some(NODE_VERSION.clone()),
                 npm: InheritOption::default(),
+                pnpm: InheritOption::default(),
                 yarn: InheritOption::Inherit,
             };
 
@@ -557,4 +676,40 @@ mod cli_platform {
             assert!(transformed.unwrap().yarn.is_none());
         }
     }
+
+    mod env_value {
+        use super::super::super::*;
+        use super::*;
+
+        #[test]
+        fn empty_platform_is_not_exported() {
+            assert_eq!(CliPlatform::default().to_env_value(), None);
+        }
+
+        #[test]
+        fn round_trips() {
+            let cli = CliPlatform {
+                node: Some(NODE_VERSION.clone()),
+                npm: InheritOption::None,
+                pnpm: InheritOption::Inherit,
+                yarn: InheritOption::Some(YARN_VERSION.clone()),
+            };
+
+            let value = cli.to_env_value().unwrap();
+            assert_eq!(value, "node=12.14.1;npm=none;yarn=1.17.0");
+
+            let parsed = CliPlatform::parse_env_value(&value).unwrap();
+            assert_eq!(parsed.node, Some(NODE_VERSION.clone()));
+            assert_eq!(parsed.npm, InheritOption::None);
+            assert_eq!(parsed.pnpm, InheritOption::Inherit);
+            assert_eq!(parsed.yarn, InheritOption::Some(YARN_VERSION.clone()));
+        }
+
+        #[test]
+        fn rejects_invalid_values() {
+            assert!(CliPlatform::parse_env_value("node=twelve").is_none());
+            assert!(CliPlatform::parse_env_value("node").is_none());
+            assert!(CliPlatform::parse_env_value("deno=1.0.0").is_none());
+        }
+    }
 }
@@ -0,0 +1,349 @@
+//! Provides support for the `engines` field of a project manifest, which can optionally be used
+//! to determine the project platform when there is no `volta` pin.
+
+use std::fmt::Display;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use super::{PartialPlatform, Project};
+use crate::error::{Context, ErrorKind, Fallible};
+use crate::fs::{create_staging_file, read_file};
+use crate::hook::HookConfig;
+use crate::layout::volta_home;
+use crate::platform::PlatformSpec;
+use crate::style::tool_version;
+use crate::tool::{node, npm, pnpm, yarn};
+use crate::version::{parse_version, VersionSpec};
+use fs_utils::ensure_containing_dir_exists;
+use hyperx::header::HttpDate;
+use log::{debug, warn};
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Environment variable that opts in to resolving the project platform from `engines`
+const USE_ENGINES: &str = "VOLTA_USE_ENGINES";
+
+/// Determines whether the `engines` requirements should be used to resolve the project platform
+pub(super) fn enabled() -> bool {
+    super::opted_in(USE_ENGINES)
+}
+
+/// The tool requirements from the `engines` field of a project manifest
+#[cfg_attr(test, derive(Debug))]
+pub(super) struct Engines {
+    pub node: Option<VersionReq>,
+    pub npm: Option<VersionReq>,
+    pub pnpm: Option<VersionReq>,
+    pub yarn: Option<VersionReq>,
+}
+
+impl Engines {
+    /// Resolves the requirements into a platform, using the versions cached for this manifest
+    /// when the requirements haven't changed since they were last resolved
+    pub(super) fn resolve(
+        &self,
+        project: &Project,
+        hooks: &HookConfig,
+    ) -> Fallible<PartialPlatform> {
+        let cache_file = volta_home()?.engines_cache_file(&cache_key(project.manifest_file()));
+        let mut cache = read_cache(&cache_file)?;
+        let mut changed = false;
+
+        let node = resolve_cached(self.node.clone(), &mut cache.node, &mut changed, |req| {
+            node::resolve_with_hooks(VersionSpec::Semver(req), hooks.node())
+        })?;
+        let npm = resolve_cached(self.npm.clone(), &mut cache.npm, &mut changed, |req| {
+            npm::resolve_with_hooks(VersionSpec::Semver(req.clone()), hooks.npm(), Some(project))?
+                .ok_or_else(|| {
+                    ErrorKind::NpmVersionNotFound {
+                        matching: req.to_string(),
+                    }
+                    .into()
+                })
+        })?;
+        let pnpm = resolve_cached(self.pnpm.clone(), &mut cache.pnpm, &mut changed, |req| {
+            pnpm::resolve_with_hooks(VersionSpec::Semver(req), hooks.pnpm(), Some(project))
+        })?;
+        let yarn = resolve_cached(self.yarn.clone(), &mut cache.yarn, &mut changed, |req| {
+            yarn::resolve_with_hooks(VersionSpec::Semver(req), hooks.yarn(), Some(project))
+        })?;
+
+        if changed {
+            write_cache(&cache_file, &cache)?;
+        }
+
+        Ok(PartialPlatform {
+            node,
+            npm,
+            pnpm,
+            yarn,
+        })
+    }
+
+    /// Warns about any tool versions in the platform that don't satisfy the requirements
+    pub(super) fn check(&self, platform: &PlatformSpec, manifest: &Path) {
+        let versions = [
+            ("node", Some(&platform.node), &self.node),
+            ("npm", platform.npm.as_ref(), &self.npm),
+            ("pnpm", platform.pnpm.as_ref(), &self.pnpm),
+            ("yarn", platform.yarn.as_ref(), &self.yarn),
+        ];
+
+        for (tool, version, requirement) in versions.iter() {
+            if let (Some(version), Some(requirement)) = (version, requirement) {
+                if !requirement.matches(version) {
+                    warn!(
+                        "{} does not satisfy the `engines.{}` requirement '{}' in {}",
+                        tool_version(tool, version),
+                        tool,
+                        requirement,
+                        manifest.display()
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Resolves the Node version from a version file, using the version cached for that file when
+/// its contents haven't changed since it was last resolved
+///
+/// Aliases like `lts/*` would otherwise need the Node index every time the project is loaded.
+/// Since the version they refer to changes with new releases, the cached version expires along
+/// with the cached Node index, and isn't cached at all without one.
+pub(super) fn resolve_version_file<F>(
+    file: &Path,
+    spec: VersionSpec,
+    resolve: F,
+) -> Fallible<Version>
+where
+    F: FnOnce(VersionSpec) -> Fallible<Version>,
+{
+    if let VersionSpec::Exact(version) = spec {
+        return Ok(version);
+    }
+
+    let cache_file = volta_home()?.engines_cache_file(&cache_key(file));
+    let mut cache = read_cache(&cache_file)?;
+    let mut changed = false;
+
+    if cache
+        .version_file
+        .as_ref()
+        .map_or(false, CachedEngine::expired)
+    {
+        cache.version_file = None;
+    }
+
+    let version = resolve_cached(Some(spec), &mut cache.version_file, &mut changed, resolve)?;
+
+    if changed {
+        if let Some(expiry) = node::index_expiry()? {
+            if let Some(entry) = cache.version_file.as_mut() {
+                entry.expires = Some(expiry.to_string());
+            }
+            write_cache(&cache_file, &cache)?;
+        }
+    }
+
+    // Invariant: `resolve_cached` always returns a version when given a requirement
+    Ok(version.expect("A requirement was provided"))
+}
+
+/// The versions most recently resolved for a single file, either the `engines` requirements of
+/// a manifest or the contents of a Node version file
+#[derive(Default, Deserialize, Serialize)]
+struct EnginesCache {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    node: Option<CachedEngine>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    npm: Option<CachedEngine>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pnpm: Option<CachedEngine>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    yarn: Option<CachedEngine>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version_file: Option<CachedEngine>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct CachedEngine {
+    requirement: String,
+    version: String,
+    /// When the cached version needs to be resolved again, for versions from a version file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expires: Option<String>,
+}
+
+impl CachedEngine {
+    /// Whether a cached version from a version file has expired, which includes any version
+    /// cached without a valid expiry
+    fn expired(&self) -> bool {
+        self.expires
+            .as_ref()
+            .and_then(|expires| HttpDate::from_str(expires).ok())
+            .map_or(true, |expires| HttpDate::from(SystemTime::now()) >= expires)
+    }
+}
+
+/// Resolves a single requirement, reusing the cached version if the requirement is unchanged
+fn resolve_cached<R, F>(
+    requirement: Option<R>,
+    cached: &mut Option<CachedEngine>,
+    changed: &mut bool,
+    resolve: F,
+) -> Fallible<Option<Version>>
+where
+    R: Display,
+    F: FnOnce(R) -> Fallible<Version>,
+{
+    let requirement = match requirement {
+        Some(requirement) => requirement,
+        None => return Ok(None),
+    };
+    let key = requirement.to_string();
+
+    if let Some(entry) = cached.as_ref().filter(|entry| entry.requirement == key) {
+        if let Ok(version) = parse_version(&entry.version) {
+            debug!("Using cached version {} for requirement '{}'", version, key);
+            return Ok(Some(version));
+        }
+    }
+
+    let version = resolve(requirement)?;
+    *cached = Some(CachedEngine {
+        requirement: key,
+        version: version.to_string(),
+        expires: None,
+    });
+    *changed = true;
+
+    Ok(Some(version))
+}
+
+fn read_cache(cache_file: &Path) -> Fallible<EnginesCache> {
+    let cached = read_file(cache_file)
+        .with_context(|| ErrorKind::ReadEnginesCacheError {
+            file: cache_file.to_owned(),
+        })?
+        .and_then(|contents| match serde_json::from_str(&contents) {
+            Ok(cached) => Some(cached),
+            Err(_) => {
+                debug!("Ignoring invalid engines cache {}", cache_file.display());
+                None
+            }
+        });
+
+    Ok(cached.unwrap_or_default())
+}
+
+fn write_cache(cache_file: &Path, cache: &EnginesCache) -> Fallible<()> {
+    let staging = create_staging_file()?;
+    let contents =
+        serde_json::to_string(cache).with_context(|| ErrorKind::WriteEnginesCacheError {
+            file: cache_file.to_owned(),
+        })?;
+    let mut staging_file: &File = staging.as_file();
+    staging_file
+        .write_all(contents.as_bytes())
+        .with_context(|| ErrorKind::WriteEnginesCacheError {
+            file: staging.path().to_owned(),
+        })?;
+
+    ensure_containing_dir_exists(&cache_file).with_context(|| ErrorKind::ContainingDirError {
+        path: cache_file.to_owned(),
+    })?;
+    staging
+        .persist(cache_file)
+        .with_context(|| ErrorKind::WriteEnginesCacheError {
+            file: cache_file.to_owned(),
+        })?;
+
+    Ok(())
+}
+
+/// The name of the cache file for a manifest or version file, derived from its path
+fn cache_key(file: &Path) -> String {
+    format!("{:x}", Sha256::digest(file.to_string_lossy().as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{resolve_cached, CachedEngine};
+    use crate::version::parse_requirements;
+    use hyperx::header::HttpDate;
+    use semver::{Version, VersionReq};
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn test_resolve_cached_uses_matching_entry() {
+        let requirement = Some(parse_requirements("^10").unwrap());
+        let mut cached = Some(CachedEngine {
+            requirement: requirement.as_ref().unwrap().to_string(),
+            version: "10.1.0".into(),
+            expires: None,
+        });
+        let mut changed = false;
+
+        let version = resolve_cached(requirement, &mut cached, &mut changed, |_| {
+            panic!("Should not resolve when the requirement is cached")
+        })
+        .unwrap();
+
+        assert_eq!(version, Some(Version::new(10, 1, 0)));
+        assert!(!changed);
+    }
+
+    #[test]
+    fn test_resolve_cached_updates_changed_requirement() {
+        let requirement = Some(parse_requirements("^12").unwrap());
+        let mut cached = Some(CachedEngine {
+            requirement: parse_requirements("^10").unwrap().to_string(),
+            version: "10.1.0".into(),
+            expires: None,
+        });
+        let mut changed = false;
+
+        let version = resolve_cached(requirement, &mut cached, &mut changed, |_| {
+            Ok(Version::new(12, 2, 0))
+        })
+        .unwrap();
+
+        assert_eq!(version, Some(Version::new(12, 2, 0)));
+        assert!(changed);
+        assert_eq!(cached.unwrap().version, "12.2.0");
+    }
+
+    #[test]
+    fn test_resolve_cached_without_requirement() {
+        let mut cached = None;
+        let mut changed = false;
+
+        let version = resolve_cached(None::<VersionReq>, &mut cached, &mut changed, |_| {
+            panic!("Should not resolve without a requirement")
+        })
+        .unwrap();
+
+        assert_eq!(version, None);
+        assert!(!changed);
+        assert!(cached.is_none());
+    }
+
+    #[test]
+    fn test_cached_engine_expired() {
+        let one_day = Duration::from_secs(24 * 60 * 60);
+        let entry = |expires: Option<SystemTime>| CachedEngine {
+            requirement: "lts/*".into(),
+            version: "10.1.0".into(),
+            expires: expires.map(|expires| HttpDate::from(expires).to_string()),
+        };
+
+        assert!(!entry(Some(SystemTime::now() + one_day)).expired());
+        assert!(entry(Some(SystemTime::now() - one_day)).expired());
+        assert!(entry(None).expired());
+    }
+}
@@ -11,16 +11,21 @@ use lazycell::LazyCell;
 use semver::Version;
 
 use crate::error::{Context, ErrorKind, Fallible, VoltaError};
+use crate::hook::HookConfig;
 use crate::layout::volta_home;
 use crate::platform::PlatformSpec;
-use crate::tool::BinConfig;
+use crate::tool::{node, BinConfig};
 use chain_map::ChainMap;
 use indexmap::IndexSet;
 
+mod engines;
 mod serial;
 #[cfg(test)]
 mod tests;
+mod version_file;
+mod yarnrc;
 
+use engines::Engines;
 use serial::{update_manifest, Manifest, ManifestKey};
 
 /// A lazily loaded Project
@@ -53,6 +58,8 @@ pub struct Project {
     workspace_manifests: IndexSet<PathBuf>,
     dependencies: ChainMap<String, String>,
     platform: Option<PlatformSpec>,
+    node_version_file: Option<PathBuf>,
+    engines: Option<Engines>,
 }
 
 impl Project {
@@ -65,7 +72,7 @@ impl Project {
     /// Creates an optional Project instance from the specified directory
     ///
     /// Will search ancestors to find a `package.json` and use that as the root of the project
-    fn for_dir(base_dir: PathBuf) -> Fallible<Option<Self>> {
+    pub fn for_dir(base_dir: PathBuf) -> Fallible<Option<Self>> {
         match find_closest_root(base_dir) {
             Some(mut project) => {
                 project.push("package.json");
@@ -82,6 +89,7 @@ impl Project {
         let mut workspace_manifests = IndexSet::new();
         let mut platform = manifest.platform;
         let mut extends = manifest.extends;
+        let package_manager_yarn = manifest.package_manager_yarn;
 
         // Iterate the `volta.extends` chain, parsing each file in turn
         while let Some(path) = extends {
@@ -110,14 +118,72 @@ impl Project {
             extends = manifest.extends;
         }
 
-        let platform = platform.map(TryInto::try_into).transpose()?;
-
-        Ok(Project {
+        let mut project = Project {
             manifest_file,
             dependencies,
             workspace_manifests,
-            platform,
-        })
+            platform: None,
+            node_version_file: None,
+            engines: manifest.engines,
+        };
+
+        // If the manifests don't pin Node, optionally fall back to a `.node-version` or `.nvmrc`
+        if !PartialPlatform::has_node(&platform) && version_file::enabled() {
+            if let Some((file, version)) = project.read_node_version_file()? {
+                let fallback = PartialPlatform {
+                    node: Some(version),
+                    npm: None,
+                    pnpm: None,
+                    yarn: None,
+                };
+                platform = Some(PartialPlatform::with_fallback(platform, fallback));
+                project.node_version_file = Some(file);
+            }
+        }
+
+        // Otherwise, optionally resolve the platform from the `engines` requirements
+        if let Some(engines) = &project.engines {
+            if engines.node.is_some() && !PartialPlatform::has_node(&platform) && engines::enabled()
+            {
+                let hooks = HookConfig::current(Some(&project))?;
+                let resolved = engines.resolve(&project, &hooks)?;
+                platform = Some(PartialPlatform::with_fallback(platform, resolved));
+            }
+        }
+
+        // The `packageManager` field pins Yarn if the `volta` key doesn't, as long as Node is known
+        if let Some(yarn) = package_manager_yarn {
+            if PartialPlatform::has_node(&platform) {
+                let fallback = PartialPlatform {
+                    node: None,
+                    npm: None,
+                    pnpm: None,
+                    yarn: Some(yarn),
+                };
+                platform = Some(PartialPlatform::with_fallback(platform, fallback));
+            }
+        }
+
+        project.platform = platform.map(TryInto::try_into).transpose()?;
+
+        Ok(project)
+    }
+
+    /// Reads and resolves the Node version from the closest Node version file in the workspace
+    fn read_node_version_file(&self) -> Fallible<Option<(PathBuf, Version)>> {
+        for root in self.workspace_roots() {
+            if let Some((file, spec)) = version_file::read_in(root)? {
+                let version = engines::resolve_version_file(&file, spec, |spec| {
+                    // Resolving an alias may need the project hooks, which can't come from the
+                    // `Session` since we are still loading the project
+                    let hooks = HookConfig::current(Some(self))?;
+                    node::resolve_with_hooks(spec, hooks.node())
+                })?;
+                return Ok(Some((file, version)));
+            }
+        }
+
+        Ok(None)
     }
 
     /// Returns a reference to the manifest file for the current project
@@ -138,6 +204,37 @@ impl Project {
         self.platform.as_ref()
     }
 
+    /// Warns about any tool versions in the project platform that don't satisfy the `engines`
+    /// requirements of the project manifest
+    ///
+    /// This is only checked by commands that show or change the project platform, rather than
+    /// every time the project is loaded, so shims don't repeat the warning on every call.
+    pub fn check_engines(&self) {
+        if let (Some(engines), Some(platform)) = (&self.engines, &self.platform) {
+            engines.check(platform, &self.manifest_file);
+        }
+    }
+
+    /// Returns the path to the file that the project's Node version was read from
+    ///
+    /// This is the manifest file, unless the version came from a `.node-version` or `.nvmrc`
+    pub fn node_version_source(&self) -> &Path {
+        self.node_version_file
+            .as_ref()
+            .unwrap_or(&self.manifest_file)
+    }
+
+    /// Returns the path to the Yarn release set by `yarnPath` in the closest `.yarnrc.yml`, if any
+    pub fn yarn_path(&self) -> Fallible<Option<PathBuf>> {
+        for root in self.workspace_roots() {
+            if let Some(path) = yarnrc::read_yarn_path(root)? {
+                return Ok(Some(path));
+            }
+        }
+
+        Ok(None)
+    }
+
     /// Returns true if the project dependency map contains the specified dependency
     pub fn has_direct_dependency(&self, dependency: &str) -> bool {
         self.dependencies.contains_key(dependency)
@@ -173,6 +270,7 @@ impl Project {
     /// Pins the Node version in this project's manifest file
     pub fn pin_node(&mut self, version: Version) -> Fallible<()> {
         update_manifest(&self.manifest_file, ManifestKey::Node, Some(&version))?;
+        self.node_version_file = None;
 
         if let Some(platform) = self.platform.as_mut() {
             platform.node = version;
@@ -180,6 +278,7 @@ impl Project {
             self.platform = Some(PlatformSpec {
                 node: version,
                 npm: None,
+                pnpm: None,
                 yarn: None,
             });
         }
@@ -200,6 +299,22 @@ impl Project {
         }
     }
 
+    /// Pins the pnpm version in this project's manifest file
+    pub fn pin_pnpm(&mut self, version: Option<Version>) -> Fallible<()> {
+        if let Some(platform) = self.platform.as_mut() {
+            update_manifest(&self.manifest_file, ManifestKey::Pnpm, version.as_ref())?;
+
+            platform.pnpm = version;
+
+            Ok(())
+        } else {
+            Err(ErrorKind::NoPinnedNodeVersion {
+                tool: "pnpm".into(),
+            }
+            .into())
+        }
+    }
+
     /// Pins the Yarn version in this project's manifest file
     pub fn pin_yarn(&mut self, version: Option<Version>) -> Fallible<()> {
         if let Some(platform) = self.platform.as_mut() {
@@ -233,6 +348,13 @@ fn is_project_root(dir: &Path) -> bool {
     is_node_root(dir) && !is_dependency(dir)
 }
 
+/// Determines whether an opt-in environment variable is set to `1` or `true`
+pub(crate) fn opted_in(var: &str) -> bool {
+    env::var(var)
+        .map(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
 /// Starts at `base_dir` and walks up the directory tree until a package.json file is found
 pub(crate) fn find_closest_root(mut dir: PathBuf) -> Option<PathBuf> {
     while !is_project_root(&dir) {
@@ -247,6 +369,7 @@ pub(crate) fn find_closest_root(mut dir: PathBuf) -> Option<PathBuf> {
 struct PartialPlatform {
     node: Option<Version>,
     npm: Option<Version>,
+    pnpm: Option<Version>,
     yarn: Option<Version>,
 }
 
@@ -255,9 +378,22 @@ impl PartialPlatform {
         PartialPlatform {
             node: self.node.or(other.node),
             npm: self.npm.or(other.npm),
+            pnpm: self.pnpm.or(other.pnpm),
             yarn: self.yarn.or(other.yarn),
         }
     }
+
+    /// Fills in any versions missing from the (maybe) platform using the fallback
+    fn with_fallback(base: Option<PartialPlatform>, fallback: PartialPlatform) -> PartialPlatform {
+        match base {
+            Some(base) => base.merge(fallback),
+            None => fallback,
+        }
+    }
+
+    fn has_node(platform: &Option<PartialPlatform>) -> bool {
+        platform.as_ref().map_or(false, |p| p.node.is_some())
+    }
 }
 
 impl TryFrom<PartialPlatform> for PlatformSpec {
@@ -269,6 +405,7 @@ impl TryFrom<PartialPlatform> for PlatformSpec {
         Ok(PlatformSpec {
             node,
             npm: partial.npm,
+            pnpm: partial.pnpm,
             yarn: partial.yarn,
         })
     }
@@ -4,11 +4,13 @@ use std::fs::{read_to_string, File};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
+use super::engines::Engines;
 use super::PartialPlatform;
 use crate::error::{Context, ErrorKind, Fallible};
-use crate::version::parse_version;
+use crate::version::{parse_requirements, parse_version};
 use dunce::canonicalize;
-use semver::Version;
+use log::debug;
+use semver::{Version, VersionReq};
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 
@@ -21,6 +23,8 @@ pub(super) struct Manifest {
     pub dependency_maps: DependencyMapIterator,
     pub platform: Option<PartialPlatform>,
     pub extends: Option<PathBuf>,
+    pub engines: Option<Engines>,
+    pub package_manager_yarn: Option<Version>,
 }
 
 impl Manifest {
@@ -52,10 +56,18 @@ impl Manifest {
             None => (None, None),
         };
 
+        let engines = raw.engines.as_ref().and_then(parse_engines);
+        let package_manager_yarn = raw
+            .package_manager
+            .as_ref()
+            .and_then(|field| parse_package_manager_yarn(field));
+
         Ok(Manifest {
             dependency_maps,
             platform,
             extends,
+            engines,
+            package_manager_yarn,
         })
     }
 }
@@ -63,6 +75,7 @@ impl Manifest {
 pub(super) enum ManifestKey {
     Node,
     Npm,
+    Pnpm,
     Yarn,
 }
 
@@ -71,6 +84,7 @@ impl fmt::Display for ManifestKey {
         f.write_str(match self {
             ManifestKey::Node => "node",
             ManifestKey::Npm => "npm",
+            ManifestKey::Pnpm => "pnpm",
             ManifestKey::Yarn => "yarn",
         })
     }
@@ -147,6 +161,12 @@ struct RawManifest {
     dev_dependencies: Option<HashMap<String, String>>,
 
     volta: Option<ToolchainSpec>,
+
+    // Parsed leniently, since older packages may use formats other than an object of ranges
+    engines: Option<Value>,
+
+    #[serde(rename = "packageManager")]
+    package_manager: Option<String>,
 }
 
 impl RawManifest {
@@ -168,6 +188,8 @@ struct ToolchainSpec {
     #[serde(skip_serializing_if = "Option::is_none")]
     npm: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pnpm: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     yarn: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     extends: Option<PathBuf>,
@@ -178,10 +200,71 @@ impl ToolchainSpec {
     fn parse_split(self) -> Fallible<(PartialPlatform, Option<PathBuf>)> {
         let node = self.node.map(parse_version).transpose()?;
         let npm = self.npm.map(parse_version).transpose()?;
+        let pnpm = self.pnpm.map(parse_version).transpose()?;
         let yarn = self.yarn.map(parse_version).transpose()?;
 
-        let platform = PartialPlatform { node, npm, yarn };
+        let platform = PartialPlatform {
+            node,
+            npm,
+            pnpm,
+            yarn,
+        };
 
         Ok((platform, self.extends))
     }
 }
+
+/// Reads the Node, npm, pnpm, and Yarn requirements from the `engines` field, if it has any
+///
+/// Requirements that aren't valid ranges are ignored, since the field is only loosely specified
+fn parse_engines(engines: &Value) -> Option<Engines> {
+    let requirement = |tool: &str| -> Option<VersionReq> {
+        let range = engines.get(tool)?.as_str()?;
+        match parse_requirements(range) {
+            Ok(requirement) => Some(requirement),
+            Err(_) => {
+                debug!(
+                    "Ignoring invalid `engines.{}` requirement '{}'",
+                    tool, range
+                );
+                None
+            }
+        }
+    };
+
+    let engines = Engines {
+        node: requirement("node"),
+        npm: requirement("npm"),
+        pnpm: requirement("pnpm"),
+        yarn: requirement("yarn"),
+    };
+
+    if engines.node.is_none()
+        && engines.npm.is_none()
+        && engines.pnpm.is_none()
+        && engines.yarn.is_none()
+    {
+        None
+    } else {
+        Some(engines)
+    }
+}
+
+/// Reads the Yarn version from a Corepack-style `packageManager` field, e.g. `yarn@3.2.1`
+///
+/// The field may include a hash of the release after a `+`, which isn't needed to pin the version
+fn parse_package_manager_yarn(field: &str) -> Option<Version> {
+    let mut parts = field.splitn(2, '@');
+    let version = match (parts.next(), parts.next()) {
+        (Some("yarn"), Some(version)) => version.split('+').next().unwrap_or_default(),
+        _ => return None,
+    };
+
+    match parse_version(version) {
+        Ok(version) => Some(version),
+        Err(_) => {
+            debug!("Ignoring invalid `packageManager` field '{}'", field);
+            None
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! Provides support for reading the Node version from `.node-version` and `.nvmrc` files, which
+//! can be used as a fallback when a project doesn't pin Node in its `package.json`.
+
+use std::path::{Path, PathBuf};
+
+use crate::error::{Context, ErrorKind, Fallible};
+use crate::fs::read_file;
+use crate::version::{VersionSpec, VersionTag};
+use log::debug;
+
+/// Environment variable that opts in to reading Node version files
+const NODE_VERSION_FILES: &str = "VOLTA_NODE_VERSION_FILES";
+
+/// The supported Node version files, in order of precedence
+const VERSION_FILE_NAMES: [&str; 2] = [".node-version", ".nvmrc"];
+
+/// Determines whether Node version files should be used as a fallback
+pub(super) fn enabled() -> bool {
+    super::opted_in(NODE_VERSION_FILES)
+}
+
+/// Reads the Node version from the first version file found in `dir`, if any
+pub(super) fn read_in(dir: &Path) -> Fallible<Option<(PathBuf, VersionSpec)>> {
+    for name in VERSION_FILE_NAMES.iter() {
+        let file = dir.join(name);
+        let contents = read_file(&file)
+            .with_context(|| ErrorKind::ReadNodeVersionFileError { file: file.clone() })?;
+
+        match contents.as_ref().and_then(|c| parse(c)) {
+            Some(spec) => {
+                debug!("Found Node version '{}' in {}", spec, file.display());
+                return Ok(Some((file, spec)));
+            }
+            None if contents.is_some() => {
+                debug!("Ignoring {}, which has no Node version", file.display());
+            }
+            None => {}
+        }
+    }
+
+    Ok(None)
+}
+
+/// Parses the contents of a version file, using the first line that isn't empty or a comment
+///
+/// The nvm aliases `node`, `stable`, `lts/*` and `lts/<codename>` are supported, in addition to
+/// plain versions and requirements.
+fn parse(contents: &str) -> Option<VersionSpec> {
+    let line = contents
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .find(|line| !line.is_empty())?;
+
+    let spec = match line.to_lowercase().as_str() {
+        "node" | "stable" | "current" | "latest" => VersionSpec::Tag(VersionTag::Latest),
+        "lts" | "lts/*" => VersionSpec::Tag(VersionTag::Lts),
+        lower if lower.starts_with("lts/") => VersionSpec::Tag(VersionTag::Custom(lower.into())),
+        _ => line.trim_start_matches('v').parse().ok()?,
+    };
+
+    Some(spec)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+    use crate::version::{parse_requirements, VersionSpec, VersionTag};
+    use semver::Version;
+
+    #[test]
+    fn test_parse_versions() {
+        assert_eq!(
+            parse("12.16.1\n"),
+            Some(VersionSpec::Exact(Version::new(12, 16, 1)))
+        );
+        assert_eq!(
+            parse("v10.19.0"),
+            Some(VersionSpec::Exact(Version::new(10, 19, 0)))
+        );
+        assert_eq!(
+            parse("# pinned for CI\n\n  14  \n"),
+            Some(VersionSpec::Semver(parse_requirements("14").unwrap()))
+        );
+    }
+
+    #[test]
+    fn test_parse_aliases() {
+        assert_eq!(parse("node"), Some(VersionSpec::Tag(VersionTag::Latest)));
+        assert_eq!(parse("stable"), Some(VersionSpec::Tag(VersionTag::Latest)));
+        assert_eq!(parse("lts/*"), Some(VersionSpec::Tag(VersionTag::Lts)));
+        assert_eq!(
+            parse("lts/Erbium"),
+            Some(VersionSpec::Tag(VersionTag::Custom("lts/erbium".into())))
+        );
+    }
+
+    #[test]
+    fn test_parse_empty() {
+        assert_eq!(parse(""), None);
+        assert_eq!(parse("\n# nothing here\n"), None);
+    }
+}
@@ -0,0 +1,75 @@
+//! Provides support for the `yarnPath` setting in `.yarnrc.yml`, which Yarn 2+ projects use to
+//! check in the release of Yarn that they are run with.
+
+use std::path::{Path, PathBuf};
+
+use crate::error::{Context, ErrorKind, Fallible};
+use crate::fs::read_file;
+use log::debug;
+
+const YARNRC_FILE_NAME: &str = ".yarnrc.yml";
+
+/// Reads the `yarnPath` setting from the `.yarnrc.yml` in `dir`, if there is one
+///
+/// The returned path is resolved relative to `dir`, as Yarn does.
+pub(super) fn read_yarn_path(dir: &Path) -> Fallible<Option<PathBuf>> {
+    let file = dir.join(YARNRC_FILE_NAME);
+    let contents =
+        read_file(&file).with_context(|| ErrorKind::ReadYarnrcError { file: file.clone() })?;
+
+    Ok(contents
+        .as_ref()
+        .and_then(|c| parse_yarn_path(c))
+        .map(|path| {
+            debug!("Found `yarnPath` '{}' in {}", path, file.display());
+            dir.join(path)
+        }))
+}
+
+/// Finds the top-level `yarnPath` key in the contents of a `.yarnrc.yml`
+///
+/// This only handles the plain and quoted scalar values that Yarn itself writes, rather than
+/// parsing the file as full YAML.
+fn parse_yarn_path(contents: &str) -> Option<&str> {
+    contents.lines().find_map(|line| {
+        let mut parts = line.splitn(2, ':');
+        match (parts.next(), parts.next()) {
+            (Some("yarnPath"), Some(value)) => {
+                let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+                if value.is_empty() {
+                    None
+                } else {
+                    Some(value)
+                }
+            }
+            _ => None,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_yarn_path;
+
+    #[test]
+    fn test_parse_yarn_path() {
+        assert_eq!(
+            parse_yarn_path("yarnPath: .yarn/releases/yarn-3.2.1.cjs\n"),
+            Some(".yarn/releases/yarn-3.2.1.cjs")
+        );
+        assert_eq!(
+            parse_yarn_path("nodeLinker: node-modules\n\nyarnPath: \"releases/yarn.cjs\"\n"),
+            Some("releases/yarn.cjs")
+        );
+    }
+
+    #[test]
+    fn test_parse_yarn_path_missing() {
+        assert_eq!(parse_yarn_path("nodeLinker: node-modules\n"), None);
+        assert_eq!(
+            parse_yarn_path("packageExtensions:\n  yarnPath: nested\n"),
+            None
+        );
+        assert_eq!(parse_yarn_path("yarnPath:\n"), None);
+    }
+}
@@ -114,9 +114,17 @@ impl DefaultBinary {
                 .default_platform()?
                 .and_then(|ref plat| plat.yarn.clone()),
         };
+        // The same goes for pnpm, which some tools also invoke when it is the user's package manager
+        let pnpm = match bin_config.platform.pnpm {
+            Some(pnpm) => Some(pnpm),
+            None => session
+                .default_platform()?
+                .and_then(|ref plat| plat.pnpm.clone()),
+        };
         let platform = Platform {
             node: Sourced::with_binary(bin_config.platform.node),
             npm: bin_config.platform.npm.map(Sourced::with_binary),
+            pnpm: pnpm.map(Sourced::with_binary),
             yarn: yarn.map(Sourced::with_binary),
         };
 
@@ -3,14 +3,20 @@
 use std::env::{self, args_os, ArgsOs};
 use std::ffi::{OsStr, OsString};
 use std::fmt;
-use std::iter::empty;
+use std::iter::{empty, once};
+#[cfg(unix)]
+use std::os::unix::process::CommandExt;
 use std::path::Path;
 use std::process::{Command, ExitStatus, Output};
 
 use crate::command::create_command;
+#[cfg(unix)]
+use crate::error::VoltaError;
 use crate::error::{Context, ErrorKind, Fallible};
-use crate::platform::{CliPlatform, Sourced, System};
+use crate::platform::{CliPlatform, Sourced, System, VOLTA_CLI_PLATFORM};
 use crate::session::Session;
+#[cfg(unix)]
+use crate::signal::forward_signals;
 use crate::signal::pass_control_to_shim;
 use crate::style::tool_version;
 use log::debug;
@@ -19,6 +25,7 @@ pub mod binary;
 pub mod node;
 pub mod npm;
 pub mod npx;
+pub mod pnpm;
 pub mod yarn;
 
 const VOLTA_BYPASS: &str = "VOLTA_BYPASS";
@@ -46,6 +53,9 @@ where
     K: AsRef<OsStr>,
     V: AsRef<OsStr>,
 {
+    let cli = cli.inherit_from_env();
+    let exported_platform = cli.to_env_value();
+
     let mut command = if env::var_os(VOLTA_BYPASS).is_some() {
         ToolCommand::passthrough(
             &exe,
@@ -59,6 +69,7 @@ where
             Some("node") => node::command(cli, session)?,
             Some("npm") => npm::command(cli, session)?,
             Some("npx") => npx::command(cli, session)?,
+            Some("pnpm") => pnpm::command(cli, session)?,
             Some("yarn") => yarn::command(cli, session)?,
             _ => binary::command(exe, cli, session)?,
         }
@@ -67,6 +78,20 @@ where
     command.args(args);
     command.envs(envs);
 
+    // Any shims that the tool runs should use the same command-line versions
+    if let Some(platform) = exported_platform {
+        command.envs(once((VOLTA_CLI_PLATFORM, platform)));
+    }
+
+    // Replacing the Volta process with the tool means signals and the PID go straight to the tool,
+    // but it's only possible when there's nothing left to do once the tool exits
+    #[cfg(unix)]
+    {
+        if !session.publishes_events() {
+            return Err(command.exec());
+        }
+    }
+
     pass_control_to_shim();
     command.status()
 }
@@ -160,10 +185,36 @@ impl ToolCommand {
     /// Execute the command, returning its status
     ///
     /// Any failures will be wrapped with the Error value in `on_failure`
+    #[cfg(unix)]
+    pub(crate) fn status(mut self) -> Fallible<ExitStatus> {
+        let on_failure = self.on_failure;
+        self.command
+            .spawn()
+            .and_then(|mut child| {
+                let _forwarder = forward_signals(child.id());
+                child.wait()
+            })
+            .with_context(|| on_failure)
+    }
+
+    /// Execute the command, returning its status
+    ///
+    /// Any failures will be wrapped with the Error value in `on_failure`
+    #[cfg(windows)]
     pub(crate) fn status(mut self) -> Fallible<ExitStatus> {
         self.command.status().with_context(|| self.on_failure)
     }
 
+    /// Replace the current process with the command
+    ///
+    /// This only returns if the command could not be started, in which case the failure is
+    /// wrapped with the Error value in `on_failure`
+    #[cfg(unix)]
+    pub(crate) fn exec(mut self) -> VoltaError {
+        let error = self.command.exec();
+        VoltaError::from_source(error, self.on_failure)
+    }
+
     /// Execute the command, returning all of its output to the caller
     ///
     /// Any failures will be wrapped with the Error value in `on_failure`
@@ -213,7 +264,7 @@ fn intercept_global_installs() -> bool {
     env::var_os(UNSAFE_GLOBAL).is_none()
 }
 
-/// Distinguish global `add` commands in npm or yarn from all others.
+/// Distinguish global `add` commands in npm, pnpm, or yarn from all others.
 enum CommandArg {
     /// The command is a *global* add command.
     GlobalAdd(Option<OsString>),
@@ -0,0 +1,69 @@
+use std::env::args_os;
+use std::ffi::OsStr;
+
+use super::{debug_tool_message, intercept_global_installs, CommandArg, ToolCommand};
+use crate::error::{ErrorKind, Fallible};
+use crate::platform::{CliPlatform, Platform, Source};
+use crate::session::{ActivityKind, Session};
+use log::debug;
+
+pub(crate) fn command(cli: CliPlatform, session: &mut Session) -> Fallible<ToolCommand> {
+    session.add_event_start(ActivityKind::Pnpm);
+
+    match Platform::with_cli(cli, session)? {
+        Some(platform) => {
+            check_pnpm_platform(&platform)?;
+
+            if intercept_global_installs() {
+                if let CommandArg::GlobalAdd(package) = check_pnpm_add() {
+                    return Err(ErrorKind::NoGlobalInstalls { package }.into());
+                }
+            }
+
+            // Note: If we've gotten this far, we know there is a pnpm version set
+            debug_tool_message("pnpm", platform.pnpm.as_ref().unwrap());
+
+            let image = platform.checkout(session)?;
+            let path = image.path()?;
+            Ok(ToolCommand::direct(OsStr::new("pnpm"), &path))
+        }
+        None => {
+            debug!("Could not find Volta-managed pnpm, delegating to system");
+            ToolCommand::passthrough(OsStr::new("pnpm"), ErrorKind::NoPlatform)
+        }
+    }
+}
+
+/// Check that pnpm is set for the platform (project or default)
+fn check_pnpm_platform(platform: &Platform) -> Fallible<()> {
+    match &platform.pnpm {
+        Some(_) => Ok(()),
+        None => match platform.node.source {
+            Source::Project => Err(ErrorKind::NoProjectPnpm.into()),
+            Source::Default | Source::Binary => Err(ErrorKind::NoDefaultPnpm.into()),
+            Source::CommandLine => Err(ErrorKind::NoCommandLinePnpm.into()),
+        },
+    }
+}
+
+fn check_pnpm_add() -> CommandArg {
+    // pnpm global installs will have `-g` or `--global` somewhere in the
+    // argument list, the same as npm
+    if !args_os().any(|arg| arg == "-g" || arg == "--global") {
+        return CommandArg::NotGlobalAdd;
+    }
+
+    let mut args = args_os().skip(1).filter(|arg| match arg.to_str() {
+        Some(arg) => !arg.starts_with('-'),
+        None => true,
+    });
+    let command = args.next();
+
+    // They will be specified by the command `add`, or one of its aliases `install` and `i`
+    match command {
+        Some(cmd) if cmd == "add" || cmd == "install" || cmd == "i" => {
+            CommandArg::GlobalAdd(args.next())
+        }
+        _ => CommandArg::NotGlobalAdd,
+    }
+}
@@ -10,8 +10,28 @@ use log::debug;
 pub(crate) fn command(cli: CliPlatform, session: &mut Session) -> Fallible<ToolCommand> {
     session.add_event_start(ActivityKind::Yarn);
 
-    match get_yarn_platform(cli, session)? {
-        Some(platform) => {
+    let yarn_path = match session.project()? {
+        Some(project) => project.yarn_path()?,
+        None => None,
+    };
+
+    match Platform::with_cli(cli, session)? {
+        Some(mut platform) => {
+            if let Some(yarn_path) = yarn_path.filter(|path| path.is_file()) {
+                // The project's own release of Yarn takes the place of a Volta-managed Yarn, so it
+                // only needs Node from the platform
+                debug!("Using yarn from `yarnPath` at '{}'", yarn_path.display());
+                platform.yarn = None;
+
+                let image = platform.checkout(session)?;
+                let path = image.path()?;
+                let mut command = ToolCommand::direct(OsStr::new("node"), &path);
+                command.arg(yarn_path);
+                return Ok(command);
+            }
+
+            check_yarn_platform(&platform)?;
+
             if intercept_global_installs() {
                 if let CommandArg::GlobalAdd(package) = check_yarn_add() {
                     return Err(ErrorKind::NoGlobalInstalls { package }.into());
@@ -32,18 +52,15 @@ pub(crate) fn command(cli: CliPlatform, session: &mut Session) -> Fallible<ToolC
     }
 }
 
-/// Determine the correct platform (project or default) and check if yarn is set for that platform
-fn get_yarn_platform(cli: CliPlatform, session: &mut Session) -> Fallible<Option<Platform>> {
-    match Platform::with_cli(cli, session)? {
-        Some(platform) => match &platform.yarn {
-            Some(_) => Ok(Some(platform)),
-            None => match platform.node.source {
-                Source::Project => Err(ErrorKind::NoProjectYarn.into()),
-                Source::Default | Source::Binary => Err(ErrorKind::NoDefaultYarn.into()),
-                Source::CommandLine => Err(ErrorKind::NoCommandLineYarn.into()),
-            },
+/// Check that yarn is set for the platform (project or default)
+fn check_yarn_platform(platform: &Platform) -> Fallible<()> {
+    match &platform.yarn {
+        Some(_) => Ok(()),
+        None => match platform.node.source {
+            Source::Project => Err(ErrorKind::NoProjectYarn.into()),
+            Source::Default | Source::Binary => Err(ErrorKind::NoDefaultYarn.into()),
+            Source::CommandLine => Err(ErrorKind::NoCommandLineYarn.into()),
         },
-        None => Ok(None),
     }
 }
 
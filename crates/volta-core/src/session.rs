@@ -25,6 +25,7 @@ pub enum ActivityKind {
     Node,
     Npm,
     Npx,
+    Pnpm,
     Yarn,
     Volta,
     Tool,
@@ -36,6 +37,15 @@ pub enum ActivityKind {
     Which,
     Setup,
     Run,
+    Prune,
+    Doctor,
+    Env,
+    Trust,
+    ListRemote,
+    Outdated,
+    Upgrade,
+    Export,
+    Import,
 }
 
 impl Display for ActivityKind {
@@ -51,6 +61,7 @@ impl Display for ActivityKind {
             ActivityKind::Node => "node",
             ActivityKind::Npm => "npm",
             ActivityKind::Npx => "npx",
+            ActivityKind::Pnpm => "pnpm",
             ActivityKind::Yarn => "yarn",
             ActivityKind::Volta => "volta",
             ActivityKind::Tool => "tool",
@@ -62,6 +73,15 @@ impl Display for ActivityKind {
             ActivityKind::Completions => "completions",
             ActivityKind::Which => "which",
             ActivityKind::Run => "run",
+            ActivityKind::Prune => "prune",
+            ActivityKind::Doctor => "doctor",
+            ActivityKind::Env => "env",
+            ActivityKind::Trust => "trust",
+            ActivityKind::ListRemote => "list-remote",
+            ActivityKind::Outdated => "outdated",
+            ActivityKind::Upgrade => "upgrade",
+            ActivityKind::Export => "export",
+            ActivityKind::Import => "import",
         };
         f.write_str(s)
     }
@@ -144,6 +164,16 @@ impl Session {
         self.event_log.add_event_error(activity_kind, error)
     }
 
+    /// Determines whether the event log will be published when Volta exits, in which case Volta
+    /// needs to wait for any tool it runs to finish, so that the tool's exit code can be reported
+    pub fn publishes_events(&self) -> bool {
+        self.hooks()
+            .ok()
+            .and_then(|hooks| hooks.events())
+            .and_then(|events| events.publish.as_ref())
+            .is_some()
+    }
+
     fn publish_to_event_log(self) {
         let plugin_res = self
             .hooks()
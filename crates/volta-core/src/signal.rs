@@ -2,6 +2,14 @@ use std::process::exit;
 use std::sync::atomic::{AtomicBool, Ordering};
 
 use log::debug;
+#[cfg(unix)]
+use signal_hook::iterator::Signals;
+#[cfg(unix)]
+use signal_hook::{SIGHUP, SIGTERM, SIGUSR1, SIGUSR2};
+#[cfg(unix)]
+use std::io;
+#[cfg(unix)]
+use std::thread;
 
 static SHIM_HAS_CONTROL: AtomicBool = AtomicBool::new(false);
 const INTERRUPTED_EXIT_CODE: i32 = 130;
@@ -21,3 +29,79 @@ pub fn setup_signal_handler() {
         debug!("Unable to set Ctrl+C handler, SIGINT will not be handled correctly");
     }
 }
+
+/// Forwards the signals sent to Volta by process managers or `kill` on to a running tool, until
+/// the returned `SignalForwarder` is dropped
+///
+/// Signals generated by the terminal, such as SIGINT from Ctrl+C, are sent to the whole
+/// foreground process group, so the tool already receives those and they aren't forwarded.
+#[cfg(unix)]
+pub fn forward_signals(pid: u32) -> Option<SignalForwarder> {
+    let signals = match Signals::new(&[SIGHUP, SIGTERM, SIGUSR1, SIGUSR2]) {
+        Ok(signals) => signals,
+        Err(_) => {
+            debug!("Unable to set signal handlers, signals will not be forwarded to the tool");
+            return None;
+        }
+    };
+
+    let forwarded = signals.clone();
+    thread::spawn(move || {
+        for signal in forwarded.forever() {
+            // Safety: `kill` has no memory safety requirements
+            let result = unsafe { libc::kill(pid as libc::pid_t, signal) };
+
+            // If the tool has already exited, there is nothing to forward to, so Volta handles
+            // the signal itself, the same way it would without the handlers
+            if result != 0 && io::Error::last_os_error().raw_os_error() == Some(libc::ESRCH) {
+                // Safety: Resetting the disposition and raising a signal have no memory safety
+                // requirements
+                unsafe {
+                    libc::signal(signal, libc::SIG_DFL);
+                    libc::raise(signal);
+                }
+            }
+        }
+    });
+
+    Some(SignalForwarder { signals })
+}
+
+/// Stops forwarding signals when dropped, restoring the default handling once the forwarding
+/// thread has finished
+#[cfg(unix)]
+pub struct SignalForwarder {
+    signals: Signals,
+}
+
+#[cfg(unix)]
+impl Drop for SignalForwarder {
+    fn drop(&mut self) {
+        self.signals.close();
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::Command;
+
+    #[test]
+    fn test_forward_signals() {
+        let mut child = Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .expect("could not start child process");
+        let forwarder = forward_signals(child.id());
+        assert!(forwarder.is_some());
+
+        // Since the handler is registered, this doesn't terminate the test process
+        unsafe {
+            libc::raise(SIGTERM);
+        }
+
+        let status = child.wait().expect("child process did not exit");
+        assert_eq!(status.signal(), Some(SIGTERM));
+    }
+}
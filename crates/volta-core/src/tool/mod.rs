@@ -1,22 +1,32 @@
 use std::fmt::{self, Display};
+use std::path::{Path, PathBuf};
 
 use crate::error::{ErrorKind, Fallible};
+use crate::fs::{
+    ensure_dir_does_not_exist, ensure_file_does_not_exist, lock_tool_version, FileLock,
+};
+use crate::layout::volta_home;
 use crate::session::Session;
 use crate::style::{note_prefix, success_prefix, tool_version};
 use crate::version::VersionSpec;
-use log::{debug, info};
+use log::{debug, info, warn};
+use semver::Version;
 
 pub mod node;
 pub mod npm;
 pub mod package;
+pub mod pnpm;
+mod registry;
 mod serial;
 pub mod yarn;
 
 pub use node::{
-    load_default_npm_version, Node, NODE_DISTRO_ARCH, NODE_DISTRO_EXTENSION, NODE_DISTRO_OS,
+    load_default_npm_version, Node, NodeDistro, NODE_DISTRO_ARCH, NODE_DISTRO_EXTENSION,
+    NODE_DISTRO_OS,
 };
 pub use npm::{BundledNpm, Npm};
 pub use package::{bin_full_path, BinConfig, BinLoader, Package, PackageConfig, PackageDetails};
+pub use pnpm::Pnpm;
 pub use yarn::Yarn;
 
 #[inline]
@@ -39,6 +49,16 @@ fn info_pinned<T: Display + Sized>(tool: T) {
     info!("{} pinned {} in package.json", success_prefix(), tool);
 }
 
+#[inline]
+fn info_uninstalled<T: Display + Sized>(tool: T) {
+    info!("{} {} uninstalled", success_prefix(), tool);
+}
+
+#[inline]
+fn warn_not_uninstalled<T: Display + Sized>(tool: T) {
+    warn!("No {} found to uninstall", tool);
+}
+
 #[inline]
 fn info_project_version<T: Display + Sized>(tool: T) {
     info!(
@@ -58,12 +78,23 @@ pub trait Tool: Display {
     fn pin(self: Box<Self>, session: &mut Session) -> Fallible<()>;
 }
 
+/// A version of a tool that is available to fetch, as listed in the tool's index
+#[derive(Debug)]
+pub struct RemoteVersion {
+    pub version: Version,
+    /// The tags that point to this version, such as npm dist-tags or `lts/<codename>` for Node
+    pub tags: Vec<String>,
+    /// Whether this is a long-term support release of Node
+    pub lts: bool,
+}
+
 /// Specification for a tool and its associated version.
 #[derive(Debug)]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum Spec {
     Node(VersionSpec),
     Npm(VersionSpec),
+    Pnpm(VersionSpec),
     Yarn(VersionSpec),
     Package(String, VersionSpec),
 }
@@ -80,6 +111,10 @@ impl Spec {
                 Some(version) => Ok(Box::new(Npm::new(version))),
                 None => Ok(Box::new(BundledNpm)),
             },
+            Spec::Pnpm(version) => {
+                let version = pnpm::resolve(version, session)?;
+                Ok(Box::new(Pnpm::new(version)))
+            }
             Spec::Yarn(version) => {
                 let version = yarn::resolve(version, session)?;
                 Ok(Box::new(Yarn::new(version)))
@@ -91,24 +126,53 @@ impl Spec {
         }
     }
 
-    /// Uninstall a tool, removing it from the local inventory
+    /// Resolve a tool spec into a Tool that can be pinned in a project
     ///
-    /// This is implemented on Spec, instead of Resolved, because there is currently no need to
-    /// resolve the specific version before uninstalling a tool.
-    pub fn uninstall(self) -> Fallible<()> {
+    /// Node prereleases (release candidates and nightly builds) are rejected unless allowed, since
+    /// pinning one would have everyone working on the project use it.
+    pub fn resolve_pin(
+        self,
+        session: &mut Session,
+        allow_prerelease: bool,
+    ) -> Fallible<Box<dyn Tool>> {
         match self {
-            Spec::Node(_) => Err(ErrorKind::Unimplemented {
-                feature: "Uninstalling node".into(),
-            }
-            .into()),
-            Spec::Npm(_) => Err(ErrorKind::Unimplemented {
-                feature: "Uninstalling npm".into(),
+            Spec::Node(version) => {
+                let version = node::resolve(version, session)?;
+                if !version.pre.is_empty() && !allow_prerelease {
+                    return Err(ErrorKind::PrereleasePinError {
+                        version: version.to_string(),
+                    }
+                    .into());
+                }
+                Ok(Box::new(Node::new(version)))
             }
-            .into()),
-            Spec::Yarn(_) => Err(ErrorKind::Unimplemented {
-                feature: "Uninstalling yarn".into(),
+            spec => spec.resolve(session),
+        }
+    }
+
+    /// Uninstall a tool, removing it from the local inventory
+    ///
+    /// This is implemented on Spec, instead of Resolved, because uninstalling never needs to look
+    /// up a version remotely: Node, npm, pnpm, and Yarn require an exact version, and packages are
+    /// removed regardless of version.
+    pub fn uninstall(self, session: &mut Session) -> Fallible<()> {
+        match self {
+            Spec::Node(version) => node::uninstall(exact_version("node", version)?, session),
+            Spec::Npm(version) => npm::uninstall(exact_version("npm", version)?, session),
+            // Before pnpm was a supported tool, it could only be installed as a package, so a bare
+            // `pnpm` with no version removes that package if it is still installed
+            Spec::Pnpm(VersionSpec::None)
+                if volta_home()?.default_package_config_file("pnpm").exists() =>
+            {
+                package::uninstall("pnpm")?;
+                // Removing the package also removed its `pnpm` shim, which is the default shim
+                // for the pnpm tool on Unix, so it needs to be restored
+                #[cfg(unix)]
+                crate::shim::create("pnpm")?;
+                Ok(())
             }
-            .into()),
+            Spec::Pnpm(version) => pnpm::uninstall(exact_version("pnpm", version)?, session),
+            Spec::Yarn(version) => yarn::uninstall(exact_version("yarn", version)?, session),
             Spec::Package(name, _) => {
                 package::uninstall(&name)?;
                 Ok(())
@@ -122,6 +186,7 @@ impl Display for Spec {
         let s = match self {
             Spec::Node(ref version) => tool_version("node", version),
             Spec::Npm(ref version) => tool_version("npm", version),
+            Spec::Pnpm(ref version) => tool_version("pnpm", version),
             Spec::Yarn(ref version) => tool_version("yarn", version),
             Spec::Package(ref name, ref version) => tool_version(name, version),
         };
@@ -129,6 +194,44 @@ impl Display for Spec {
     }
 }
 
+/// Extract the exact version from a spec, as a tool can only be uninstalled one version at a time
+fn exact_version(tool: &str, version: VersionSpec) -> Fallible<Version> {
+    match version {
+        VersionSpec::Exact(version) => Ok(version),
+        _ => Err(ErrorKind::UninstallRequiresVersion { tool: tool.into() }.into()),
+    }
+}
+
+/// Remove the unpacked image and any cached files for a tool version from the inventory
+///
+/// Returns `true` if the image existed, i.e. if the tool version was actually fetched.
+fn remove_from_inventory(image_dir: &Path, cached_files: &[PathBuf]) -> Fallible<bool> {
+    let found = image_dir.exists();
+    ensure_dir_does_not_exist(&image_dir)?;
+
+    for file in cached_files {
+        ensure_file_does_not_exist(file)?;
+    }
+
+    Ok(found)
+}
+
+/// Lock fetching a tool version, unless `available` shows that it has already been fetched
+///
+/// Availability should be checked again while the lock is held, since another process may have
+/// finished fetching the same version while this one was waiting. That process's image can then
+/// be used as-is, rather than fetching the version a second time.
+fn lock_fetch<F>(tool: &str, version: &Version, available: F) -> Fallible<Option<FileLock>>
+where
+    F: FnOnce(&Version) -> Fallible<bool>,
+{
+    if available(version)? {
+        Ok(None)
+    } else {
+        lock_tool_version(tool, &version.to_string()).map(Some)
+    }
+}
+
 fn download_tool_error(tool: Spec, from_url: impl AsRef<str>) -> impl FnOnce() -> ErrorKind {
     let from_url = from_url.as_ref().to_string();
     || ErrorKind::DownloadToolNetworkError { tool, from_url }
@@ -1,31 +1,49 @@
 //! Provides fetcher for Node distributions
 
 use std::fs::{read_to_string, rename, write, File};
+use std::io;
 use std::path::{Path, PathBuf};
 
 use super::NodeVersion;
 use crate::error::{Context, ErrorKind, Fallible};
-use crate::fs::{create_staging_dir, create_staging_file};
+use crate::fs::{create_staging_dir, create_staging_file, ensure_dir_does_not_exist, read_file};
 use crate::hook::ToolHooks;
+use crate::http;
 use crate::layout::volta_home;
+use crate::offline::ensure_online;
 use crate::style::{progress_bar, tool_version};
-use crate::tool::{self, download_tool_error, Node};
+use crate::tool::{self, download_tool_error, Node, NodeDistro};
 use crate::version::{parse_version, VersionSpec};
 use archive::{self, Archive};
+use attohttpc::Response;
 use cfg_if::cfg_if;
 use fs_utils::ensure_containing_dir_exists;
 use log::debug;
 use semver::Version;
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+/// The name of the file listing the SHA-256 checksums for every file in a Node release
+const CHECKSUM_FILE: &str = "SHASUMS256.txt";
 
 cfg_if! {
     if #[cfg(feature = "mock-network")] {
-        fn public_node_server_root() -> String {
+        fn public_node_server_root(_version: &Version) -> String {
             mockito::SERVER_URL.to_string()
         }
     } else {
-        fn public_node_server_root() -> String {
-            "https://nodejs.org/dist".to_string()
+        /// Returns the root URL of the public Node server that publishes the given version
+        fn public_node_server_root(version: &Version) -> String {
+            if let Some(channel) = super::NodeChannel::of(version) {
+                return format!("https://nodejs.org/download/{}", channel);
+            }
+
+            match super::NodeDistro::current() {
+                super::NodeDistro::Official => "https://nodejs.org/dist".to_string(),
+                super::NodeDistro::Musl => {
+                    "https://unofficial-builds.nodejs.org/download/release".to_string()
+                }
+            }
         }
     }
 }
@@ -46,9 +64,11 @@ fn npm_manifest_path(version: &str) -> PathBuf {
 pub fn fetch(version: &Version, hooks: Option<&ToolHooks<Node>>) -> Fallible<NodeVersion> {
     let home = volta_home()?;
     let node_dir = home.node_inventory_dir();
-    let cache_file = node_dir.join(Node::archive_filename(&version.to_string()));
+    let version_str = version.to_string();
+    let cache_file = node_dir.join(Node::archive_filename(&version_str));
+    let checksum_file = home.node_distro_checksum(&Node::archive_filename(&version_str));
 
-    let (archive, staging) = match load_cached_distro(&cache_file) {
+    let (archive, staging) = match load_cached_distro(&cache_file, &checksum_file) {
         Some(archive) => {
             debug!(
                 "Loading {} from cached archive at '{}'",
@@ -58,16 +78,24 @@ pub fn fetch(version: &Version, hooks: Option<&ToolHooks<Node>>) -> Fallible<Nod
             (archive, None)
         }
         None => {
+            ensure_online(tool_version("node", &version))?;
             let staging = create_staging_file()?;
             let remote_url = determine_remote_url(&version, hooks)?;
+            let checksum = match determine_checksum_url(&version, hooks)? {
+                Some(checksum_url) => Some(fetch_checksum(&version, &checksum_url)?),
+                None => None,
+            };
             let archive = fetch_remote_distro(&version, &remote_url, staging.path())?;
-            (archive, Some(staging))
+            (archive, Some((staging, checksum)))
         }
     };
 
-    let node_version = unpack_archive(archive, version)?;
+    let verify = staging
+        .as_ref()
+        .and_then(|(file, checksum)| checksum.as_ref().map(|sum| (file.path(), sum.as_str())));
+    let node_version = unpack_archive(archive, version, verify)?;
 
-    if let Some(staging_file) = staging {
+    if let Some((staging_file, checksum)) = staging {
         ensure_containing_dir_exists(&cache_file).with_context(|| {
             ErrorKind::ContainingDirError {
                 path: cache_file.clone(),
@@ -78,13 +106,36 @@ pub fn fetch(version: &Version, hooks: Option<&ToolHooks<Node>>) -> Fallible<Nod
             .with_context(|| ErrorKind::PersistInventoryError {
                 tool: "Node".into(),
             })?;
+
+        // Save the checksum so that the cached archive can be validated before reuse. Without a
+        // published checksum to verify against, the digest of the downloaded archive is saved.
+        let checksum = match checksum {
+            Some(checksum) => checksum,
+            None => calculate_checksum(&cache_file).with_context(|| {
+                ErrorKind::PersistInventoryError {
+                    tool: "Node".into(),
+                }
+            })?,
+        };
+        write(&checksum_file, checksum.as_bytes()).with_context(|| {
+            ErrorKind::PersistInventoryError {
+                tool: "Node".into(),
+            }
+        })?;
     }
 
     Ok(node_version)
 }
 
 /// Unpack the node archive into the image directory so that it is ready for use
-fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<NodeVersion> {
+///
+/// If `verify` is provided, the downloaded archive file is checked against the expected checksum
+/// once it has been completely read, before anything is moved into the image directory.
+fn unpack_archive(
+    archive: Box<dyn Archive>,
+    version: &Version,
+    verify: Option<(&Path, &str)>,
+) -> Fallible<NodeVersion> {
     let temp = create_staging_dir()?;
     debug!("Unpacking node into '{}'", temp.path().display());
 
@@ -106,25 +157,40 @@ fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<Node
             version: version_string.clone(),
         })?;
 
+    if let Some((archive_file, expected)) = verify {
+        verify_checksum(archive_file, expected, &version_string)?;
+    }
+
     // Save the npm version number in the npm version file for this distro
     let npm_package_json = temp.path().join(npm_manifest_path(&version_string));
     let npm = Manifest::version(&npm_package_json)?;
     save_default_npm_version(&version, &npm)?;
 
-    let dest = volta_home()?.node_image_dir(&version_string);
+    let home = volta_home()?;
+    let dest = home.node_image_dir(&version_string);
     ensure_containing_dir_exists(&dest)
         .with_context(|| ErrorKind::ContainingDirError { path: dest.clone() })?;
 
+    // An existing image must be for another flavor of distro, so it is replaced
+    ensure_dir_does_not_exist(&dest)?;
+
     rename(
         temp.path().join(Node::archive_basename(&version_string)),
         &dest,
     )
     .with_context(|| ErrorKind::SetupToolImageError {
         tool: "Node".into(),
-        version: version_string,
+        version: version_string.clone(),
         dir: dest.clone(),
     })?;
 
+    let distro_file = home.node_image_distro_file(&version_string);
+    write(&distro_file, NodeDistro::current().to_string()).with_context(|| {
+        ErrorKind::PersistInventoryError {
+            tool: "Node".into(),
+        }
+    })?;
+
     progress.finish_and_clear();
 
     // Note: We write these after the progress bar is finished to avoid display bugs with re-renders of the progress
@@ -138,10 +204,23 @@ fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<Node
 }
 
 /// Return the archive if it is valid. It may have been corrupted or interrupted in the middle of
-/// downloading.
-// ISSUE(#134) - verify checksum
-fn load_cached_distro(file: &Path) -> Option<Box<dyn Archive>> {
+/// downloading, so it is only considered valid if it matches the checksum saved alongside it.
+///
+/// Archives cached by earlier versions of Volta have no saved checksum, so they are used as-is.
+fn load_cached_distro(file: &Path, checksum_file: &Path) -> Option<Box<dyn Archive>> {
     if file.is_file() {
+        if let Some(stored_checksum) = read_file(checksum_file).ok()? {
+            let calculated_checksum = calculate_checksum(file).ok()?;
+
+            if stored_checksum.trim() != calculated_checksum {
+                debug!(
+                    "Cached archive at '{}' failed checksum verification",
+                    file.display()
+                );
+                return None;
+            }
+        }
+
         let file = File::open(file).ok()?;
         archive::load_native(file).ok()
     } else {
@@ -149,6 +228,107 @@ fn load_cached_distro(file: &Path) -> Option<Box<dyn Archive>> {
     }
 }
 
+/// Calculate the hex-encoded SHA-256 checksum of a file
+fn calculate_checksum(file: &Path) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(file)?, &mut hasher)?;
+    Ok(hex::encode(hasher.result()))
+}
+
+/// Verify that a downloaded archive matches the checksum published for it
+fn verify_checksum(file: &Path, expected: &str, version: &str) -> Fallible<()> {
+    let actual = calculate_checksum(file).with_context(|| ErrorKind::UnpackArchiveError {
+        tool: "Node".into(),
+        version: version.into(),
+    })?;
+
+    if actual == expected {
+        debug!("Verified checksum of {}", tool_version("node", version));
+        Ok(())
+    } else {
+        Err(ErrorKind::ChecksumMismatch {
+            tool: "node".into(),
+            version: version.into(),
+            expected: expected.into(),
+            actual,
+        }
+        .into())
+    }
+}
+
+/// Determine the URL of the checksum file, using the hooks if available
+///
+/// A custom distro hook may point to builds that don't match the public checksums, so unless it
+/// is paired with a checksum hook, the download can't be verified.
+fn determine_checksum_url(
+    version: &Version,
+    hooks: Option<&ToolHooks<Node>>,
+) -> Fallible<Option<String>> {
+    match hooks {
+        Some(&ToolHooks {
+            checksum: Some(ref hook),
+            ..
+        }) => {
+            debug!("Using node.checksum hook to determine checksum URL");
+            hook.resolve(&version, CHECKSUM_FILE).map(Some)
+        }
+        Some(&ToolHooks {
+            distro: Some(_), ..
+        }) => {
+            debug!(
+                "No node.checksum hook provided for custom node.distro hook, skipping verification"
+            );
+            Ok(None)
+        }
+        _ => Ok(Some(format!(
+            "{}/v{}/{}",
+            public_node_server_root(version),
+            version,
+            CHECKSUM_FILE
+        ))),
+    }
+}
+
+/// Fetch the published checksum for the distro archive of the given version
+fn fetch_checksum(version: &Version, url: &str) -> Fallible<String> {
+    debug!(
+        "Fetching checksums for {} from {}",
+        tool_version("node", version),
+        url
+    );
+    let checksums = http::get(url)?
+        .send()
+        .and_then(Response::error_for_status)
+        .and_then(Response::text)
+        .with_context(|| ErrorKind::ChecksumFetchError {
+            tool: "Node".into(),
+            from_url: url.into(),
+        })?;
+
+    let filename = Node::archive_filename(&version.to_string());
+    find_checksum(&checksums, &filename).ok_or_else(|| {
+        ErrorKind::ChecksumNotFound {
+            file: filename,
+            from_url: url.into(),
+        }
+        .into()
+    })
+}
+
+/// Find the checksum for a file in the contents of a `SHASUMS256.txt` file
+///
+/// Each line of the file has the format `<checksum>  <filename>`
+fn find_checksum(checksums: &str, filename: &str) -> Option<String> {
+    checksums.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        let checksum = parts.next()?;
+        match parts.next() {
+            Some(file) if file == filename => Some(checksum.to_lowercase()),
+            _ => None,
+        }
+    })
+}
+
 /// Determine the remote URL to download from, using the hooks if available
 fn determine_remote_url(version: &Version, hooks: Option<&ToolHooks<Node>>) -> Fallible<String> {
     let version_str = version.to_string();
@@ -163,7 +343,7 @@ fn determine_remote_url(version: &Version, hooks: Option<&ToolHooks<Node>>) -> F
         }
         _ => Ok(format!(
             "{}/v{}/{}",
-            public_node_server_root(),
+            public_node_server_root(version),
             version,
             distro_file_name
         )),
@@ -218,3 +398,42 @@ fn save_default_npm_version(node: &Version, npm: &Version) -> Fallible<()> {
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::find_checksum;
+
+    const CHECKSUMS: &str = "\
+3e2e4a4d1a2e8cfbf3a01de4a6e1b8f7b3e5a52d0d2a8e8b5c6f3d2b1a0f9e8d  node-v12.16.1-darwin-x64.tar.gz
+5a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f9  node-v12.16.1-linux-x64.tar.gz
+ABCDEF0123456789ABCDEF0123456789ABCDEF0123456789ABCDEF0123456789  node-v12.16.1-win-x64.zip
+";
+
+    #[test]
+    fn test_find_checksum() {
+        assert_eq!(
+            find_checksum(CHECKSUMS, "node-v12.16.1-linux-x64.tar.gz"),
+            Some("5a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f9".to_string())
+        );
+    }
+
+    #[test]
+    fn test_find_checksum_normalizes_case() {
+        assert_eq!(
+            find_checksum(CHECKSUMS, "node-v12.16.1-win-x64.zip"),
+            Some("abcdef0123456789abcdef0123456789abcdef0123456789abcdef0123456789".to_string())
+        );
+    }
+
+    #[test]
+    fn test_find_checksum_missing() {
+        assert_eq!(
+            find_checksum(CHECKSUMS, "node-v12.16.1-linux-arm64.tar.gz"),
+            None
+        );
+        assert_eq!(
+            find_checksum(CHECKSUMS, "node-v12.16.1-linux-x64.tar"),
+            None
+        );
+    }
+}
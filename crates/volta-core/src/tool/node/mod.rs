@@ -1,14 +1,22 @@
+use std::env;
 use std::fmt::{self, Display};
+use std::fs::write;
+use std::str::FromStr;
 
 use super::{
-    debug_already_fetched, info_fetched, info_installed, info_pinned, info_project_version, Tool,
+    debug_already_fetched, info_fetched, info_installed, info_pinned, info_project_version,
+    info_uninstalled, lock_fetch, remove_from_inventory, warn_not_uninstalled, Tool,
 };
 use crate::error::{ErrorKind, Fallible};
-use crate::inventory::node_available;
+use crate::fs::read_file;
+use crate::inventory::{node_available, package_configs};
+use crate::layout::volta_home;
 use crate::session::Session;
 use crate::style::{note_prefix, tool_version};
 use cfg_if::cfg_if;
-use log::info;
+use fs_utils::ensure_containing_dir_exists;
+use lazy_static::lazy_static;
+use log::{debug, info, warn};
 use semver::Version;
 
 mod fetch;
@@ -16,7 +24,8 @@ mod resolve;
 mod serial;
 
 pub use fetch::load_default_npm_version;
-pub use resolve::resolve;
+pub(crate) use resolve::{index_expiry, resolve_with_hooks};
+pub use resolve::{remote_versions, resolve};
 
 cfg_if! {
     if #[cfg(target_os = "windows")] {
@@ -61,6 +70,206 @@ cfg_if! {
     }
 }
 
+/// The environment variable for forcing the flavor of Node distro, instead of detecting it
+const VOLTA_NODE_DISTRO: &str = "VOLTA_NODE_DISTRO";
+
+lazy_static! {
+    static ref FORCED_DISTRO: Option<NodeDistro> = NodeDistro::forced();
+    static ref CURRENT_DISTRO: NodeDistro = NodeDistro::detect();
+}
+
+/// The flavor of Node distro that runs on this system
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NodeDistro {
+    /// The builds published on nodejs.org, which are linked against glibc on Linux
+    Official,
+    /// The builds linked against musl libc (as used by Alpine Linux), which are only published
+    /// on unofficial-builds.nodejs.org
+    Musl,
+}
+
+impl NodeDistro {
+    /// The flavor to download on this system, either from `VOLTA_NODE_DISTRO` or detected from
+    /// the system's C library
+    ///
+    /// Detecting the C library runs `ldd`, so the result is recorded in the Volta directory and
+    /// reused from then on.
+    pub fn current() -> Self {
+        *CURRENT_DISTRO
+    }
+
+    /// The flavor on this system if it is known without detecting it, i.e. if it is forced with
+    /// `VOLTA_NODE_DISTRO` or has been recorded by an earlier detection
+    ///
+    /// This is cheap enough to check on every shim call, unlike `current`.
+    pub(crate) fn known() -> Option<Self> {
+        (*FORCED_DISTRO).or_else(NodeDistro::recorded)
+    }
+
+    fn forced() -> Option<Self> {
+        match env::var(VOLTA_NODE_DISTRO) {
+            Ok(ref value) if !value.is_empty() => match value.parse() {
+                Ok(distro) => Some(distro),
+                Err(_) => {
+                    warn!(
+                        "Ignoring unrecognized {} value '{}' (expected 'official' or 'musl')",
+                        VOLTA_NODE_DISTRO, value
+                    );
+                    None
+                }
+            },
+            _ => None,
+        }
+    }
+
+    fn recorded() -> Option<Self> {
+        let distro_file = volta_home().ok()?.node_distro_file();
+        read_file(distro_file)
+            .ok()
+            .and_then(|contents| contents)
+            .and_then(|contents| contents.trim().parse().ok())
+    }
+
+    fn detect() -> Self {
+        if let Some(distro) = NodeDistro::known() {
+            return distro;
+        }
+
+        let distro = detect_libc();
+
+        // Failing to record the flavor only means that it is detected again next time
+        if let Ok(home) = volta_home() {
+            let distro_file = home.node_distro_file();
+            if let Err(error) = ensure_containing_dir_exists(&distro_file)
+                .and_then(|_| write(distro_file, distro.to_string()))
+            {
+                debug!("Could not record the Node distro flavor: {}", error);
+            }
+        }
+
+        distro
+    }
+
+    /// The system architecture component of the distro's filename
+    pub fn arch(self) -> String {
+        match self {
+            NodeDistro::Official => NODE_DISTRO_ARCH.to_string(),
+            NodeDistro::Musl => format!("{}-musl", NODE_DISTRO_ARCH),
+        }
+    }
+
+    /// The flavor of the unpacked image for a Node version
+    ///
+    /// Images unpacked before the flavor was recorded are official builds, since those were the
+    /// only ones Volta downloaded.
+    pub(crate) fn of_image(version: &str) -> Fallible<Self> {
+        let distro_file = volta_home()?.node_image_distro_file(version);
+        let distro = read_file(&distro_file)
+            .ok()
+            .and_then(|contents| contents)
+            .and_then(|contents| contents.trim().parse().ok());
+
+        Ok(distro.unwrap_or(NodeDistro::Official))
+    }
+}
+
+impl Display for NodeDistro {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            NodeDistro::Official => "official",
+            NodeDistro::Musl => "musl",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for NodeDistro {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "official" => Ok(NodeDistro::Official),
+            "musl" => Ok(NodeDistro::Musl),
+            _ => Err(()),
+        }
+    }
+}
+
+cfg_if! {
+    if #[cfg(target_os = "linux")] {
+        /// Determines the flavor from the C library, using the output of `ldd --version`, which
+        /// names musl on systems that use it
+        ///
+        /// Checking for musl's dynamic loader isn't enough, since glibc systems can have musl
+        /// installed alongside for building static binaries.
+        fn detect_libc() -> NodeDistro {
+            match std::process::Command::new("ldd").arg("--version").output() {
+                Ok(output)
+                    if String::from_utf8_lossy(&output.stdout).contains("musl")
+                        || String::from_utf8_lossy(&output.stderr).contains("musl") =>
+                {
+                    NodeDistro::Musl
+                }
+                _ => NodeDistro::Official,
+            }
+        }
+    } else {
+        /// Outside of Linux, the official builds are the only ones available
+        fn detect_libc() -> NodeDistro {
+            NodeDistro::Official
+        }
+    }
+}
+
+/// A prerelease channel of Node, which is published separately from the stable releases
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum NodeChannel {
+    /// Release candidates, with versions like `16.0.0-rc.1`
+    Rc,
+    /// Nightly builds, with versions like `17.0.0-nightly20210601e1b2c3d4`
+    Nightly,
+}
+
+impl NodeChannel {
+    /// The channel that a version was published on, if it isn't a stable release
+    pub(crate) fn of(version: &Version) -> Option<Self> {
+        if version.pre.is_empty() {
+            return None;
+        }
+
+        let version = version.to_string();
+        let prerelease = version.splitn(2, '-').nth(1).unwrap_or_default();
+        if prerelease.starts_with("rc") {
+            Some(NodeChannel::Rc)
+        } else if prerelease.starts_with("nightly") {
+            Some(NodeChannel::Nightly)
+        } else {
+            None
+        }
+    }
+}
+
+impl FromStr for NodeChannel {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rc" => Ok(NodeChannel::Rc),
+            "nightly" => Ok(NodeChannel::Nightly),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Display for NodeChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            NodeChannel::Rc => "rc",
+            NodeChannel::Nightly => "nightly",
+        })
+    }
+}
+
 /// A full Node version including not just the version of Node itself
 /// but also the specific version of npm installed globally with that
 /// Node installation.
@@ -94,7 +303,12 @@ impl Node {
     }
 
     pub fn archive_basename(version: &str) -> String {
-        format!("node-v{}-{}-{}", version, NODE_DISTRO_OS, NODE_DISTRO_ARCH)
+        format!(
+            "node-v{}-{}-{}",
+            version,
+            NODE_DISTRO_OS,
+            NodeDistro::current().arch()
+        )
     }
 
     pub fn archive_filename(version: &str) -> String {
@@ -106,6 +320,8 @@ impl Node {
     }
 
     pub(crate) fn ensure_fetched(&self, session: &mut Session) -> Fallible<NodeVersion> {
+        let _lock = lock_fetch("node", &self.version, node_available)?;
+
         if node_available(&self.version)? {
             debug_already_fetched(self);
             let npm = fetch::load_default_npm_version(&self.version)?;
@@ -190,6 +406,57 @@ impl Tool for Node {
     }
 }
 
+/// Uninstall a version of Node, removing it from the local inventory
+///
+/// This removes the unpacked image, the cached archive and its checksum, and the saved version
+/// of the bundled npm. The default version of Node can't be removed, since that would leave the
+/// default platform without a runtime.
+pub fn uninstall(version: Version, session: &mut Session) -> Fallible<()> {
+    if let Some(platform) = session.toolchain()?.platform() {
+        if platform.node == version {
+            return Err(ErrorKind::CannotUninstallDefaultNode {
+                version: version.to_string(),
+            }
+            .into());
+        }
+    }
+
+    let home = volta_home()?;
+    let version_str = version.to_string();
+    let found = remove_from_inventory(
+        &home.node_image_dir(&version_str),
+        &[
+            home.node_inventory_dir()
+                .join(Node::archive_filename(&version_str)),
+            home.node_distro_checksum(&Node::archive_filename(&version_str)),
+            home.node_npm_version_file(&version_str),
+            home.node_image_distro_file(&version_str),
+        ],
+    )?;
+
+    let tool = tool_version("node", &version);
+    if !found {
+        warn_not_uninstalled(tool);
+        return Ok(());
+    }
+
+    let packages: Vec<String> = package_configs()?
+        .into_iter()
+        .filter(|config| config.platform.node == version)
+        .map(|config| config.name)
+        .collect();
+    if !packages.is_empty() {
+        warn!(
+            "{} was used to install {}, so it will be fetched again the next time they are run",
+            tool,
+            packages.join(", ")
+        );
+    }
+
+    info_uninstalled(tool);
+    Ok(())
+}
+
 impl Display for Node {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str(&tool_version("node", &self.version))
@@ -204,7 +471,11 @@ mod tests {
     fn test_node_archive_basename() {
         assert_eq!(
             Node::archive_basename("1.2.3"),
-            format!("node-v1.2.3-{}-{}", NODE_DISTRO_OS, NODE_DISTRO_ARCH)
+            format!(
+                "node-v1.2.3-{}-{}",
+                NODE_DISTRO_OS,
+                NodeDistro::current().arch()
+            )
         );
     }
 
@@ -214,8 +485,43 @@ mod tests {
             Node::archive_filename("1.2.3"),
             format!(
                 "node-v1.2.3-{}-{}.{}",
-                NODE_DISTRO_OS, NODE_DISTRO_ARCH, NODE_DISTRO_EXTENSION
+                NODE_DISTRO_OS,
+                NodeDistro::current().arch(),
+                NODE_DISTRO_EXTENSION
             )
         );
     }
+
+    #[test]
+    fn test_node_channel_of() {
+        let channel = |version: &str| NodeChannel::of(&version.parse().unwrap());
+
+        assert_eq!(channel("16.0.0-rc.1"), Some(NodeChannel::Rc));
+        assert_eq!(
+            channel("17.0.0-nightly20210601e1b2c3d4"),
+            Some(NodeChannel::Nightly)
+        );
+        assert_eq!(channel("14.17.0"), None);
+        assert_eq!(channel("14.17.0-beta.1"), None);
+    }
+
+    #[test]
+    fn test_node_distro_from_str() {
+        assert_eq!("official".parse::<NodeDistro>(), Ok(NodeDistro::Official));
+        assert_eq!("musl".parse::<NodeDistro>(), Ok(NodeDistro::Musl));
+        assert_eq!("glibc".parse::<NodeDistro>(), Err(()));
+
+        for distro in &[NodeDistro::Official, NodeDistro::Musl] {
+            assert_eq!(distro.to_string().parse::<NodeDistro>(), Ok(*distro));
+        }
+    }
+
+    #[test]
+    fn test_node_distro_arch() {
+        assert_eq!(NodeDistro::Official.arch(), NODE_DISTRO_ARCH);
+        assert_eq!(
+            NodeDistro::Musl.arch(),
+            format!("{}-musl", NODE_DISTRO_ARCH)
+        );
+    }
 }
@@ -1,21 +1,26 @@
 //! Provides resolution of Node requirements into specific versions, using the NodeJS index
 
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::fs::File;
 use std::io::Write;
+use std::path::PathBuf;
 use std::str::FromStr;
 use std::time::{Duration, SystemTime};
 
 use super::super::registry_fetch_error;
-use super::serial;
+use super::{serial, NodeChannel};
 use crate::error::{Context, ErrorKind, Fallible};
 use crate::fs::{create_staging_file, read_file};
 use crate::hook::ToolHooks;
+use crate::http;
+use crate::inventory::node_versions;
 use crate::layout::volta_home;
+use crate::offline::{self, ensure_online, is_offline};
 use crate::session::Session;
 use crate::style::progress_spinner;
-use crate::tool::Node;
+use crate::tool::{Node, NodeDistro, RemoteVersion, NODE_DISTRO_OS};
 use crate::version::{VersionSpec, VersionTag};
+use attohttpc::header::HeaderMap;
 use attohttpc::Response;
 use cfg_if::cfg_if;
 use fs_utils::ensure_containing_dir_exists;
@@ -29,26 +34,90 @@ cfg_if! {
         fn public_node_version_index() -> String {
             format!("{}/node-dist/index.json", mockito::SERVER_URL)
         }
+        fn public_node_channel_index(channel: NodeChannel) -> String {
+            format!("{}/node-dist/{}/index.json", mockito::SERVER_URL, channel)
+        }
     } else {
         /// Returns the URL of the index of available Node versions on the public Node server.
         fn public_node_version_index() -> String {
-            "https://nodejs.org/dist/index.json".to_string()
+            match NodeDistro::current() {
+                NodeDistro::Official => "https://nodejs.org/dist/index.json".to_string(),
+                NodeDistro::Musl => {
+                    "https://unofficial-builds.nodejs.org/download/release/index.json".to_string()
+                }
+            }
+        }
+        /// Returns the URL of the index of the versions published on a prerelease channel.
+        fn public_node_channel_index(channel: NodeChannel) -> String {
+            format!("https://nodejs.org/download/{}/index.json", channel)
         }
     }
 }
 
 pub fn resolve(matching: VersionSpec, session: &mut Session) -> Fallible<Version> {
-    let hooks = session.hooks()?.node();
+    resolve_with_hooks(matching, session.hooks()?.node())
+}
+
+/// Lists the versions of Node in the index, newest first
+pub fn remote_versions(session: &Session) -> Fallible<Vec<RemoteVersion>> {
+    ensure_online("the available versions of node")?;
+
+    let url = match session.hooks()?.node() {
+        Some(&ToolHooks {
+            index: Some(ref hook),
+            ..
+        }) => {
+            debug!("Using node.index hook to determine node index URL");
+            hook.resolve("index.json")?
+        }
+        _ => public_node_version_index(),
+    };
+
+    Ok(available_entries(&url)?
+        .map(|entry| RemoteVersion {
+            tags: entry
+                .codename
+                .iter()
+                .map(|codename| format!("lts/{}", codename.to_lowercase()))
+                .collect(),
+            lts: entry.lts,
+            version: entry.version,
+        })
+        .collect())
+}
+
+/// Resolves a Node version using the given hooks, for use before a `Session` is available
+pub(crate) fn resolve_with_hooks(
+    matching: VersionSpec,
+    hooks: Option<&ToolHooks<Node>>,
+) -> Fallible<Version> {
+    if is_offline() {
+        let lts_versions = cached_lts_versions()?;
+        return offline::resolve("node", matching, node_versions()?, lts_versions.as_ref());
+    }
+
     match matching {
         VersionSpec::Semver(requirement) => resolve_semver(requirement, hooks),
         VersionSpec::Exact(version) => Ok(version),
         VersionSpec::None | VersionSpec::Tag(VersionTag::Lts) => resolve_lts(hooks),
         VersionSpec::Tag(VersionTag::Latest) => resolve_latest(hooks),
         VersionSpec::Tag(VersionTag::LtsRequirement(req)) => resolve_lts_semver(req, hooks),
-        // Node doesn't have "tagged" versions (apart from 'latest' and 'lts'), so custom tags will always be an error
-        VersionSpec::Tag(VersionTag::Custom(tag)) => {
-            Err(ErrorKind::NodeVersionNotFound { matching: tag }.into())
-        }
+        // Apart from 'latest' and 'lts', Node has the 'rc' and 'nightly' prerelease channels, and
+        // "tagged" versions for LTS codenames, either bare or in the nvm-style format
+        // 'lts/<codename>'. Any other custom tag won't match a codename, so it will be an error
+        VersionSpec::Tag(VersionTag::Custom(tag)) => match tag.parse() {
+            Ok(channel) => resolve_channel(channel),
+            Err(_) => resolve_lts_codename(lts_codename(&tag).unwrap_or(&tag), &tag, hooks),
+        },
+    }
+}
+
+/// Extracts the codename from an 'lts/<codename>' tag
+fn lts_codename(tag: &str) -> Option<&str> {
+    if tag.len() > 4 && tag[..4].eq_ignore_ascii_case("lts/") {
+        Some(&tag[4..])
+    } else {
+        None
     }
 }
 
@@ -105,6 +174,70 @@ fn resolve_lts(hooks: Option<&ToolHooks<Node>>) -> Fallible<Version> {
     }
 }
 
+fn resolve_lts_codename(
+    codename: &str,
+    tag: &str,
+    hooks: Option<&ToolHooks<Node>>,
+) -> Fallible<Version> {
+    let url = match hooks {
+        Some(&ToolHooks {
+            index: Some(ref hook),
+            ..
+        }) => {
+            debug!("Using node.index hook to determine node index URL");
+            hook.resolve("index.json")?
+        }
+        _ => public_node_version_index(),
+    };
+    let version_opt = match_node_version(&url, |entry| {
+        entry
+            .codename
+            .as_ref()
+            .map_or(false, |name| name.eq_ignore_ascii_case(codename))
+    })?;
+
+    match version_opt {
+        Some(version) => {
+            debug!(
+                "Found newest node version ({}) for LTS codename '{}' from {}",
+                version, codename, url
+            );
+            Ok(version)
+        }
+        None => Err(ErrorKind::NodeVersionNotFound {
+            matching: tag.into(),
+        }
+        .into()),
+    }
+}
+
+/// Resolves the newest version published on a prerelease channel
+///
+/// Each channel has its own index, so the `node.index` hook doesn't apply to them. The channel
+/// indexes aren't cached, since the cache only holds the main index, which is also used to
+/// resolve versions offline.
+fn resolve_channel(channel: NodeChannel) -> Fallible<Version> {
+    let url = public_node_channel_index(channel);
+    let (_, _, index) = fetch_node_index(&url)?;
+    let version_opt = filter_available(index.into())
+        .next()
+        .map(|NodeEntry { version, .. }| version);
+
+    match version_opt {
+        Some(version) => {
+            debug!(
+                "Found newest node version ({}) on the {} channel from {}",
+                version, channel, url
+            );
+            Ok(version)
+        }
+        None => Err(ErrorKind::NodeVersionNotFound {
+            matching: channel.to_string(),
+        }
+        .into()),
+    }
+}
+
 fn resolve_semver(matching: VersionReq, hooks: Option<&ToolHooks<Node>>) -> Fallible<Version> {
     // ISSUE #34: also make sure this OS is available for this version
     let url = match hooks {
@@ -188,13 +321,28 @@ fn match_node_version(
     url: &str,
     predicate: impl Fn(&NodeEntry) -> bool,
 ) -> Fallible<Option<Version>> {
-    let index: NodeIndex = resolve_node_versions(url)?.into();
-    let mut entries = index.entries.into_iter();
-    Ok(entries
+    Ok(available_entries(url)?
         .find(predicate)
         .map(|NodeEntry { version, .. }| version))
 }
 
+/// The entries in the index at the given URL that can be fetched for this system, newest first
+fn available_entries(url: &str) -> Fallible<impl Iterator<Item = NodeEntry>> {
+    Ok(filter_available(resolve_node_versions(url)?.into()))
+}
+
+/// The entries in an index that can be fetched for this system, newest first
+fn filter_available(index: NodeIndex) -> impl Iterator<Item = NodeEntry> {
+    // The unofficial builds don't include every flavor for every version, so only the versions
+    // with a build for this system can be used
+    let distro = NodeDistro::current();
+    let distro_file = format!("{}-{}", NODE_DISTRO_OS, distro.arch());
+
+    index.entries.into_iter().filter(move |entry| {
+        distro == NodeDistro::Official || entry.files.files.contains(&distro_file)
+    })
+}
+
 /// The index of the public Node server.
 pub struct NodeIndex {
     pub(super) entries: Vec<NodeEntry>,
@@ -206,6 +354,7 @@ pub struct NodeEntry {
     pub npm: Version,
     pub files: NodeDistroFiles,
     pub lts: bool,
+    pub codename: Option<String>,
 }
 
 /// The set of available files on the public Node server for a given Node version.
@@ -214,20 +363,43 @@ pub struct NodeDistroFiles {
     pub files: HashSet<String>,
 }
 
-/// Reads a public index from the Node cache, if it exists and hasn't expired.
-fn read_cached_opt(url: &str) -> Fallible<Option<serial::RawNodeIndex>> {
-    let expiry_file = volta_home()?.node_index_expiry_file();
+/// The cached Node index for the current flavor of distro, and the file recording its expiry
+///
+/// Each flavor is fetched from a different server, so they're cached separately to avoid
+/// overwriting each other.
+fn index_cache_files() -> Fallible<(PathBuf, PathBuf)> {
+    let home = volta_home()?;
+    match NodeDistro::current() {
+        NodeDistro::Official => Ok((
+            home.node_index_file().to_owned(),
+            home.node_index_expiry_file().to_owned(),
+        )),
+        NodeDistro::Musl => Ok((
+            home.node_musl_index_file().to_owned(),
+            home.node_musl_index_expiry_file().to_owned(),
+        )),
+    }
+}
+
+/// Reads when the cached Node index expires, if there is one
+pub(crate) fn index_expiry() -> Fallible<Option<HttpDate>> {
+    let (_, expiry_file) = index_cache_files()?;
     let expiry = read_file(&expiry_file).with_context(|| ErrorKind::ReadNodeIndexExpiryError {
         file: expiry_file.to_owned(),
     })?;
 
-    if let Some(date) = expiry {
-        let expiry_date =
-            HttpDate::from_str(&date).with_context(|| ErrorKind::ParseNodeIndexExpiryError)?;
+    expiry
+        .map(|date| HttpDate::from_str(&date).with_context(|| ErrorKind::ParseNodeIndexExpiryError))
+        .transpose()
+}
+
+/// Reads a public index from the Node cache, if it exists and hasn't expired.
+fn read_cached_opt(url: &str) -> Fallible<Option<serial::RawNodeIndex>> {
+    if let Some(expiry_date) = index_expiry()? {
         let current_date = HttpDate::from(SystemTime::now());
 
         if current_date < expiry_date {
-            let index_file = volta_home()?.node_index_file();
+            let (index_file, _) = index_cache_files()?;
             let cached =
                 read_file(&index_file).with_context(|| ErrorKind::ReadNodeIndexCacheError {
                     file: index_file.to_owned(),
@@ -245,6 +417,39 @@ fn read_cached_opt(url: &str) -> Fallible<Option<serial::RawNodeIndex>> {
     Ok(None)
 }
 
+/// Reads the LTS versions from the cached Node index, even if it has expired, for resolving LTS
+/// versions offline
+fn cached_lts_versions() -> Fallible<Option<BTreeSet<Version>>> {
+    let (index_file, _) = index_cache_files()?;
+    let cached = read_file(&index_file).with_context(|| ErrorKind::ReadNodeIndexCacheError {
+        file: index_file.to_owned(),
+    })?;
+
+    // The cached index is prefixed with the URL it was fetched from
+    let index = cached.and_then(|content| {
+        let json = content.splitn(2, '\n').nth(1)?;
+        serde_json::de::from_str::<serial::RawNodeIndex>(json).ok()
+    });
+
+    match index {
+        Some(raw) => {
+            let index: NodeIndex = raw.into();
+            Ok(Some(
+                index
+                    .entries
+                    .into_iter()
+                    .filter(|entry| entry.lts)
+                    .map(|entry| entry.version)
+                    .collect(),
+            ))
+        }
+        None => {
+            debug!("No cached Node index, assuming LTS versions have even major versions");
+            Ok(None)
+        }
+    }
+}
+
 /// Get the cache max-age of an HTTP reponse.
 fn max_age(headers: &attohttpc::header::HeaderMap) -> u32 {
     if let Ok(cache_control_header) = headers.decode::<CacheControl>() {
@@ -267,22 +472,7 @@ fn resolve_node_versions(url: &str) -> Fallible<serial::RawNodeIndex> {
         }
         None => {
             debug!("Node index cache was not found or was invalid");
-            let spinner = progress_spinner(&format!("Fetching public registry: {}", url));
-
-            let (_, headers, response) = attohttpc::get(url)
-                .send()
-                .and_then(Response::error_for_status)
-                .with_context(registry_fetch_error("Node", url))?
-                .split();
-
-            let response_text = response
-                .text()
-                .with_context(registry_fetch_error("Node", url))?;
-
-            let index: serial::RawNodeIndex = serde_json::de::from_str(&response_text)
-                .with_context(|| ErrorKind::ParseNodeIndexError {
-                    from_url: url.to_string(),
-                })?;
+            let (headers, response_text, index) = fetch_node_index(url)?;
 
             let cached = create_staging_file()?;
 
@@ -293,7 +483,7 @@ fn resolve_node_versions(url: &str) -> Fallible<serial::RawNodeIndex> {
                     file: cached.path().to_path_buf(),
                 })?;
 
-            let index_cache_file = volta_home()?.node_index_file();
+            let (index_cache_file, index_expiry_file) = index_cache_files()?;
             ensure_containing_dir_exists(&index_cache_file).with_context(|| {
                 ErrorKind::ContainingDirError {
                     path: index_cache_file.to_owned(),
@@ -320,7 +510,6 @@ fn resolve_node_versions(url: &str) -> Fallible<serial::RawNodeIndex> {
                 file: expiry.path().to_path_buf(),
             })?;
 
-            let index_expiry_file = volta_home()?.node_index_expiry_file();
             ensure_containing_dir_exists(&index_expiry_file).with_context(|| {
                 ErrorKind::ContainingDirError {
                     path: index_expiry_file.to_owned(),
@@ -332,8 +521,31 @@ fn resolve_node_versions(url: &str) -> Fallible<serial::RawNodeIndex> {
                 }
             })?;
 
-            spinner.finish_and_clear();
             Ok(index)
         }
     }
 }
+
+/// Fetches and parses the Node index at the given URL, without using the cache
+///
+/// The headers and the text of the response are also returned, so that the index can be cached.
+fn fetch_node_index(url: &str) -> Fallible<(HeaderMap, String, serial::RawNodeIndex)> {
+    let spinner = progress_spinner(&format!("Fetching public registry: {}", url));
+    let (_, headers, response) = http::get(url)?
+        .send()
+        .and_then(Response::error_for_status)
+        .with_context(registry_fetch_error("Node", url))?
+        .split();
+
+    let text = response
+        .text()
+        .with_context(registry_fetch_error("Node", url))?;
+
+    let index =
+        serde_json::de::from_str(&text).with_context(|| ErrorKind::ParseNodeIndexError {
+            from_url: url.to_string(),
+        })?;
+
+    spinner.finish_and_clear();
+    Ok((headers, text, index))
+}
@@ -18,7 +18,7 @@ pub struct RawNodeEntry {
     pub npm: Option<Version>,
     pub files: Vec<String>,
     #[serde(deserialize_with = "lts_version_serde")]
-    pub lts: bool,
+    pub lts: Option<String>,
 }
 
 impl From<RawNodeIndex> for NodeIndex {
@@ -33,7 +33,8 @@ impl From<RawNodeIndex> for NodeIndex {
                     version: entry.version,
                     npm,
                     files: data,
-                    lts: entry.lts,
+                    lts: entry.lts.is_some(),
+                    codename: entry.lts,
                 });
             }
         }
@@ -41,12 +42,12 @@ impl From<RawNodeIndex> for NodeIndex {
     }
 }
 
-fn lts_version_serde<'de, D>(deserializer: D) -> Result<bool, D::Error>
+fn lts_version_serde<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
 where
     D: Deserializer<'de>,
 {
     match String::deserialize(deserializer) {
-        Ok(_) => Ok(true),
-        Err(_) => Ok(false),
+        Ok(codename) => Ok(Some(codename)),
+        Err(_) => Ok(None),
     }
 }
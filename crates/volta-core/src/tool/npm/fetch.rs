@@ -1,39 +1,33 @@
 //! Provides fetcher for npm distributions
 
-use std::fs::{rename, write, File};
-use std::path::{Path, PathBuf};
+use std::fs::{rename, write};
+use std::path::Path;
 
-use super::super::download_tool_error;
+use super::super::{download_tool_error, registry};
 use crate::error::{Context, ErrorKind, Fallible};
 use crate::fs::{create_staging_dir, create_staging_file, set_executable};
 use crate::hook::ToolHooks;
 use crate::layout::volta_home;
+use crate::offline::ensure_online;
+use crate::project::Project;
 use crate::style::{progress_bar, tool_version};
 use crate::tool::{self, Npm};
 use crate::version::VersionSpec;
 use archive::{Archive, Tarball};
-use cfg_if::cfg_if;
 use fs_utils::ensure_containing_dir_exists;
 use log::debug;
 use semver::Version;
 
-cfg_if! {
-    if #[cfg(feature = "mock-network")] {
-        fn public_npm_server_root() -> String {
-            mockito::SERVER_URL.to_string()
-        }
-    } else {
-        fn public_npm_server_root() -> String {
-            "https://registry.npmjs.org".to_string()
-        }
-    }
-}
-
-pub fn fetch(version: &Version, hooks: Option<&ToolHooks<Npm>>) -> Fallible<()> {
+pub fn fetch(
+    version: &Version,
+    hooks: Option<&ToolHooks<Npm>>,
+    project: Option<&Project>,
+) -> Fallible<()> {
     let npm_dir = volta_home()?.npm_inventory_dir();
-    let cache_file = npm_dir.join(Npm::archive_filename(&version.to_string()));
+    let archive_filename = Npm::archive_filename(&version.to_string());
+    let cache_file = npm_dir.join(&archive_filename);
 
-    let (archive, staging) = match load_cached_distro(&cache_file) {
+    let (archive, staging) = match registry::load_cached_tarball(&cache_file) {
         Some(archive) => {
             debug!(
                 "Loading {} from cached archive at '{}'",
@@ -43,9 +37,16 @@ pub fn fetch(version: &Version, hooks: Option<&ToolHooks<Npm>>) -> Fallible<()>
             (archive, None)
         }
         None => {
+            ensure_online(tool_version("npm", &version))?;
             let staging = create_staging_file()?;
-            let remote_url = determine_remote_url(&version, hooks)?;
-            let archive = fetch_remote_distro(&version, &remote_url, staging.path())?;
+            let (remote_url, authorization) =
+                registry::remote_url("npm", version, &archive_filename, hooks, project)?;
+            let archive = fetch_remote_distro(
+                &version,
+                &remote_url,
+                authorization.as_deref(),
+                staging.path(),
+            )?;
             (archive, Some(staging))
         }
     };
@@ -117,46 +118,22 @@ fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<()>
     Ok(())
 }
 
-/// Return the archive if it is valid. It may have been corrupted or interrupted in the middle of
-/// downloading.
-/// ISSUE(#134) - verify checksum
-fn load_cached_distro(file: &PathBuf) -> Option<Box<dyn Archive>> {
-    if file.is_file() {
-        let file = File::open(file).ok()?;
-        Tarball::load(file).ok()
-    } else {
-        None
-    }
-}
-
-/// Determine the remote URL to download from, using the hooks if avaialble
-fn determine_remote_url(version: &Version, hooks: Option<&ToolHooks<Npm>>) -> Fallible<String> {
-    let version_str = version.to_string();
-    let distro_file_name = Npm::archive_filename(&version_str);
-    match hooks {
-        Some(&ToolHooks {
-            distro: Some(ref hook),
-            ..
-        }) => {
-            debug!("Using npm.distro hook to determine download URL");
-            hook.resolve(&version, &distro_file_name)
-        }
-        _ => Ok(format!(
-            "{}/npm/-/{}",
-            public_npm_server_root(),
-            distro_file_name
-        )),
-    }
-}
-
 /// Fetch the distro archive from the internet
 fn fetch_remote_distro(
     version: &Version,
     url: &str,
+    authorization: Option<&str>,
     staging_path: &Path,
 ) -> Fallible<Box<dyn Archive>> {
     debug!("Downloading {} from {}", tool_version("npm", version), url);
-    Tarball::fetch(url, staging_path).with_context(download_tool_error(
+
+    // Tarballs from a private registry need the same token as its metadata
+    let archive = match authorization {
+        Some(authorization) => Tarball::fetch_authorized(url, authorization, staging_path),
+        None => Tarball::fetch(url, staging_path),
+    };
+
+    archive.with_context(download_tool_error(
         tool::Spec::Npm(VersionSpec::Exact(version.clone())),
         url,
     ))
@@ -2,19 +2,22 @@ use std::fmt::{self, Display};
 
 use super::node::load_default_npm_version;
 use super::{
-    debug_already_fetched, info_fetched, info_installed, info_pinned, info_project_version, Tool,
+    debug_already_fetched, info_fetched, info_installed, info_pinned, info_project_version,
+    info_uninstalled, lock_fetch, remove_from_inventory, warn_not_uninstalled, Tool,
 };
 use crate::error::{Context, ErrorKind, Fallible};
 use crate::inventory::npm_available;
+use crate::layout::volta_home;
 use crate::session::Session;
-use crate::style::{success_prefix, tool_version};
+use crate::style::{note_prefix, success_prefix, tool_version};
 use log::info;
 use semver::Version;
 
 mod fetch;
 mod resolve;
 
-pub use resolve::resolve;
+pub(crate) use resolve::resolve_with_hooks;
+pub use resolve::{remote_versions, resolve};
 
 /// The Tool implementation for fetching and installing npm
 pub struct Npm {
@@ -35,11 +38,13 @@ impl Npm {
     }
 
     pub(crate) fn ensure_fetched(&self, session: &mut Session) -> Fallible<()> {
+        let _lock = lock_fetch("npm", &self.version, npm_available)?;
+
         if npm_available(&self.version)? {
             debug_already_fetched(self);
             Ok(())
         } else {
-            fetch::fetch(&self.version, session.hooks()?.npm())
+            fetch::fetch(&self.version, session.hooks()?.npm(), session.project()?)
         }
     }
 }
@@ -83,6 +88,38 @@ impl Tool for Npm {
     }
 }
 
+/// Uninstall a custom version of npm, removing it from the local inventory
+///
+/// If the version is the default npm, the default is reset to the version bundled with Node.
+pub fn uninstall(version: Version, session: &mut Session) -> Fallible<()> {
+    let toolchain = session.toolchain_mut()?;
+    if toolchain.platform().and_then(|p| p.npm.as_ref()) == Some(&version) {
+        toolchain.set_active_npm(None)?;
+        info!(
+            "{} {} was your default npm, the version bundled with Node will be used instead",
+            note_prefix(),
+            tool_version("npm", &version)
+        );
+    }
+
+    let home = volta_home()?;
+    let version_str = version.to_string();
+    let found = remove_from_inventory(
+        &home.npm_image_dir(&version_str),
+        &[home
+            .npm_inventory_dir()
+            .join(Npm::archive_filename(&version_str))],
+    )?;
+
+    let tool = tool_version("npm", &version);
+    if found {
+        info_uninstalled(tool);
+    } else {
+        warn_not_uninstalled(tool);
+    }
+    Ok(())
+}
+
 impl Display for Npm {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str(&tool_version("npm", &self.version))
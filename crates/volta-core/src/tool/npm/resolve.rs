@@ -1,109 +1,67 @@
 //! Provides resolution of npm Version requirements into specific versions
 
-use super::super::registry_fetch_error;
-use crate::error::{Context, ErrorKind, Fallible};
+use super::super::registry;
+use crate::error::{ErrorKind, Fallible};
 use crate::hook::ToolHooks;
+use crate::inventory::npm_versions;
+use crate::offline::{self, ensure_online, is_offline};
+use crate::project::Project;
 use crate::session::Session;
-use crate::style::progress_spinner;
-use crate::tool::package;
-use crate::tool::Npm;
+use crate::tool::{Npm, RemoteVersion};
 use crate::version::{VersionSpec, VersionTag};
-use attohttpc::header::ACCEPT;
-use attohttpc::Response;
-use cfg_if::cfg_if;
-use log::debug;
 use semver::{Version, VersionReq};
 
-// Accept header needed to request the abbreviated metadata from the npm registry
-// See https://github.com/npm/registry/blob/master/docs/responses/package-metadata.md
-static NPM_ABBREVIATED_ACCEPT_HEADER: &str =
-    "application/vnd.npm.install-v1+json; q=1.0, application/json; q=0.8, */*";
+pub fn resolve(matching: VersionSpec, session: &mut Session) -> Fallible<Option<Version>> {
+    resolve_with_hooks(matching, session.hooks()?.npm(), session.project()?)
+}
 
-cfg_if! {
-    if #[cfg(feature = "mock-network")] {
-        fn public_npm_version_index() -> String {
-            format!("{}/npm", mockito::SERVER_URL)
-        }
-    } else {
-        fn public_npm_version_index() -> String {
-            "https://registry.npmjs.org/npm".to_string()
-        }
-    }
+/// Lists the versions of npm in the registry, newest first
+pub fn remote_versions(session: &Session) -> Fallible<Vec<RemoteVersion>> {
+    ensure_online("the available versions of npm")?;
+    let (_, index) = registry::fetch_index("npm", session.hooks()?.npm(), session.project()?)?;
+    Ok(index.into_remote_versions())
 }
 
-pub fn resolve(matching: VersionSpec, session: &mut Session) -> Fallible<Option<Version>> {
-    let hooks = session.hooks()?.npm();
+/// Resolves an npm version using the given hooks, for use before a `Session` is available
+pub(crate) fn resolve_with_hooks(
+    matching: VersionSpec,
+    hooks: Option<&ToolHooks<Npm>>,
+    project: Option<&Project>,
+) -> Fallible<Option<Version>> {
+    if is_offline() {
+        return match matching {
+            VersionSpec::Tag(VersionTag::Custom(tag)) if tag == "bundled" => Ok(None),
+            matching => offline::resolve("npm", matching, npm_versions()?, None).map(Some),
+        };
+    }
+
     match matching {
-        VersionSpec::Semver(requirement) => resolve_semver(requirement, hooks).map(Some),
+        VersionSpec::Semver(requirement) => resolve_semver(requirement, hooks, project).map(Some),
         VersionSpec::Exact(version) => Ok(Some(version)),
         VersionSpec::None | VersionSpec::Tag(VersionTag::Latest) => {
-            resolve_tag("latest", hooks).map(Some)
+            resolve_tag("latest", hooks, project).map(Some)
         }
         VersionSpec::Tag(VersionTag::Custom(tag)) if tag == "bundled" => Ok(None),
-        VersionSpec::Tag(tag) => resolve_tag(&tag.to_string(), hooks).map(Some),
+        VersionSpec::Tag(tag) => resolve_tag(&tag.to_string(), hooks, project).map(Some),
     }
 }
 
-fn fetch_npm_index(
+fn resolve_tag(
+    tag: &str,
     hooks: Option<&ToolHooks<Npm>>,
-) -> Fallible<(String, package::resolve::PackageIndex)> {
-    let url = match hooks {
-        Some(&ToolHooks {
-            index: Some(ref hook),
-            ..
-        }) => {
-            debug!("Using npm.index hook to determine npm index URL");
-            hook.resolve("npm")?
-        }
-        _ => public_npm_version_index(),
-    };
-
-    let spinner = progress_spinner(&format!("Fetching public registry: {}", url));
-    let metadata: package::serial::RawPackageMetadata = attohttpc::get(&url)
-        .header(ACCEPT, NPM_ABBREVIATED_ACCEPT_HEADER)
-        .send()
-        .and_then(Response::error_for_status)
-        .and_then(Response::json)
-        .with_context(registry_fetch_error("npm", &url))?;
-
-    spinner.finish_and_clear();
-    Ok((url, metadata.into()))
+    project: Option<&Project>,
+) -> Fallible<Version> {
+    registry::resolve_tag("npm", tag, hooks, project, |matching| {
+        ErrorKind::NpmVersionNotFound { matching }
+    })
 }
 
-fn resolve_tag(tag: &str, hooks: Option<&ToolHooks<Npm>>) -> Fallible<Version> {
-    let (url, mut index) = fetch_npm_index(hooks)?;
-
-    match index.tags.remove(tag) {
-        Some(version) => {
-            debug!("Found npm@{} matching tag '{}' from {}", version, tag, url);
-            Ok(version)
-        }
-        None => Err(ErrorKind::NpmVersionNotFound {
-            matching: tag.into(),
-        }
-        .into()),
-    }
-}
-
-fn resolve_semver(matching: VersionReq, hooks: Option<&ToolHooks<Npm>>) -> Fallible<Version> {
-    let (url, index) = fetch_npm_index(hooks)?;
-
-    let details_opt = index
-        .entries
-        .into_iter()
-        .find(|package::PackageDetails { version, .. }| matching.matches(&version));
-
-    match details_opt {
-        Some(details) => {
-            debug!(
-                "Found npm@{} matching requirement '{}' from {}",
-                details.version, matching, url
-            );
-            Ok(details.version)
-        }
-        None => Err(ErrorKind::NpmVersionNotFound {
-            matching: matching.to_string(),
-        }
-        .into()),
-    }
+fn resolve_semver(
+    matching: VersionReq,
+    hooks: Option<&ToolHooks<Npm>>,
+    project: Option<&Project>,
+) -> Fallible<Version> {
+    registry::resolve_semver("npm", matching, hooks, project, |matching| {
+        ErrorKind::NpmVersionNotFound { matching }
+    })
 }
@@ -1,58 +1,73 @@
 //! Provides fetcher for 3rd-party packages
 
 use std::fs::{rename, write, File};
-use std::io::{Read, Seek, SeekFrom};
+use std::io::{self, Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 
+use super::registry::NpmConfig;
 use crate::error::{Context, ErrorKind, Fallible};
-use crate::fs::{create_staging_dir, ensure_dir_does_not_exist, read_dir_eager, read_file};
+use crate::fs::{
+    create_staging_dir, create_staging_file, ensure_dir_does_not_exist, read_dir_eager, read_file,
+};
 use crate::layout::volta_home;
-use crate::platform::CliPlatform;
-use crate::run::{self, ToolCommand};
-use crate::session::Session;
-use crate::style::{progress_bar, progress_spinner, tool_version};
-use crate::tool::PackageDetails;
+use crate::offline::ensure_online;
+use crate::style::{progress_bar, tool_version};
+use crate::tool::{self, download_tool_error, PackageDetails};
+use crate::version::VersionSpec;
 use archive::{Archive, Tarball};
 use fs_utils::ensure_containing_dir_exists;
 use log::debug;
 use semver::Version;
 use sha1::{Digest, Sha1};
+use sha2::Sha512;
 
-pub fn fetch(name: &str, details: &PackageDetails, session: &mut Session) -> Fallible<()> {
+pub fn fetch(name: &str, details: &PackageDetails, config: &NpmConfig) -> Fallible<()> {
     let version_string = details.version.to_string();
     let home = volta_home()?;
     let cache_file = home.package_distro_file(&name, &version_string);
     let shasum_file = home.package_distro_shasum(&name, &version_string);
 
-    let (archive, cached) = match load_cached_distro(&cache_file, &shasum_file) {
+    let (archive, staging) = match load_cached_distro(&cache_file, &shasum_file) {
         Some(archive) => {
             debug!(
                 "Loading {} from cached archive at '{}'",
                 tool_version(&name, &version_string),
                 cache_file.display(),
             );
-            (archive, true)
+            (archive, None)
         }
         None => {
-            let archive = fetch_remote_distro(&cache_file, &name, &details, session)?;
-            (archive, false)
+            ensure_online(tool_version(&name, &version_string))?;
+            let staging = create_staging_file()?;
+            let archive = fetch_remote_distro(staging.path(), &name, &details, config)?;
+            (archive, Some(staging))
         }
     };
 
-    unpack_archive(archive, name, &details.version)?;
+    let verify = staging.as_ref().map(|file| (file.path(), details));
+    let shasum = unpack_archive(archive, name, &details.version, verify)?;
 
-    if cached {
-        Ok(())
-    } else {
-        // Save the shasum in a file
-        write(&shasum_file, details.shasum.as_bytes()).with_context(|| {
+    if let (Some(staging_file), Some(shasum)) = (staging, shasum) {
+        ensure_containing_dir_exists(&cache_file).with_context(|| {
+            ErrorKind::ContainingDirError {
+                path: cache_file.clone(),
+            }
+        })?;
+        staging_file
+            .persist(&cache_file)
+            .with_context(|| ErrorKind::PersistInventoryError { tool: name.into() })?;
+
+        // Save the shasum in a file, so that the cached archive can be checked before it is used
+        write(&shasum_file, shasum.as_bytes()).with_context(|| {
             ErrorKind::WritePackageShasumError {
                 package: name.into(),
                 version: version_string,
                 file: shasum_file,
             }
-        })
+        })?;
     }
+
+    Ok(())
 }
 
 fn load_cached_distro(file: &Path, shasum_file: &Path) -> Option<Box<dyn Archive>> {
@@ -80,101 +95,102 @@ fn fetch_remote_distro(
     path: &Path,
     name: &str,
     details: &PackageDetails,
-    session: &mut Session,
+    config: &NpmConfig,
 ) -> Fallible<Box<dyn Archive>> {
-    ensure_containing_dir_exists(&path).with_context(|| ErrorKind::ContainingDirError {
-        path: path.to_path_buf(),
-    })?;
-
-    // path.parent() will always be Some, because the previous call to ensure_containing_dir_exists would
-    // error otherwise
-    let dir = path.parent().unwrap();
-
-    let command = npm_pack_command_for(name, &details.version.to_string()[..], session, dir)?;
-    debug!("Running command: `{:?}`", command);
-
+    let url = &details.tarball_url;
     debug!(
-        "Downloading {} via npm pack to {}",
-        tool_version(name, details.version.to_string()),
-        dir.to_str().unwrap()
+        "Downloading {} from {}",
+        tool_version(name, &details.version),
+        url
     );
-    let spinner = progress_spinner(&format!(
-        "Downloading {}",
-        tool_version(name, details.version.to_string()),
-    ));
-    let output = command.output()?;
-    spinner.finish_and_clear();
-
-    if !output.status.success() {
-        debug!(
-            "Command failed, stderr is:\n{}",
-            String::from_utf8_lossy(&output.stderr).to_string()
-        );
-        debug!("Exit code is {:?}", output.status.code());
-        return Err(ErrorKind::NpmPackFetchError {
-            package: tool_version(name, details.version.to_string()),
-        }
-        .into());
-    }
 
-    let filename = String::from_utf8_lossy(&output.stdout);
-    // The output from `npm pack` contains a newline, so we'll trim it here.
-    let trimmed_filename = filename.trim();
+    // Tarballs from a private registry need the same token as its metadata
+    let archive = match config.authorization_for(url) {
+        Some(authorization) => Tarball::fetch_authorized(url, &authorization, path),
+        None => Tarball::fetch(url, path),
+    };
 
-    if trimmed_filename.is_empty() {
-        return Err(ErrorKind::NpmPackUnpackError {
-            package: tool_version(name, details.version.to_string()),
-        }
-        .into());
-    }
+    archive.with_context(download_tool_error(
+        tool::Spec::Package(name.into(), VersionSpec::Exact(details.version.clone())),
+        url,
+    ))
+}
 
-    let tarball_from_npm_pack = dir.join(trimmed_filename.to_string());
+/// Verify a downloaded tarball against the registry metadata, returning its shasum
+///
+/// The registry publishes a Subresource Integrity string for most packages, which is preferred
+/// since it uses SHA-512. Older packages only have a SHA-1 `shasum`, so that is used otherwise.
+fn verify_download(file: &Path, name: &str, details: &PackageDetails) -> Fallible<String> {
+    let read_error = || ErrorKind::UnpackArchiveError {
+        tool: name.into(),
+        version: details.version.to_string(),
+    };
 
-    if !tarball_from_npm_pack.exists() {
-        return Err(ErrorKind::NpmPackUnpackError {
-            package: tool_version(name, details.version.to_string()),
+    let shasum = hex::encode(hash_file::<Sha1>(file).with_context(read_error)?.result());
+    let (expected, actual) = match details.integrity.as_deref().and_then(sha512_integrity) {
+        Some(expected) => {
+            let digest = hash_file::<Sha512>(file).with_context(read_error)?.result();
+            (
+                format!("sha512-{}", expected),
+                format!("sha512-{}", base64::encode(&digest)),
+            )
         }
-        .into());
-    }
+        None if !details.shasum.is_empty() => (details.shasum.clone(), shasum.clone()),
+        None => {
+            debug!(
+                "No integrity or shasum published for {}, skipping verification",
+                tool_version(name, &details.version)
+            );
+            return Ok(shasum);
+        }
+    };
 
-    // If `npm pack` didn't name the tarball what we expect (usually because of scoped packages),
-    // move it to where we expect it to be.
-    if tarball_from_npm_pack != path {
+    if expected == actual {
         debug!(
-            "Moving the tarball from {:?} to the expected path {:?}",
-            tarball_from_npm_pack, path
+            "Verified integrity of {}",
+            tool_version(name, &details.version)
         );
-        rename(tarball_from_npm_pack, path).with_context(|| ErrorKind::NpmPackUnpackError {
-            package: tool_version(name, details.version.to_string()),
-        })?;
+        Ok(shasum)
+    } else {
+        Err(ErrorKind::IntegrityMismatch {
+            package: tool_version(name, &details.version),
+            expected,
+            actual,
+        }
+        .into())
     }
+}
 
-    debug!("Attempting to load {:?}", path);
-    let distro = File::open(path).with_context(|| ErrorKind::NpmPackUnpackError {
-        package: tool_version(name, details.version.to_string()),
-    })?;
+/// Feed the contents of a file through a hasher, without reading it all into memory
+fn hash_file<D: Digest + Write>(file: &Path) -> io::Result<D> {
+    let mut hasher = D::new();
+    io::copy(&mut File::open(file)?, &mut hasher)?;
+    Ok(hasher)
+}
 
-    Tarball::load(distro).with_context(|| ErrorKind::NpmPackUnpackError {
-        package: tool_version(name, details.version.to_string()),
+/// Find the SHA-512 digest in an `integrity` string, which may list several hashes
+fn sha512_integrity(integrity: &str) -> Option<&str> {
+    integrity.split_whitespace().find_map(|hash| {
+        let mut parts = hash.splitn(2, '-');
+        match (parts.next(), parts.next()) {
+            // Any options after the digest are separated by `?`
+            (Some("sha512"), Some(digest)) => digest.split('?').next(),
+            _ => None,
+        }
     })
 }
 
-// build a command to run `npm pack`
-fn npm_pack_command_for(
+/// Unpack the package archive into the image directory so that it is ready for use
+///
+/// If `verify` is provided, the downloaded tarball is checked against the registry metadata once
+/// it has been completely read, before anything is moved into the image directory. The shasum of
+/// the verified tarball is returned.
+fn unpack_archive(
+    archive: Box<dyn Archive>,
     name: &str,
-    version: &str,
-    session: &mut Session,
-    current_dir: &Path,
-) -> Fallible<ToolCommand> {
-    let mut command = run::npm::command(CliPlatform::default(), session)?;
-    command.arg("pack");
-    command.arg("--no-update-notifier");
-    command.arg(format!("{}@{}", name, version));
-    command.current_dir(current_dir);
-    Ok(command)
-}
-
-fn unpack_archive(archive: Box<dyn Archive>, name: &str, version: &Version) -> Fallible<()> {
+    version: &Version,
+    verify: Option<(&Path, &PackageDetails)>,
+) -> Fallible<Option<String>> {
     let temp = create_staging_dir()?;
     debug!("Unpacking {} into '{}'", name, temp.path().display());
 
@@ -195,6 +211,11 @@ fn unpack_archive(archive: Box<dyn Archive>, name: &str, version: &Version) -> F
             version: version.to_string(),
         })?;
 
+    let shasum = match verify {
+        Some((tarball, details)) => Some(verify_download(tarball, name, details)?),
+        None => None,
+    };
+
     let image_dir = volta_home()?.package_image_dir(&name, &version.to_string());
     // ensure that the dir where this will be unpacked exists
     ensure_containing_dir_exists(&image_dir).with_context(|| ErrorKind::ContainingDirError {
@@ -215,7 +236,7 @@ fn unpack_archive(archive: Box<dyn Archive>, name: &str, version: &Version) -> F
     // Note: We write this after the progress bar is finished to avoid display bugs with re-renders of the progress
     debug!("Installing {} in '{}'", name, image_dir.display());
 
-    Ok(())
+    Ok(shasum)
 }
 
 /// Figure out the unpacked package directory name dynamically
@@ -235,3 +256,16 @@ fn find_unpack_dir(in_dir: &Path) -> Fallible<PathBuf> {
     // there is more than just a single directory here, something is wrong
     Err(ErrorKind::PackageUnpackError.into())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::sha512_integrity;
+
+    #[test]
+    fn test_sha512_integrity() {
+        assert_eq!(sha512_integrity("sha512-abc+/="), Some("abc+/="));
+        assert_eq!(sha512_integrity("sha1-xyz sha512-abc?opt"), Some("abc"));
+        assert_eq!(sha512_integrity("sha1-xyz"), None);
+        assert_eq!(sha512_integrity(""), None);
+    }
+}
@@ -122,16 +122,21 @@ pub struct BinLoader {
 pub fn install(
     name: &str,
     version: &Version,
+    node: Option<&Version>,
     session: &mut Session,
 ) -> Fallible<HashMap<String, String>> {
     let package_dir = volta_home()?.package_image_dir(name, &version.to_string());
     let bin_map = read_bins(name, version)?;
     let display = tool_version(name, version);
 
-    let engine = determine_engine(&package_dir, &display)?;
+    let node = match node {
+        Some(node) => node.clone(),
+        None => node::resolve(determine_engine(&package_dir, &display)?, session)?,
+    };
     let platform = PlatformSpec {
-        node: node::resolve(engine, session)?,
+        node,
         npm: None,
+        pnpm: None,
         yarn: None,
     };
     let image = platform.as_binary().checkout(session)?;
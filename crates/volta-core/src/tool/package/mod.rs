@@ -2,9 +2,9 @@ use std::fmt::{self, Display};
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use super::{debug_already_fetched, info_fetched, Tool};
+use super::{debug_already_fetched, info_fetched, lock_fetch, Tool};
 use crate::error::{Context, ErrorKind, Fallible};
-use crate::fs::{delete_dir_error, delete_file_error, dir_entry_match};
+use crate::fs::{delete_dir_error, delete_file_error, dir_entry_match, lock_volta_home};
 use crate::inventory::package_available;
 use crate::layout::volta_home;
 use crate::session::Session;
@@ -12,15 +12,17 @@ use crate::shim;
 use crate::style::{success_prefix, tool_version};
 use dunce::canonicalize;
 use log::{info, warn};
+use registry::NpmConfig;
 use semver::Version;
 
 mod fetch;
 mod install;
+pub(crate) mod registry;
 pub(crate) mod resolve;
 pub(crate) mod serial;
 
 pub use install::{BinConfig, BinLoader, PackageConfig};
-pub use resolve::resolve;
+pub use resolve::{remote_versions, resolve};
 
 pub fn bin_full_path<P>(
     package: &str,
@@ -42,30 +44,56 @@ where
 }
 
 /// Details required for fetching a 3rd-party Package
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct PackageDetails {
     pub(crate) version: Version,
     pub(crate) tarball_url: String,
     pub(crate) shasum: String,
+    pub(crate) integrity: Option<String>,
+}
+
+impl PackageDetails {
+    pub fn version(&self) -> &Version {
+        &self.version
+    }
 }
 
 /// The Tool implementation for fetching and installing 3rd-party packages
 pub struct Package {
     pub(crate) name: String,
     pub(crate) details: PackageDetails,
+    /// The version of Node to install the package with, instead of determining it from the
+    /// package's `engines`
+    pub(crate) node: Option<Version>,
 }
 
 impl Package {
     pub fn new(name: String, details: PackageDetails) -> Self {
-        Package { name, details }
+        Package {
+            name,
+            details,
+            node: None,
+        }
+    }
+
+    /// Install the package with the given version of Node, such as the one that a previous
+    /// version of the package was installed with
+    pub fn with_node(mut self, node: Version) -> Self {
+        self.node = Some(node);
+        self
     }
 
     fn ensure_fetched(&self, session: &mut Session) -> Fallible<()> {
+        let _lock = lock_fetch(&self.name, &self.details.version, |version| {
+            package_available(&self.name, version)
+        })?;
+
         if package_available(&self.name, &self.details.version)? {
             debug_already_fetched(self);
             Ok(())
         } else {
-            fetch::fetch(&self.name, &self.details, session)
+            let config = NpmConfig::current(session.project()?)?;
+            fetch::fetch(&self.name, &self.details, &config)
         }
     }
 
@@ -96,7 +124,22 @@ impl Tool for Package {
         } else {
             self.ensure_fetched(session)?;
 
-            let bin_map = install::install(&self.name, &self.details.version, session)?;
+            // The package configs and shims are shared with other packages, so another process
+            // mustn't change them while this one is installing
+            let _lock = lock_volta_home()?;
+
+            // Another process may have installed the same version while this one was waiting
+            if self.is_installed() {
+                info!("Package {} is already installed", self);
+                return Ok(());
+            }
+
+            let bin_map = install::install(
+                &self.name,
+                &self.details.version,
+                self.node.as_ref(),
+                session,
+            )?;
 
             let bins = bin_map
                 .keys()
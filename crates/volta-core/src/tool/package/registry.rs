@@ -0,0 +1,316 @@
+//! Provides a client for fetching package metadata from npm registries, configured by `.npmrc`
+
+use std::collections::HashMap;
+use std::env;
+use std::path::{Path, PathBuf};
+
+use super::serial::RawPackageMetadata;
+use crate::error::{Context, ErrorKind, Fallible, VoltaError};
+use crate::fs::read_file;
+use crate::http;
+use crate::project::Project;
+use crate::style::progress_spinner;
+use attohttpc::header::{ACCEPT, AUTHORIZATION};
+use attohttpc::{Response, StatusCode};
+use cfg_if::cfg_if;
+use log::debug;
+
+// Accept header needed to request the abbreviated metadata from the npm registry
+// See https://github.com/npm/registry/blob/master/docs/responses/package-metadata.md
+pub(crate) static NPM_ABBREVIATED_ACCEPT_HEADER: &str =
+    "application/vnd.npm.install-v1+json; q=1.0, application/json; q=0.8, */*";
+
+cfg_if! {
+    if #[cfg(feature = "mock-network")] {
+        fn public_registry() -> String {
+            mockito::SERVER_URL.to_string()
+        }
+    } else {
+        fn public_registry() -> String {
+            "https://registry.npmjs.org".to_string()
+        }
+    }
+}
+
+/// The registry settings from the user's and the project's `.npmrc` files
+///
+/// Only the settings needed to locate packages are read:
+///
+/// * `registry`, the default registry for all packages
+/// * `@scope:registry`, the registry for packages in that scope
+/// * `//host/path/:_authToken`, the token to send with requests to URLs under that prefix
+#[derive(Debug, Default)]
+pub struct NpmConfig {
+    registry: Option<String>,
+    scope_registries: HashMap<String, String>,
+    auth_tokens: HashMap<String, String>,
+}
+
+impl NpmConfig {
+    /// Reads the user's `.npmrc` and then the project's, so that project settings take precedence
+    pub fn current(project: Option<&Project>) -> Fallible<Self> {
+        let mut config = NpmConfig::default();
+
+        if let Some(file) = user_npmrc() {
+            config.load(&file)?;
+        }
+
+        if let Some(root) = project.and_then(|project| project.manifest_file().parent()) {
+            config.load(&root.join(".npmrc"))?;
+        }
+
+        Ok(config)
+    }
+
+    fn load(&mut self, file: &Path) -> Fallible<()> {
+        let contents = read_file(file).with_context(|| ErrorKind::ReadNpmrcError {
+            file: file.to_owned(),
+        })?;
+
+        if let Some(contents) = contents {
+            debug!("Using npm configuration from {}", file.display());
+            self.parse(&contents);
+        }
+
+        Ok(())
+    }
+
+    fn parse(&mut self, contents: &str) {
+        for line in contents.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+
+            let mut parts = line.splitn(2, '=');
+            let key = parts.next().unwrap_or_default().trim();
+            let value = match parts.next() {
+                Some(value) => expand_env_vars(unquote(value.trim())),
+                None => continue,
+            };
+
+            if key == "registry" {
+                self.registry = Some(value);
+            } else if key.starts_with('@') && key.ends_with(":registry") {
+                let scope = &key[..key.len() - ":registry".len()];
+                self.scope_registries.insert(scope.to_string(), value);
+            } else if key.starts_with("//") && key.ends_with(":_authToken") {
+                let prefix = &key[..key.len() - ":_authToken".len()];
+                self.auth_tokens.insert(with_trailing_slash(prefix), value);
+            }
+        }
+    }
+
+    /// Determines the URL of the metadata document for a package
+    ///
+    /// Scoped packages use the registry configured for their scope if there is one. The `/` in
+    /// a scoped package name is escaped, since registries expect the name as a single segment.
+    pub fn metadata_url(&self, package: &str) -> String {
+        let scope_registry = if package.starts_with('@') {
+            package
+                .splitn(2, '/')
+                .next()
+                .and_then(|scope| self.scope_registries.get(scope))
+        } else {
+            None
+        };
+
+        let registry = scope_registry
+            .or_else(|| self.registry.as_ref())
+            .cloned()
+            .unwrap_or_else(public_registry);
+
+        format!(
+            "{}/{}",
+            registry.trim_end_matches('/'),
+            package.replace('/', "%2f")
+        )
+    }
+
+    /// Finds the value of the `Authorization` header for a request to the given URL, if any
+    ///
+    /// Like npm, tokens are matched by the URL without its protocol, using the longest prefix.
+    pub fn authorization_for(&self, url: &str) -> Option<String> {
+        let target = &url[url.find("//")?..];
+
+        self.auth_tokens
+            .iter()
+            .filter(|(prefix, _)| target.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, token)| format!("Bearer {}", token))
+    }
+}
+
+/// Fetches and parses the metadata for a package from the given URL
+pub fn fetch_metadata(
+    package: &str,
+    url: &str,
+    authorization: Option<&str>,
+) -> Fallible<RawPackageMetadata> {
+    let spinner = progress_spinner(&format!("Fetching package metadata: {}", url));
+    let mut request = http::get(url)?.header(ACCEPT, NPM_ABBREVIATED_ACCEPT_HEADER);
+    if let Some(value) = authorization {
+        request = request.header(AUTHORIZATION, value);
+    }
+
+    let response_text = request
+        .send()
+        .and_then(Response::error_for_status)
+        .and_then(Response::text)
+        .map_err(|err| {
+            let kind = match err.kind() {
+                attohttpc::ErrorKind::StatusCode(StatusCode::NOT_FOUND) => {
+                    ErrorKind::PackageNotFound {
+                        package: package.into(),
+                    }
+                }
+                _ => ErrorKind::PackageMetadataFetchError {
+                    from_url: url.into(),
+                },
+            };
+
+            VoltaError::from_source(err, kind)
+        })?;
+
+    let metadata: RawPackageMetadata =
+        serde_json::de::from_str(&response_text).with_context(|| {
+            ErrorKind::ParsePackageMetadataError {
+                from_url: url.to_string(),
+            }
+        })?;
+
+    spinner.finish_and_clear();
+    Ok(metadata)
+}
+
+/// The location of the user's `.npmrc`, which npm allows to be overridden with an environment
+/// variable
+fn user_npmrc() -> Option<PathBuf> {
+    match env::var_os("NPM_CONFIG_USERCONFIG") {
+        Some(file) => Some(PathBuf::from(file)),
+        None => dirs::home_dir().map(|home| home.join(".npmrc")),
+    }
+}
+
+fn unquote(value: &str) -> &str {
+    if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+        &value[1..value.len() - 1]
+    } else {
+        value
+    }
+}
+
+fn with_trailing_slash(prefix: &str) -> String {
+    if prefix.ends_with('/') {
+        prefix.to_string()
+    } else {
+        format!("{}/", prefix)
+    }
+}
+
+/// Replaces `${NAME}` references with the value of the environment variable, which is commonly
+/// used to keep tokens out of `.npmrc` files. Unset variables are left as-is.
+fn expand_env_vars(value: &str) -> String {
+    let mut expanded = String::new();
+    let mut rest = value;
+
+    while let Some(start) = rest.find("${") {
+        let end = match rest[start..].find('}') {
+            Some(offset) => start + offset,
+            None => break,
+        };
+
+        expanded.push_str(&rest[..start]);
+        match env::var(&rest[start + 2..end]) {
+            Ok(var) => expanded.push_str(&var),
+            Err(_) => expanded.push_str(&rest[start..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+
+    expanded.push_str(rest);
+    expanded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(contents: &str) -> NpmConfig {
+        let mut config = NpmConfig::default();
+        config.parse(contents);
+        config
+    }
+
+    #[test]
+    fn test_metadata_url() {
+        let config = parse(
+            "; comment
+registry=https://registry.example.com/
+@internal:registry = \"https://npm.internal.example.com/api/\"
+",
+        );
+
+        assert_eq!(
+            config.metadata_url("typescript"),
+            "https://registry.example.com/typescript"
+        );
+        assert_eq!(
+            config.metadata_url("@types/node"),
+            "https://registry.example.com/@types%2fnode"
+        );
+        assert_eq!(
+            config.metadata_url("@internal/tool"),
+            "https://npm.internal.example.com/api/@internal%2ftool"
+        );
+    }
+
+    #[test]
+    fn test_metadata_url_later_files_take_precedence() {
+        let mut config = parse("registry=https://user.example.com");
+        config.parse("registry=https://project.example.com");
+
+        assert_eq!(
+            config.metadata_url("typescript"),
+            "https://project.example.com/typescript"
+        );
+    }
+
+    #[test]
+    fn test_authorization_for() {
+        let config = parse(
+            "//npm.internal.example.com/:_authToken=host-token
+//npm.internal.example.com/api:_authToken=api-token
+",
+        );
+
+        assert_eq!(
+            config.authorization_for("https://npm.internal.example.com/api/@internal%2ftool"),
+            Some("Bearer api-token".to_string())
+        );
+        assert_eq!(
+            config.authorization_for("https://npm.internal.example.com/other/tool.tgz"),
+            Some("Bearer host-token".to_string())
+        );
+        assert_eq!(
+            config.authorization_for("https://npm.internal.example.com.evil/tool"),
+            None
+        );
+        assert_eq!(
+            config.authorization_for("https://registry.npmjs.org/typescript"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_expand_env_vars() {
+        env::set_var("VOLTA_TEST_NPM_TOKEN", "secret");
+
+        assert_eq!(expand_env_vars("${VOLTA_TEST_NPM_TOKEN}"), "secret");
+        assert_eq!(expand_env_vars("a-${VOLTA_TEST_NPM_TOKEN}-b"), "a-secret-b");
+        assert_eq!(
+            expand_env_vars("${VOLTA_TEST_UNSET_VARIABLE}"),
+            "${VOLTA_TEST_UNSET_VARIABLE}"
+        );
+        assert_eq!(expand_env_vars("${unterminated"), "${unterminated");
+    }
+}
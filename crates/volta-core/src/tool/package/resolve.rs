@@ -1,16 +1,15 @@
-//! Provides resolution of 3rd-party packages into specific versions, using the npm repository
+//! Provides resolution of 3rd-party packages into specific versions, using the npm registry
 
 use std::collections::HashMap;
 
-use crate::error::{Context, ErrorKind, Fallible, VoltaError};
+use super::registry::{self, NpmConfig};
+use crate::error::{ErrorKind, Fallible};
 use crate::hook::ToolHooks;
-use crate::platform::CliPlatform;
-use crate::run::{self, ToolCommand};
+use crate::inventory::package_versions;
+use crate::offline::{self, ensure_online, is_offline};
 use crate::session::Session;
-use crate::style::{progress_spinner, tool_version};
-use crate::tool::PackageDetails;
+use crate::tool::{PackageDetails, RemoteVersion};
 use crate::version::{VersionSpec, VersionTag};
-use attohttpc::{Response, StatusCode};
 use log::debug;
 use semver::{Version, VersionReq};
 
@@ -19,6 +18,10 @@ pub fn resolve(
     matching: VersionSpec,
     session: &mut Session,
 ) -> Fallible<PackageDetails> {
+    if is_offline() {
+        return resolve_offline(name, matching);
+    }
+
     match matching {
         VersionSpec::Semver(requirement) => resolve_semver(name, requirement, session),
         VersionSpec::Exact(version) => resolve_semver(name, VersionReq::exact(&version), session),
@@ -29,18 +32,21 @@ pub fn resolve(
     }
 }
 
+/// Resolves a package version from the unpacked package images, without any registry metadata
+fn resolve_offline(name: &str, matching: VersionSpec) -> Fallible<PackageDetails> {
+    let available = package_versions()?.remove(name).unwrap_or_default();
+    let version = offline::resolve(name, matching, available, None)?;
+
+    Ok(PackageDetails {
+        version,
+        tarball_url: String::new(),
+        shasum: String::new(),
+        integrity: None,
+    })
+}
+
 fn resolve_tag(name: &str, tag: &str, session: &mut Session) -> Fallible<PackageDetails> {
-    let package_index = match session.hooks()?.package() {
-        Some(&ToolHooks {
-            index: Some(ref hook),
-            ..
-        }) => {
-            debug!("Using packages.index hook to determine package metadata URL");
-            let url = hook.resolve(&name)?;
-            resolve_package_metadata(name, &url)?.into()
-        }
-        _ => npm_view_query(name, tag, session)?,
-    };
+    let package_index = fetch_package_index(name, session)?;
 
     let mut entries = package_index.entries.into_iter();
     let details_opt = package_index
@@ -69,17 +75,7 @@ fn resolve_semver(
     matching: VersionReq,
     session: &mut Session,
 ) -> Fallible<PackageDetails> {
-    let package_index = match session.hooks()?.package() {
-        Some(&ToolHooks {
-            index: Some(ref hook),
-            ..
-        }) => {
-            debug!("Using packages.index hook to determine package metadata URL");
-            let url = hook.resolve(&name)?;
-            resolve_package_metadata(name, &url)?.into()
-        }
-        _ => npm_view_query(name, &matching.to_string(), session)?,
-    };
+    let package_index = fetch_package_index(name, session)?;
 
     let details_opt = package_index
         .entries
@@ -108,117 +104,54 @@ pub struct PackageIndex {
     pub entries: Vec<PackageDetails>,
 }
 
-/// Use `npm view` to get the info for the package. This supports:
-///
-/// * normal package installation from the public npm repo
-/// * installing packages from alternate registries, configured via .npmrc
-fn npm_view_query(name: &str, version: &str, session: &mut Session) -> Fallible<PackageIndex> {
-    let command = npm_view_command_for(name, version, session)?;
-    debug!("Running command: `{:?}`", command);
-
-    let spinner = progress_spinner(&format!(
-        "Querying metadata for {}",
-        tool_version(name, version)
-    ));
-    let output = command.output()?;
-    spinner.finish_and_clear();
-
-    if !output.status.success() {
-        debug!(
-            "Command failed, stderr is:\n{}",
-            String::from_utf8_lossy(&output.stderr).to_string()
-        );
-        debug!("Exit code is {:?}", output.status.code());
-        return Err(ErrorKind::NpmViewMetadataFetchError {
-            package: name.to_string(),
-        }
-        .into());
-    }
-
-    let response_json = String::from_utf8_lossy(&output.stdout);
-
-    // Sometimes the returned JSON is an array (semver case), otherwise it's a single object.
-    // Check if the first char is '[' and parse as an array if so
-    if response_json.starts_with('[') {
-        let metadatas: Vec<super::serial::NpmViewData> = serde_json::de::from_str(&response_json)
-            .with_context(|| {
-            ErrorKind::NpmViewMetadataParseError {
-                package: name.to_string(),
-            }
-        })?;
-        debug!("[parsed package metadata (array)]\n{:?}", metadatas);
-
-        // get latest version, making sure the array is not empty
-        let tags = match metadatas.iter().next() {
-            Some(m) => m.dist_tags.clone(),
-            None => {
-                return Err(ErrorKind::PackageNotFound {
-                    package: name.to_string(),
+impl PackageIndex {
+    /// The versions in the index, newest first, along with the dist-tags that point to each
+    pub(crate) fn into_remote_versions(self) -> Vec<RemoteVersion> {
+        let tags = self.tags;
+        self.entries
+            .into_iter()
+            .map(|PackageDetails { version, .. }| {
+                let mut version_tags: Vec<String> = tags
+                    .iter()
+                    .filter(|(_, tagged)| **tagged == version)
+                    .map(|(tag, _)| tag.clone())
+                    .collect();
+                version_tags.sort();
+
+                RemoteVersion {
+                    version,
+                    tags: version_tags,
+                    lts: false,
                 }
-                .into())
-            }
-        };
-
-        let mut entries: Vec<PackageDetails> = metadatas.into_iter().map(|e| e.into()).collect();
-        // sort so that the versions are ordered highest-to-lowest
-        entries.sort_by(|a, b| b.version.cmp(&a.version));
-
-        debug!("[sorted entries]\n{:?}", entries);
-
-        Ok(PackageIndex { tags, entries })
-    } else {
-        let metadata: super::serial::NpmViewData = serde_json::de::from_str(&response_json)
-            .with_context(|| ErrorKind::NpmViewMetadataParseError {
-                package: name.to_string(),
-            })?;
-        debug!("[parsed package metadata (single)]\n{:?}", metadata);
-
-        Ok(PackageIndex {
-            tags: metadata.dist_tags.clone(),
-            entries: vec![metadata.into()],
-        })
+            })
+            .collect()
     }
 }
 
-// build a command to run `npm view` with json output
-fn npm_view_command_for(name: &str, version: &str, session: &mut Session) -> Fallible<ToolCommand> {
-    let mut command = run::npm::command(CliPlatform::default(), session)?;
-    command.arg("view");
-    command.arg("--json");
-    command.arg(format!("{}@{}", name, version));
-    Ok(command)
+/// Lists the versions of a package in the registry, newest first
+pub fn remote_versions(name: &str, session: &Session) -> Fallible<Vec<RemoteVersion>> {
+    ensure_online(format!("the available versions of {}", name))?;
+    fetch_package_index(name, session).map(PackageIndex::into_remote_versions)
 }
 
-// fetch metadata for the input url
-fn resolve_package_metadata(
-    package_name: &str,
-    package_info_url: &str,
-) -> Fallible<super::serial::RawPackageMetadata> {
-    let spinner = progress_spinner(&format!("Fetching package metadata: {}", package_info_url));
-    let response_text = attohttpc::get(package_info_url)
-        .send()
-        .and_then(Response::error_for_status)
-        .and_then(Response::text)
-        .map_err(|err| {
-            let kind = match err.kind() {
-                attohttpc::ErrorKind::StatusCode(StatusCode::NOT_FOUND) => {
-                    ErrorKind::PackageNotFound {
-                        package: package_name.into(),
-                    }
-                }
-                _ => ErrorKind::PackageMetadataFetchError {
-                    from_url: package_info_url.into(),
-                },
-            };
-
-            VoltaError::from_source(err, kind)
-        })?;
-
-    let metadata: super::serial::RawPackageMetadata = serde_json::de::from_str(&response_text)
-        .with_context(|| ErrorKind::ParsePackageMetadataError {
-            from_url: package_info_url.to_string(),
-        })?;
+/// Fetch the index of versions for a package from the registry
+///
+/// The `packages.index` hook takes precedence if it is set. Otherwise the registry is
+/// determined by the user's and project's `.npmrc` files, which allows packages to be installed
+/// from alternate and private registries.
+fn fetch_package_index(name: &str, session: &Session) -> Fallible<PackageIndex> {
+    let config = NpmConfig::current(session.project()?)?;
+    let url = match session.hooks()?.package() {
+        Some(&ToolHooks {
+            index: Some(ref hook),
+            ..
+        }) => {
+            debug!("Using packages.index hook to determine package metadata URL");
+            hook.resolve(&name)?
+        }
+        _ => config.metadata_url(name),
+    };
 
-    spinner.finish_and_clear();
-    Ok(metadata)
+    let authorization = config.authorization_for(&url);
+    registry::fetch_metadata(name, &url, authorization.as_deref()).map(PackageIndex::from)
 }
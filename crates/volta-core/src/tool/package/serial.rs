@@ -41,6 +41,9 @@ pub struct RawPackageVersionInfo {
 pub struct RawDistInfo {
     pub shasum: String,
     pub tarball: String,
+    /// The Subresource Integrity string, which older packages don't have
+    #[serde(default)]
+    pub integrity: Option<String>,
 }
 
 impl From<RawPackageMetadata> for PackageIndex {
@@ -52,6 +55,7 @@ impl From<RawPackageMetadata> for PackageIndex {
                 version: version_info.version,
                 tarball_url: version_info.dist.tarball,
                 shasum: version_info.dist.shasum,
+                integrity: version_info.dist.integrity,
             })
             .collect();
 
@@ -64,49 +68,6 @@ impl From<RawPackageMetadata> for PackageIndex {
     }
 }
 
-// Data structures for `npm view` data
-//
-// $ npm view --json gulp@latest
-// {
-//   "name": "gulp",
-//   "description": "The streaming build system.",
-//   "dist-tags": {
-//     "latest": "4.0.2"
-//   },
-//   "version": "4.0.2",
-//   "engines": {
-//     "node": ">= 0.10"
-//   },
-//   "dist": {
-//     "shasum": "543651070fd0f6ab0a0650c6a3e6ff5a7cb09caa",
-//     "tarball": "https://registry.npmjs.org/gulp/-/gulp-4.0.2.tgz",
-//   },
-//   (...and lots of other stuff we don't use...)
-// }
-//
-#[derive(Deserialize, Clone, Debug)]
-pub struct NpmViewData {
-    pub name: String,
-    #[serde(with = "version_serde")]
-    pub version: Version,
-    pub dist: RawDistInfo,
-    #[serde(
-        rename = "dist-tags",
-        deserialize_with = "hashmap_version_serde::deserialize"
-    )]
-    pub dist_tags: HashMap<String, Version>,
-}
-
-impl From<NpmViewData> for PackageDetails {
-    fn from(view_data: NpmViewData) -> PackageDetails {
-        PackageDetails {
-            version: view_data.version,
-            tarball_url: view_data.dist.tarball,
-            shasum: view_data.dist.shasum,
-        }
-    }
-}
-
 #[derive(Serialize, Deserialize, Debug)]
 pub struct RawPackageConfig {
     pub name: String,
@@ -0,0 +1,183 @@
+//! Provides fetcher for pnpm distributions
+
+use std::fs::{rename, write};
+use std::path::Path;
+
+use super::super::{download_tool_error, registry};
+use crate::error::{Context, ErrorKind, Fallible};
+use crate::fs::{create_staging_dir, create_staging_file, set_executable};
+use crate::hook::ToolHooks;
+use crate::layout::volta_home;
+use crate::offline::ensure_online;
+use crate::project::Project;
+use crate::style::{progress_bar, tool_version};
+use crate::tool::{self, Pnpm};
+use crate::version::VersionSpec;
+use archive::{Archive, Tarball};
+use fs_utils::ensure_containing_dir_exists;
+use log::debug;
+use semver::Version;
+
+pub fn fetch(
+    version: &Version,
+    hooks: Option<&ToolHooks<Pnpm>>,
+    project: Option<&Project>,
+) -> Fallible<()> {
+    let pnpm_dir = volta_home()?.pnpm_inventory_dir();
+    let archive_filename = Pnpm::archive_filename(&version.to_string());
+    let cache_file = pnpm_dir.join(&archive_filename);
+
+    let (archive, staging) = match registry::load_cached_tarball(&cache_file) {
+        Some(archive) => {
+            debug!(
+                "Loading {} from cached archive at '{}'",
+                tool_version("pnpm", &version),
+                cache_file.display()
+            );
+            (archive, None)
+        }
+        None => {
+            ensure_online(tool_version("pnpm", &version))?;
+            let staging = create_staging_file()?;
+            let (remote_url, authorization) =
+                registry::remote_url("pnpm", version, &archive_filename, hooks, project)?;
+            let archive = fetch_remote_distro(
+                &version,
+                &remote_url,
+                authorization.as_deref(),
+                staging.path(),
+            )?;
+            (archive, Some(staging))
+        }
+    };
+
+    unpack_archive(archive, version)?;
+
+    if let Some(staging_file) = staging {
+        ensure_containing_dir_exists(&cache_file).with_context(|| {
+            ErrorKind::ContainingDirError {
+                path: cache_file.clone(),
+            }
+        })?;
+        staging_file
+            .persist(cache_file)
+            .with_context(|| ErrorKind::PersistInventoryError {
+                tool: "pnpm".into(),
+            })?;
+    }
+
+    Ok(())
+}
+
+/// Unpack the pnpm archive into the image directory so that it is ready for use
+fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<()> {
+    let temp = create_staging_dir()?;
+    debug!("Unpacking pnpm into '{}'", temp.path().display());
+
+    let progress = progress_bar(
+        archive.origin(),
+        &tool_version("pnpm", version),
+        archive
+            .uncompressed_size()
+            .unwrap_or_else(|| archive.compressed_size()),
+    );
+    let version_string = version.to_string();
+
+    archive
+        .unpack(temp.path(), &mut |_, read| {
+            progress.inc(read as u64);
+        })
+        .with_context(|| ErrorKind::UnpackArchiveError {
+            tool: "pnpm".into(),
+            version: version_string.clone(),
+        })?;
+
+    let bin_path = temp.path().join("package").join("bin");
+    write_launcher(&bin_path, "pnpm")?;
+    write_launcher(&bin_path, "pnpx")?;
+
+    let dest = volta_home()?.pnpm_image_dir(&version_string);
+    ensure_containing_dir_exists(&dest)
+        .with_context(|| ErrorKind::ContainingDirError { path: dest.clone() })?;
+
+    rename(temp.path().join("package"), &dest).with_context(|| ErrorKind::SetupToolImageError {
+        tool: "pnpm".into(),
+        version: version_string.clone(),
+        dir: dest.clone(),
+    })?;
+
+    progress.finish_and_clear();
+
+    // Note: We write this after the progress bar is finished to avoid display bugs with re-renders of the progress
+    debug!("Installing pnpm in '{}'", dest.display());
+
+    Ok(())
+}
+
+/// Fetch the distro archive from the internet
+fn fetch_remote_distro(
+    version: &Version,
+    url: &str,
+    authorization: Option<&str>,
+    staging_path: &Path,
+) -> Fallible<Box<dyn Archive>> {
+    debug!("Downloading {} from {}", tool_version("pnpm", version), url);
+
+    // Tarballs from a private registry need the same token as its metadata
+    let archive = match authorization {
+        Some(authorization) => Tarball::fetch_authorized(url, authorization, staging_path),
+        None => Tarball::fetch(url, staging_path),
+    };
+
+    archive.with_context(download_tool_error(
+        tool::Spec::Pnpm(VersionSpec::Exact(version.clone())),
+        url,
+    ))
+}
+
+/// Write the launcher scripts for one of the pnpm executables
+///
+/// The package only declares its executables in `package.json`, as `bin/<tool>.cjs` in newer
+/// releases and `bin/<tool>.js` in older ones, so we provide launchers that call them with Node.
+fn write_launcher(base_path: &Path, tool: &str) -> Fallible<()> {
+    let cjs_script = format!("{}.cjs", tool);
+    let script = if base_path.join(&cjs_script).is_file() {
+        cjs_script
+    } else {
+        format!("{}.js", tool)
+    };
+
+    let path = base_path.join(tool);
+    write(
+        &path,
+        format!(
+            r#"#!/bin/sh
+basedir=`dirname "$0"`
+
+case `uname` in
+    *CYGWIN*) basedir=`cygpath -w "$basedir"`;;
+esac
+
+node "$basedir/{}" "$@"
+"#,
+            script
+        ),
+    )
+    .and_then(|_| set_executable(&path))
+    .with_context(|| ErrorKind::WriteLauncherError { tool: tool.into() })?;
+
+    #[cfg(windows)]
+    write(
+        base_path.join(format!("{}.cmd", tool)),
+        format!(
+            r#"@ECHO OFF
+
+node "%~dp0\{}" %*
+"#,
+            script
+        ),
+    )
+    .with_context(|| ErrorKind::WriteLauncherError { tool: tool.into() })?;
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use std::fmt::{self, Display};
+
+use super::{
+    debug_already_fetched, info_fetched, info_installed, info_pinned, info_project_version,
+    info_uninstalled, lock_fetch, remove_from_inventory, warn_not_uninstalled, Tool,
+};
+use crate::error::{ErrorKind, Fallible};
+use crate::inventory::pnpm_available;
+use crate::layout::volta_home;
+use crate::session::Session;
+use crate::style::{note_prefix, tool_version};
+use log::info;
+use semver::Version;
+
+mod fetch;
+mod resolve;
+
+pub(crate) use resolve::resolve_with_hooks;
+pub use resolve::{remote_versions, resolve};
+
+/// The Tool implementation for fetching and installing pnpm
+pub struct Pnpm {
+    pub(super) version: Version,
+}
+
+impl Pnpm {
+    pub fn new(version: Version) -> Self {
+        Pnpm { version }
+    }
+
+    pub fn archive_basename(version: &str) -> String {
+        format!("pnpm-{}", version)
+    }
+
+    pub fn archive_filename(version: &str) -> String {
+        format!("{}.tgz", Pnpm::archive_basename(version))
+    }
+
+    pub(crate) fn ensure_fetched(&self, session: &mut Session) -> Fallible<()> {
+        let _lock = lock_fetch("pnpm", &self.version, pnpm_available)?;
+
+        if pnpm_available(&self.version)? {
+            debug_already_fetched(self);
+            Ok(())
+        } else {
+            fetch::fetch(&self.version, session.hooks()?.pnpm(), session.project()?)
+        }
+    }
+}
+
+impl Tool for Pnpm {
+    fn fetch(self: Box<Self>, session: &mut Session) -> Fallible<()> {
+        self.ensure_fetched(session)?;
+
+        info_fetched(self);
+        Ok(())
+    }
+    fn install(self: Box<Self>, session: &mut Session) -> Fallible<()> {
+        self.ensure_fetched(session)?;
+
+        session
+            .toolchain_mut()?
+            .set_active_pnpm(Some(self.version.clone()))?;
+
+        info_installed(self);
+
+        if let Ok(Some(project)) = session.project_platform() {
+            if let Some(pnpm) = &project.pnpm {
+                info_project_version(tool_version("pnpm", pnpm));
+            }
+        }
+        Ok(())
+    }
+    fn pin(self: Box<Self>, session: &mut Session) -> Fallible<()> {
+        if session.project()?.is_some() {
+            self.ensure_fetched(session)?;
+
+            // Note: We know this will succeed, since we checked above
+            let project = session.project_mut()?.unwrap();
+            project.pin_pnpm(Some(self.version.clone()))?;
+
+            info_pinned(self);
+            Ok(())
+        } else {
+            Err(ErrorKind::NotInPackage.into())
+        }
+    }
+}
+
+/// Uninstall a version of pnpm, removing it from the local inventory
+///
+/// If the version is the default pnpm, the default is cleared.
+pub fn uninstall(version: Version, session: &mut Session) -> Fallible<()> {
+    let toolchain = session.toolchain_mut()?;
+    if toolchain.platform().and_then(|p| p.pnpm.as_ref()) == Some(&version) {
+        toolchain.set_active_pnpm(None)?;
+        info!(
+            "{} {} was your default pnpm, you no longer have a default pnpm version",
+            note_prefix(),
+            tool_version("pnpm", &version)
+        );
+    }
+
+    let home = volta_home()?;
+    let version_str = version.to_string();
+    let found = remove_from_inventory(
+        &home.pnpm_image_dir(&version_str),
+        &[home
+            .pnpm_inventory_dir()
+            .join(Pnpm::archive_filename(&version_str))],
+    )?;
+
+    let tool = tool_version("pnpm", &version);
+    if found {
+        info_uninstalled(tool);
+    } else {
+        warn_not_uninstalled(tool);
+    }
+    Ok(())
+}
+
+impl Display for Pnpm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&tool_version("pnpm", &self.version))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pnpm_archive_basename() {
+        assert_eq!(Pnpm::archive_basename("1.2.3"), "pnpm-1.2.3");
+    }
+
+    #[test]
+    fn test_pnpm_archive_filename() {
+        assert_eq!(Pnpm::archive_filename("1.2.3"), "pnpm-1.2.3.tgz");
+    }
+}
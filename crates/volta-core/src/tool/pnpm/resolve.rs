@@ -0,0 +1,63 @@
+//! Provides resolution of pnpm Version requirements into specific versions
+
+use super::super::registry;
+use crate::error::{ErrorKind, Fallible};
+use crate::hook::ToolHooks;
+use crate::inventory::pnpm_versions;
+use crate::offline::{self, ensure_online, is_offline};
+use crate::project::Project;
+use crate::session::Session;
+use crate::tool::{Pnpm, RemoteVersion};
+use crate::version::{VersionSpec, VersionTag};
+use semver::{Version, VersionReq};
+
+pub fn resolve(matching: VersionSpec, session: &mut Session) -> Fallible<Version> {
+    resolve_with_hooks(matching, session.hooks()?.pnpm(), session.project()?)
+}
+
+/// Lists the versions of pnpm in the registry, newest first
+pub fn remote_versions(session: &Session) -> Fallible<Vec<RemoteVersion>> {
+    ensure_online("the available versions of pnpm")?;
+    let (_, index) = registry::fetch_index("pnpm", session.hooks()?.pnpm(), session.project()?)?;
+    Ok(index.into_remote_versions())
+}
+
+/// Resolves a pnpm version using the given hooks, for use before a `Session` is available
+pub(crate) fn resolve_with_hooks(
+    matching: VersionSpec,
+    hooks: Option<&ToolHooks<Pnpm>>,
+    project: Option<&Project>,
+) -> Fallible<Version> {
+    if is_offline() {
+        return offline::resolve("pnpm", matching, pnpm_versions()?, None);
+    }
+
+    match matching {
+        VersionSpec::Semver(requirement) => resolve_semver(requirement, hooks, project),
+        VersionSpec::Exact(version) => Ok(version),
+        VersionSpec::None | VersionSpec::Tag(VersionTag::Latest) => {
+            resolve_tag("latest", hooks, project)
+        }
+        VersionSpec::Tag(tag) => resolve_tag(&tag.to_string(), hooks, project),
+    }
+}
+
+fn resolve_tag(
+    tag: &str,
+    hooks: Option<&ToolHooks<Pnpm>>,
+    project: Option<&Project>,
+) -> Fallible<Version> {
+    registry::resolve_tag("pnpm", tag, hooks, project, |matching| {
+        ErrorKind::PnpmVersionNotFound { matching }
+    })
+}
+
+fn resolve_semver(
+    matching: VersionReq,
+    hooks: Option<&ToolHooks<Pnpm>>,
+    project: Option<&Project>,
+) -> Fallible<Version> {
+    registry::resolve_semver("pnpm", matching, hooks, project, |matching| {
+        ErrorKind::PnpmVersionNotFound { matching }
+    })
+}
@@ -0,0 +1,156 @@
+//! Provides resolution and downloads for tools that are distributed as packages on the npm
+//! registry, like npm and pnpm
+
+use std::fs::File;
+use std::path::Path;
+
+use super::package::registry::{NpmConfig, NPM_ABBREVIATED_ACCEPT_HEADER};
+use super::package::resolve::PackageIndex;
+use super::package::serial::RawPackageMetadata;
+use super::{registry_fetch_error, PackageDetails, Tool};
+use crate::error::{Context, ErrorKind, Fallible};
+use crate::hook::ToolHooks;
+use crate::http;
+use crate::project::Project;
+use crate::style::progress_spinner;
+use archive::{Archive, Tarball};
+use attohttpc::header::{ACCEPT, AUTHORIZATION};
+use attohttpc::Response;
+use log::debug;
+use semver::{Version, VersionReq};
+
+/// Fetch the registry metadata of the package that distributes a tool
+///
+/// The `index` hook for the tool takes precedence if it is set. Otherwise, like other packages,
+/// the registry is determined by the user's and project's `.npmrc` files.
+pub(super) fn fetch_index<T: Tool>(
+    package: &str,
+    hooks: Option<&ToolHooks<T>>,
+    project: Option<&Project>,
+) -> Fallible<(String, PackageIndex)> {
+    let config = NpmConfig::current(project)?;
+    let url = match hooks {
+        Some(&ToolHooks {
+            index: Some(ref hook),
+            ..
+        }) => {
+            debug!(
+                "Using {}.index hook to determine {} index URL",
+                package, package
+            );
+            hook.resolve(package)?
+        }
+        _ => config.metadata_url(package),
+    };
+
+    let spinner = progress_spinner(&format!("Fetching public registry: {}", url));
+    let mut request = http::get(&url)?.header(ACCEPT, NPM_ABBREVIATED_ACCEPT_HEADER);
+    if let Some(value) = config.authorization_for(&url) {
+        request = request.header(AUTHORIZATION, value);
+    }
+
+    let metadata: RawPackageMetadata = request
+        .send()
+        .and_then(Response::error_for_status)
+        .and_then(Response::json)
+        .with_context(registry_fetch_error(package, &url))?;
+
+    spinner.finish_and_clear();
+    Ok((url, metadata.into()))
+}
+
+/// Resolves the version of a tool that a dist-tag of its package points to
+pub(super) fn resolve_tag<T, E>(
+    package: &str,
+    tag: &str,
+    hooks: Option<&ToolHooks<T>>,
+    project: Option<&Project>,
+    not_found: E,
+) -> Fallible<Version>
+where
+    T: Tool,
+    E: FnOnce(String) -> ErrorKind,
+{
+    let (url, mut index) = fetch_index(package, hooks, project)?;
+
+    match index.tags.remove(tag) {
+        Some(version) => {
+            debug!(
+                "Found {}@{} matching tag '{}' from {}",
+                package, version, tag, url
+            );
+            Ok(version)
+        }
+        None => Err(not_found(tag.into()).into()),
+    }
+}
+
+/// Resolves the newest version of a tool that matches a requirement
+pub(super) fn resolve_semver<T, E>(
+    package: &str,
+    matching: VersionReq,
+    hooks: Option<&ToolHooks<T>>,
+    project: Option<&Project>,
+    not_found: E,
+) -> Fallible<Version>
+where
+    T: Tool,
+    E: FnOnce(String) -> ErrorKind,
+{
+    let (url, index) = fetch_index(package, hooks, project)?;
+
+    let details_opt = index
+        .entries
+        .into_iter()
+        .find(|PackageDetails { version, .. }| matching.matches(&version));
+
+    match details_opt {
+        Some(details) => {
+            debug!(
+                "Found {}@{} matching requirement '{}' from {}",
+                package, details.version, matching, url
+            );
+            Ok(details.version)
+        }
+        None => Err(not_found(matching.to_string()).into()),
+    }
+}
+
+/// Determine the URL to download a tool's package tarball from, using the hooks if available
+///
+/// Also returns the `Authorization` header to send with the download, if the `.npmrc` files
+/// configure a token for the URL.
+pub(super) fn remote_url<T: Tool>(
+    package: &str,
+    version: &Version,
+    archive_filename: &str,
+    hooks: Option<&ToolHooks<T>>,
+    project: Option<&Project>,
+) -> Fallible<(String, Option<String>)> {
+    let config = NpmConfig::current(project)?;
+    let url = match hooks {
+        Some(&ToolHooks {
+            distro: Some(ref hook),
+            ..
+        }) => {
+            debug!("Using {}.distro hook to determine download URL", package);
+            hook.resolve(&version, archive_filename)?
+        }
+        _ => format!("{}/-/{}", config.metadata_url(package), archive_filename),
+    };
+
+    let authorization = config.authorization_for(&url);
+    Ok((url, authorization))
+}
+
+/// Return the cached package tarball if it is valid. It may have been corrupted or interrupted
+/// in the middle of downloading.
+// ISSUE(#134) - verify checksum
+pub(super) fn load_cached_tarball(file: &Path) -> Option<Box<dyn Archive>> {
+    if file.is_file() {
+        let file = File::open(file).ok()?;
+        Tarball::load(file).ok()
+    } else {
+        None
+    }
+}
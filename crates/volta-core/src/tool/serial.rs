@@ -20,6 +20,7 @@ impl Spec {
         match tool_name {
             "node" => Spec::Node(version),
             "npm" => Spec::Npm(version),
+            "pnpm" => Spec::Pnpm(version),
             "yarn" => Spec::Yarn(version),
             package => Spec::Package(package.to_string(), version),
         }
@@ -53,6 +54,7 @@ impl Spec {
         Ok(match name {
             "node" => Spec::Node(version),
             "npm" => Spec::Npm(version),
+            "pnpm" => Spec::Pnpm(version),
             "yarn" => Spec::Yarn(version),
             package => Spec::Package(package.into(), version),
         })
@@ -116,7 +118,7 @@ impl Spec {
     ///
     /// We want to preserve the original order as much as possible, so we treat tools in
     /// the same tool category as equal. We still need to pull Node to the front of the
-    /// list, followed by Npm / Yarn, and then Packages last.
+    /// list, followed by Npm / pnpm / Yarn, and then Packages last.
     fn sort_comparator(left: &Spec, right: &Spec) -> Ordering {
         match (left, right) {
             (Spec::Node(_), Spec::Node(_)) => Ordering::Equal,
@@ -125,6 +127,9 @@ impl Spec {
             (Spec::Npm(_), Spec::Npm(_)) => Ordering::Equal,
             (Spec::Npm(_), _) => Ordering::Less,
             (_, Spec::Npm(_)) => Ordering::Greater,
+            (Spec::Pnpm(_), Spec::Pnpm(_)) => Ordering::Equal,
+            (Spec::Pnpm(_), _) => Ordering::Less,
+            (_, Spec::Pnpm(_)) => Ordering::Greater,
             (Spec::Yarn(_), Spec::Yarn(_)) => Ordering::Equal,
             (Spec::Yarn(_), _) => Ordering::Less,
             (_, Spec::Yarn(_)) => Ordering::Greater,
@@ -239,6 +244,29 @@ mod tests {
             );
         }
 
+        #[test]
+        fn parses_bare_pnpm() {
+            assert_eq!(
+                Spec::try_from_str("pnpm").expect("succeeds"),
+                Spec::Pnpm(VersionSpec::default())
+            );
+        }
+
+        #[test]
+        fn parses_pnpm_with_valid_versions() {
+            let tool = "pnpm";
+
+            assert_eq!(
+                Spec::try_from_str(&versioned_tool!(tool, MAJOR)).expect("succeeds"),
+                Spec::Pnpm(VersionSpec::from_str(MAJOR).expect("`VersionSpec` has its own tests"))
+            );
+
+            assert_eq!(
+                Spec::try_from_str(&versioned_tool!(tool, LATEST)).expect("succeeds"),
+                Spec::Pnpm(VersionSpec::Tag(VersionTag::Latest))
+            );
+        }
+
         #[test]
         fn parses_bare_packages() {
             let package = "ember-cli";
@@ -422,16 +450,18 @@ mod tests {
         }
 
         #[test]
-        fn sorts_node_npm_yarn_to_front() {
+        fn sorts_node_npm_pnpm_yarn_to_front() {
             let multiple = [
                 "ember-cli@3".to_owned(),
                 "yarn".to_owned(),
+                "pnpm@5".to_owned(),
                 "npm@5".to_owned(),
                 "node@latest".to_owned(),
             ];
             let expected = [
                 Spec::Node(VersionSpec::Tag(VersionTag::Latest)),
                 Spec::Npm(VersionSpec::from_str("5").expect("requirement is valid")),
+                Spec::Pnpm(VersionSpec::from_str("5").expect("requirement is valid")),
                 Spec::Yarn(VersionSpec::default()),
                 Spec::Package(
                     "ember-cli".to_owned(),
@@ -1,14 +1,18 @@
 //! Provides fetcher for Yarn distributions
 
-use std::fs::{rename, File};
+use std::fs::{rename, write, File};
 use std::path::{Path, PathBuf};
 
 use super::super::download_tool_error;
+use super::resolve::fetch_berry_index;
 use crate::error::{Context, ErrorKind, Fallible};
-use crate::fs::{create_staging_dir, create_staging_file};
+use crate::fs::{create_staging_dir, create_staging_file, set_executable};
 use crate::hook::ToolHooks;
 use crate::layout::volta_home;
+use crate::offline::ensure_online;
+use crate::project::Project;
 use crate::style::{progress_bar, tool_version};
+use crate::tool::package::registry::NpmConfig;
 use crate::tool::{self, Yarn};
 use crate::version::VersionSpec;
 use archive::{Archive, Tarball};
@@ -29,7 +33,11 @@ cfg_if! {
     }
 }
 
-pub fn fetch(version: &Version, hooks: Option<&ToolHooks<Yarn>>) -> Fallible<()> {
+pub fn fetch(
+    version: &Version,
+    hooks: Option<&ToolHooks<Yarn>>,
+    project: Option<&Project>,
+) -> Fallible<()> {
     let yarn_dir = volta_home()?.yarn_inventory_dir();
     let cache_file = yarn_dir.join(Yarn::archive_filename(&version.to_string()));
 
@@ -43,9 +51,15 @@ pub fn fetch(version: &Version, hooks: Option<&ToolHooks<Yarn>>) -> Fallible<()>
             (archive, None)
         }
         None => {
+            ensure_online(tool_version("yarn", &version))?;
             let staging = create_staging_file()?;
-            let remote_url = determine_remote_url(&version, hooks)?;
-            let archive = fetch_remote_distro(&version, &remote_url, staging.path())?;
+            let (remote_url, authorization) = determine_remote_url(&version, hooks, project)?;
+            let archive = fetch_remote_distro(
+                &version,
+                &remote_url,
+                authorization.as_deref(),
+                staging.path(),
+            )?;
             (archive, Some(staging))
         }
     };
@@ -91,15 +105,20 @@ fn unpack_archive(archive: Box<dyn Archive>, version: &Version) -> Fallible<()>
             version: version_string.clone(),
         })?;
 
+    // Yarn 2+ is published as an npm package, so it has a different layout from classic releases
+    let unpacked = if Yarn::is_berry(version) {
+        let unpacked = temp.path().join("package");
+        write_berry_launchers(&unpacked.join("bin"))?;
+        unpacked
+    } else {
+        temp.path().join(Yarn::archive_basename(&version_string))
+    };
+
     let dest = volta_home()?.yarn_image_dir(&version_string);
     ensure_containing_dir_exists(&dest)
         .with_context(|| ErrorKind::ContainingDirError { path: dest.clone() })?;
 
-    rename(
-        temp.path().join(Yarn::archive_basename(&version_string)),
-        &dest,
-    )
-    .with_context(|| ErrorKind::SetupToolImageError {
+    rename(unpacked, &dest).with_context(|| ErrorKind::SetupToolImageError {
         tool: "Yarn".into(),
         version: version_string.clone(),
         dir: dest.clone(),
@@ -125,8 +144,13 @@ fn load_cached_distro(file: &PathBuf) -> Option<Box<dyn Archive>> {
     }
 }
 
-/// Determine the remote URL to download from, using the hooks if available
-fn determine_remote_url(version: &Version, hooks: Option<&ToolHooks<Yarn>>) -> Fallible<String> {
+/// Determine the remote URL to download from, using the hooks if available, along with the
+/// `Authorization` header to send with the download, if any
+fn determine_remote_url(
+    version: &Version,
+    hooks: Option<&ToolHooks<Yarn>>,
+    project: Option<&Project>,
+) -> Fallible<(String, Option<String>)> {
     let version_str = version.to_string();
     let distro_file_name = Yarn::archive_filename(&version_str);
     match hooks {
@@ -135,26 +159,95 @@ fn determine_remote_url(version: &Version, hooks: Option<&ToolHooks<Yarn>>) -> F
             ..
         }) => {
             debug!("Using yarn.distro hook to determine download URL");
-            hook.resolve(&version, &distro_file_name)
+            Ok((hook.resolve(&version, &distro_file_name)?, None))
         }
-        _ => Ok(format!(
-            "{}/v{}/{}",
-            public_yarn_server_root(),
-            version_str,
-            distro_file_name
+        _ if Yarn::is_berry(version) => berry_tarball(version, project),
+        _ => Ok((
+            format!(
+                "{}/v{}/{}",
+                public_yarn_server_root(),
+                version_str,
+                distro_file_name
+            ),
+            None,
         )),
     }
 }
 
+/// Find the tarball of a Yarn 2+ release in the metadata from the registry configured by
+/// `.npmrc`, along with the token for downloading it from that registry
+fn berry_tarball(
+    version: &Version,
+    project: Option<&Project>,
+) -> Fallible<(String, Option<String>)> {
+    let (url, index) = fetch_berry_index(project)?;
+    let details = index
+        .entries
+        .into_iter()
+        .find(|details| details.version == *version)
+        .ok_or_else(|| ErrorKind::YarnVersionNotFound {
+            matching: version.to_string(),
+        })?;
+
+    debug!("Found the tarball for yarn@{} in {}", version, url);
+    let authorization = NpmConfig::current(project)?.authorization_for(&details.tarball_url);
+    Ok((details.tarball_url, authorization))
+}
+
 /// Fetch the distro archive from the internet
 fn fetch_remote_distro(
     version: &Version,
     url: &str,
+    authorization: Option<&str>,
     staging_path: &Path,
 ) -> Fallible<Box<dyn Archive>> {
     debug!("Downloading {} from {}", tool_version("yarn", version), url);
-    Tarball::fetch(url, staging_path).with_context(download_tool_error(
+
+    // Tarballs from a private registry need the same token as its metadata
+    let archive = match authorization {
+        Some(authorization) => Tarball::fetch_authorized(url, authorization, staging_path),
+        None => Tarball::fetch(url, staging_path),
+    };
+
+    archive.with_context(download_tool_error(
         tool::Spec::Yarn(VersionSpec::Exact(version.clone())),
         url,
     ))
 }
+
+/// Write the launchers for Yarn 2+, which only ships the bundled `yarn.js` script
+fn write_berry_launchers(bin_path: &Path) -> Fallible<()> {
+    for launcher in &["yarn", "yarnpkg"] {
+        let path = bin_path.join(launcher);
+        write(
+            &path,
+            r#"#!/bin/sh
+basedir=`dirname "$0"`
+
+case `uname` in
+    *CYGWIN*) basedir=`cygpath -w "$basedir"`;;
+esac
+
+node "$basedir/yarn.js" "$@"
+"#,
+        )
+        .and_then(|_| set_executable(&path))
+        .with_context(|| ErrorKind::WriteLauncherError {
+            tool: (*launcher).into(),
+        })?;
+
+        #[cfg(windows)]
+        write(
+            bin_path.join(format!("{}.cmd", launcher)),
+            r#"@ECHO OFF
+
+node "%~dp0\yarn.js" %*
+"#,
+        )
+        .with_context(|| ErrorKind::WriteLauncherError {
+            tool: (*launcher).into(),
+        })?;
+    }
+
+    Ok(())
+}
@@ -1,19 +1,23 @@
 use std::fmt::{self, Display};
 
 use super::{
-    debug_already_fetched, info_fetched, info_installed, info_pinned, info_project_version, Tool,
+    debug_already_fetched, info_fetched, info_installed, info_pinned, info_project_version,
+    info_uninstalled, lock_fetch, remove_from_inventory, warn_not_uninstalled, Tool,
 };
 use crate::error::{ErrorKind, Fallible};
 use crate::inventory::yarn_available;
+use crate::layout::volta_home;
 use crate::session::Session;
-use crate::style::tool_version;
+use crate::style::{note_prefix, tool_version};
+use log::info;
 use semver::Version;
 
 mod fetch;
 mod resolve;
 mod serial;
 
-pub use resolve::resolve;
+pub(crate) use resolve::resolve_with_hooks;
+pub use resolve::{remote_versions, resolve};
 
 /// The Tool implementation for fetching and installing Yarn
 pub struct Yarn {
@@ -33,12 +37,20 @@ impl Yarn {
         format!("{}.tar.gz", Yarn::archive_basename(version))
     }
 
+    /// Determines whether a version is Yarn 2+ (berry), which is distributed through the npm
+    /// registry as `@yarnpkg/cli-dist` rather than as a classic Yarn release
+    pub fn is_berry(version: &Version) -> bool {
+        version.major >= 2
+    }
+
     pub(crate) fn ensure_fetched(&self, session: &mut Session) -> Fallible<()> {
+        let _lock = lock_fetch("yarn", &self.version, yarn_available)?;
+
         if yarn_available(&self.version)? {
             debug_already_fetched(self);
             Ok(())
         } else {
-            fetch::fetch(&self.version, session.hooks()?.yarn())
+            fetch::fetch(&self.version, session.hooks()?.yarn(), session.project()?)
         }
     }
 }
@@ -82,6 +94,38 @@ impl Tool for Yarn {
     }
 }
 
+/// Uninstall a version of Yarn, removing it from the local inventory
+///
+/// If the version is the default Yarn, the default is cleared.
+pub fn uninstall(version: Version, session: &mut Session) -> Fallible<()> {
+    let toolchain = session.toolchain_mut()?;
+    if toolchain.platform().and_then(|p| p.yarn.as_ref()) == Some(&version) {
+        toolchain.set_active_yarn(None)?;
+        info!(
+            "{} {} was your default Yarn, you no longer have a default Yarn version",
+            note_prefix(),
+            tool_version("yarn", &version)
+        );
+    }
+
+    let home = volta_home()?;
+    let version_str = version.to_string();
+    let found = remove_from_inventory(
+        &home.yarn_image_dir(&version_str),
+        &[home
+            .yarn_inventory_dir()
+            .join(Yarn::archive_filename(&version_str))],
+    )?;
+
+    let tool = tool_version("yarn", &version);
+    if found {
+        info_uninstalled(tool);
+    } else {
+        warn_not_uninstalled(tool);
+    }
+    Ok(())
+}
+
 impl Display for Yarn {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str(&tool_version("yarn", &self.version))
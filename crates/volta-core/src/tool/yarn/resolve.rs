@@ -6,15 +6,27 @@ use super::super::registry_fetch_error;
 use super::serial;
 use crate::error::{Context, ErrorKind, Fallible};
 use crate::hook::ToolHooks;
+use crate::http;
+use crate::inventory::yarn_versions;
+use crate::offline::{self, ensure_online, is_offline};
+use crate::project::Project;
 use crate::session::Session;
 use crate::style::progress_spinner;
-use crate::tool::Yarn;
+use crate::tool::package::registry::{self, NpmConfig};
+use crate::tool::package::resolve::PackageIndex;
+use crate::tool::{RemoteVersion, Yarn};
 use crate::version::{parse_version, VersionSpec, VersionTag};
 use attohttpc::Response;
 use cfg_if::cfg_if;
 use log::debug;
 use semver::{Version, VersionReq};
 
+/// The npm package that distributes Yarn 2+
+const BERRY_PACKAGE: &str = "@yarnpkg/cli-dist";
+
+/// Tag that refers to the latest release of Yarn 2+, which isn't published to the classic index
+const BERRY_TAG: &str = "berry";
+
 // ISSUE (#86): Move public repository URLs to config file
 cfg_if! {
     if #[cfg(feature = "mock-network")] {
@@ -37,11 +49,54 @@ cfg_if! {
 }
 
 pub fn resolve(matching: VersionSpec, session: &mut Session) -> Fallible<Version> {
-    let hooks = session.hooks()?.yarn();
+    resolve_with_hooks(matching, session.hooks()?.yarn(), session.project()?)
+}
+
+/// Lists the versions of Yarn, newest first
+///
+/// This combines the classic index with the registry metadata for Yarn 2+. The `latest` dist-tag
+/// of the latter is shown as `berry`, since `yarn@latest` refers to the latest classic release.
+pub fn remote_versions(session: &Session) -> Fallible<Vec<RemoteVersion>> {
+    ensure_online("the available versions of yarn")?;
+
+    let (_, classic) = fetch_yarn_index(session.hooks()?.yarn())?;
+    let (_, berry) = fetch_berry_index(session.project()?)?;
+
+    let mut versions = berry.into_remote_versions();
+    for remote in &mut versions {
+        for tag in &mut remote.tags {
+            if tag == "latest" {
+                *tag = BERRY_TAG.into();
+            }
+        }
+    }
+    versions.extend(classic.entries.into_iter().map(|version| RemoteVersion {
+        version,
+        tags: Vec::new(),
+        lts: false,
+    }));
+    versions.sort_by(|a, b| b.version.cmp(&a.version));
+
+    Ok(versions)
+}
+
+/// Resolves a Yarn version using the given hooks, for use before a `Session` is available
+///
+/// The project is used to find the `.npmrc` registry settings for Yarn 2+.
+pub(crate) fn resolve_with_hooks(
+    matching: VersionSpec,
+    hooks: Option<&ToolHooks<Yarn>>,
+    project: Option<&Project>,
+) -> Fallible<Version> {
+    if is_offline() {
+        return offline::resolve("yarn", matching, yarn_versions()?, None);
+    }
+
     match matching {
-        VersionSpec::Semver(requirement) => resolve_semver(requirement, hooks),
+        VersionSpec::Semver(requirement) => resolve_semver(requirement, hooks, project),
         VersionSpec::Exact(version) => Ok(version),
         VersionSpec::None | VersionSpec::Tag(VersionTag::Latest) => resolve_latest(hooks),
+        VersionSpec::Tag(VersionTag::Custom(tag)) => resolve_berry_tag(&tag, project),
         VersionSpec::Tag(tag) => Err(ErrorKind::YarnVersionNotFound {
             matching: tag.to_string(),
         }
@@ -60,7 +115,7 @@ fn resolve_latest(hooks: Option<&ToolHooks<Yarn>>) -> Fallible<Version> {
         }
         _ => public_yarn_latest_version(),
     };
-    let response_text = attohttpc::get(&url)
+    let response_text = http::get(&url)?
         .send()
         .and_then(Response::error_for_status)
         .and_then(Response::text)
@@ -72,7 +127,8 @@ fn resolve_latest(hooks: Option<&ToolHooks<Yarn>>) -> Fallible<Version> {
     parse_version(response_text)
 }
 
-fn resolve_semver(matching: VersionReq, hooks: Option<&ToolHooks<Yarn>>) -> Fallible<Version> {
+/// Fetch the classic index of Yarn releases
+fn fetch_yarn_index(hooks: Option<&ToolHooks<Yarn>>) -> Fallible<(String, YarnIndex)> {
     let url = match hooks {
         Some(&ToolHooks {
             index: Some(ref hook),
@@ -85,15 +141,77 @@ fn resolve_semver(matching: VersionReq, hooks: Option<&ToolHooks<Yarn>>) -> Fall
     };
 
     let spinner = progress_spinner(&format!("Fetching public registry: {}", url));
-    let releases: serial::RawYarnIndex = attohttpc::get(&url)
+    let releases: serial::RawYarnIndex = http::get(&url)?
         .send()
         .and_then(Response::error_for_status)
         .and_then(Response::json)
         .with_context(registry_fetch_error("Yarn", &url))?;
-    let index = YarnIndex::from(releases);
-    let releases = index.entries;
     spinner.finish_and_clear();
-    let version_opt = releases.into_iter().rev().find(|v| matching.matches(v));
+
+    Ok((url, YarnIndex::from(releases)))
+}
+
+fn resolve_semver(
+    matching: VersionReq,
+    hooks: Option<&ToolHooks<Yarn>>,
+    project: Option<&Project>,
+) -> Fallible<Version> {
+    let (url, index) = fetch_yarn_index(hooks)?;
+    let version_opt = index
+        .entries
+        .into_iter()
+        .rev()
+        .find(|v| matching.matches(v));
+
+    match version_opt {
+        Some(version) => {
+            debug!(
+                "Found yarn@{} matching requirement '{}' from {}",
+                version, matching, url
+            );
+            Ok(version)
+        }
+        // Yarn 2+ isn't released to the classic index, so check its registry metadata instead
+        None => resolve_berry_semver(matching, project),
+    }
+}
+
+/// Fetch the npm registry metadata of `@yarnpkg/cli-dist`, which is the distribution of Yarn 2+
+///
+/// Like other packages, the registry is determined by the user's and project's `.npmrc` files.
+pub(super) fn fetch_berry_index(project: Option<&Project>) -> Fallible<(String, PackageIndex)> {
+    let config = NpmConfig::current(project)?;
+    let url = config.metadata_url(BERRY_PACKAGE);
+    let authorization = config.authorization_for(&url);
+
+    let metadata = registry::fetch_metadata(BERRY_PACKAGE, &url, authorization.as_deref())?;
+    Ok((url, metadata.into()))
+}
+
+fn resolve_berry_tag(tag: &str, project: Option<&Project>) -> Fallible<Version> {
+    let (url, mut index) = fetch_berry_index(project)?;
+    let dist_tag = if tag == BERRY_TAG { "latest" } else { tag };
+
+    match index.tags.remove(dist_tag) {
+        Some(version) => {
+            debug!("Found yarn@{} matching tag '{}' from {}", version, tag, url);
+            Ok(version)
+        }
+        None => Err(ErrorKind::YarnVersionNotFound {
+            matching: tag.into(),
+        }
+        .into()),
+    }
+}
+
+fn resolve_berry_semver(matching: VersionReq, project: Option<&Project>) -> Fallible<Version> {
+    let (url, index) = fetch_berry_index(project)?;
+
+    let version_opt = index
+        .entries
+        .into_iter()
+        .map(|details| details.version)
+        .find(|version| matching.matches(version));
 
     match version_opt {
         Some(version) => {
@@ -1,7 +1,7 @@
 use std::fs::write;
 
 use crate::error::{Context, ErrorKind, Fallible};
-use crate::fs::touch;
+use crate::fs::{lock_volta_home, touch};
 use crate::layout::volta_home;
 use crate::platform::PlatformSpec;
 use lazycell::LazyCell;
@@ -74,6 +74,7 @@ impl Toolchain {
                 self.platform = Some(PlatformSpec {
                     node: node_version.clone(),
                     npm: None,
+                    pnpm: None,
                     yarn: None,
                 });
                 dirty = true;
@@ -99,6 +100,18 @@ impl Toolchain {
         Ok(())
     }
 
+    /// Set the active pnpm version in the default platform file.
+    pub fn set_active_pnpm(&mut self, pnpm: Option<Version>) -> Fallible<()> {
+        if let Some(platform) = self.platform.as_mut() {
+            if platform.pnpm != pnpm {
+                platform.pnpm = pnpm;
+                self.save()?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Set the active Npm version in the default platform file.
     pub fn set_active_npm(&mut self, npm: Option<Version>) -> Fallible<()> {
         if let Some(platform) = self.platform.as_mut() {
@@ -112,6 +125,7 @@ impl Toolchain {
     }
 
     pub fn save(&self) -> Fallible<()> {
+        let _lock = lock_volta_home()?;
         let path = volta_home()?.default_platform_file();
         let result = match &self.platform {
             Some(platform) => {
@@ -19,6 +19,10 @@ pub struct Platform {
     #[serde(default)]
     #[serde(with = "option_version_serde")]
     pub yarn: Option<Version>,
+    #[serde(default)]
+    #[serde(with = "option_version_serde")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pnpm: Option<Version>,
 }
 
 impl Platform {
@@ -29,14 +33,17 @@ impl Platform {
                 npm: source.npm.clone(),
             }),
             yarn: source.yarn.clone(),
+            pnpm: source.pnpm.clone(),
         }
     }
 
     pub fn into_platform(self) -> Option<PlatformSpec> {
         let yarn = self.yarn;
+        let pnpm = self.pnpm;
         self.node.map(|node_version| PlatformSpec {
             node: node_version.runtime,
             npm: node_version.npm,
+            pnpm,
             yarn,
         })
     }
@@ -86,6 +93,7 @@ pub mod tests {
                 runtime: Version::parse("4.5.6").expect("could not parse version"),
                 npm: Some(Version::parse("7.8.9").expect("could not parse version")),
             }),
+            pnpm: None,
         };
         assert_eq!(platform, expected_platform);
     }
@@ -97,6 +105,7 @@ pub mod tests {
         let expected_platform = Platform {
             node: None,
             yarn: None,
+            pnpm: None,
         };
         assert_eq!(platform, expected_platform);
     }
@@ -107,6 +116,7 @@ pub mod tests {
             yarn: Some(Version::parse("1.2.3").expect("could not parse version")),
             node: Version::parse("4.5.6").expect("could not parse version"),
             npm: Some(Version::parse("7.8.9").expect("could not parse version")),
+            pnpm: None,
         };
         let json_str = Platform::of(&platform_spec)
             .into_json()
@@ -114,4 +124,30 @@ pub mod tests {
         let expected_json_str = BASIC_JSON_STR.to_string();
         assert_eq!(json_str, expected_json_str);
     }
+
+    const PNPM_JSON_STR: &str = r#"{
+  "node": {
+    "runtime": "4.5.6",
+    "npm": null
+  },
+  "yarn": null,
+  "pnpm": "5.5.1"
+}"#;
+
+    #[test]
+    fn test_pnpm_round_trip() {
+        let platform = Platform::from_json(PNPM_JSON_STR.to_string())
+            .expect("could not parse JSON string")
+            .into_platform()
+            .expect("platform has a Node version");
+        assert_eq!(
+            platform.pnpm,
+            Some(Version::parse("5.5.1").expect("could not parse version"))
+        );
+
+        let json_str = Platform::of(&platform)
+            .into_json()
+            .expect("could not serialize platform to JSON");
+        assert_eq!(json_str, PNPM_JSON_STR);
+    }
 }
@@ -9,8 +9,8 @@ use semver::{Compat, ReqParseError, VersionReq};
 //    commas differently)
 //
 // Because we are parsing the version requirements from the command line,
-// then serializing them to pass to `npm view`, they need to be handled in
-// a Node-compatible way (or we get the wrong version info returned).
+// where users write them the same way as for npm, they need to be handled in
+// a Node-compatible way (or we resolve the wrong version).
 pub fn parse_requirements(src: &str) -> Result<VersionReq, ReqParseError> {
     let src = src.trim();
     if !src.is_empty() && src.chars().next().unwrap().is_digit(10) {
@@ -11,21 +11,30 @@ layout! {
             "node": node_cache_dir {
                 "index.json": node_index_file;
                 "index.json.expires": node_index_expiry_file;
+                "index-musl.json": node_musl_index_file;
+                "index-musl.json.expires": node_musl_index_expiry_file;
+                "distro": node_distro_file;
             }
+            "engines": engines_cache_dir {}
         }
         "bin": shim_dir {}
+        "locks": lock_dir {
+            "volta.lock": global_lock_file;
+        }
         "log": log_dir {}
         "tools": tools_dir {
             "inventory": inventory_dir {
                 "node": node_inventory_dir {}
                 "npm": npm_inventory_dir {}
                 "packages": package_inventory_dir {}
+                "pnpm": pnpm_inventory_dir {}
                 "yarn": yarn_inventory_dir {}
             }
             "image": image_dir {
                 "node": node_image_root_dir {}
                 "npm": npm_image_root_dir {}
                 "yarn": yarn_image_root_dir {}
+                "pnpm": pnpm_image_root_dir {}
                 "packages": package_image_root_dir {}
             }
             "user": default_toolchain_dir {
@@ -36,6 +45,7 @@ layout! {
         }
         "tmp": tmp_dir {}
         "hooks.json": default_hooks_file;
+        "trusted-hooks.json": trusted_hooks_file;
         "layout.v2": layout_file;
     }
 }
@@ -75,6 +85,14 @@ impl VoltaHome {
         path_buf!(self.yarn_image_dir(version), "bin")
     }
 
+    pub fn pnpm_image_dir(&self, version: &str) -> PathBuf {
+        path_buf!(self.pnpm_image_root_dir.clone(), version)
+    }
+
+    pub fn pnpm_image_bin_dir(&self, version: &str) -> PathBuf {
+        path_buf!(self.pnpm_image_dir(version), "bin")
+    }
+
     pub fn package_image_dir(&self, name: &str, version: &str) -> PathBuf {
         path_buf!(self.package_image_root_dir.clone(), name, version)
     }
@@ -97,6 +115,28 @@ impl VoltaHome {
         )
     }
 
+    pub fn node_distro_checksum(&self, archive: &str) -> PathBuf {
+        path_buf!(
+            self.node_inventory_dir.clone(),
+            format!("{}.sha256", archive)
+        )
+    }
+
+    pub fn node_image_distro_file(&self, version: &str) -> PathBuf {
+        path_buf!(
+            self.node_inventory_dir.clone(),
+            format!("node-v{}-distro", version)
+        )
+    }
+
+    pub fn engines_cache_file(&self, key: &str) -> PathBuf {
+        path_buf!(self.engines_cache_dir.clone(), format!("{}.json", key))
+    }
+
+    pub fn tool_lock_file(&self, tool: &str, version: &str) -> PathBuf {
+        path_buf!(self.lock_dir.clone(), format!("{}-{}.lock", tool, version))
+    }
+
     pub fn shim_file(&self, toolname: &str) -> PathBuf {
         path_buf!(self.shim_dir.clone(), executable(toolname))
     }
@@ -21,6 +21,7 @@ use v1::V1;
 use v2::V2;
 
 use volta_core::error::Fallible;
+use volta_core::fs::lock_volta_home;
 use volta_core::layout::volta_home;
 #[cfg(unix)]
 use volta_core::layout::volta_install;
@@ -132,6 +133,19 @@ impl MigrationState {
 pub fn run_migration() -> Fallible<()> {
     let mut state = MigrationState::current()?;
 
+    // Migrating an existing directory moves files around, so only one process can do it at a time.
+    // Once the lock is acquired, the state is detected again, since another process may have
+    // already finished the migration. (A new directory is created idempotently, and creating the
+    // lock file first would make it look like a legacy layout, so it isn't locked.)
+    let _lock = match state {
+        MigrationState::Empty(_) | MigrationState::V2(_) => None,
+        _ => {
+            let lock = lock_volta_home()?;
+            state = MigrationState::current()?;
+            Some(lock)
+        }
+    };
+
     // To keep the complexity of writing a new migration from continuously increasing, each new
     // layout version only needs to implement a migration from 2 states: Empty and the previously
     // latest version. We then apply the migrations sequentially here: V0 -> V1 -> ... -> VX
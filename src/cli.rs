@@ -36,6 +36,13 @@ pub(crate) struct Volta {
     )]
     pub(crate) quiet: bool,
 
+    #[structopt(
+        long = "offline",
+        help = "Resolves tool versions from the local inventory, without using the network",
+        global = true
+    )]
+    pub(crate) offline: bool,
+
     #[structopt(
         short = "v",
         long = "version",
@@ -60,6 +67,16 @@ impl Volta {
             Volta::from_iter(["volta", "help"].iter()).run(session)
         }
     }
+
+    /// Whether the Volta directory needs to be migrated to the current layout before running
+    ///
+    /// `volta doctor` checks the layout itself, so that it can diagnose a failed migration.
+    pub(crate) fn needs_layout(&self) -> bool {
+        match self.command {
+            Some(Subcommand::Doctor(_)) => false,
+            _ => true,
+        }
+    }
 }
 
 #[derive(StructOpt)]
@@ -76,6 +93,10 @@ pub(crate) enum Subcommand {
     #[structopt(name = "uninstall", author = "", version = "")]
     Uninstall(command::Uninstall),
 
+    /// Removes fetched tools that are no longer used by your toolchain
+    #[structopt(name = "prune", author = "", version = "")]
+    Prune(command::Prune),
+
     /// Pins your project's runtime or package manager
     #[structopt(name = "pin", author = "", version = "")]
     Pin(command::Pin),
@@ -84,6 +105,26 @@ pub(crate) enum Subcommand {
     #[structopt(name = "list", alias = "ls", author = "", version = "")]
     List(command::List),
 
+    /// Displays the versions of a tool that are available to fetch
+    #[structopt(name = "list-remote", author = "", version = "")]
+    ListRemote(command::ListRemote),
+
+    /// Displays the tools in your toolchain that have newer versions available
+    #[structopt(name = "outdated", author = "", version = "")]
+    Outdated(command::Outdated),
+
+    /// Upgrades the tools in your toolchain to newer versions
+    #[structopt(name = "upgrade", author = "", version = "")]
+    Upgrade(command::Upgrade),
+
+    /// Writes your default toolchain and installed packages to a file
+    #[structopt(name = "export", author = "", version = "")]
+    Export(command::Export),
+
+    /// Installs the toolchain from a file written by `volta export`
+    #[structopt(name = "import", author = "", version = "")]
+    Import(command::Import),
+
     /// Generates Volta completions
     #[structopt(
         name = "completions",
@@ -122,7 +163,19 @@ otherwise, they will be written to `stdout`.
     #[structopt(name = "setup", author = "", version = "")]
     Setup(command::Setup),
 
-    /// Run a command with custom Node, npm, and/or Yarn versions
+    /// Allows the current project's hooks to run commands and change where tools are downloaded from
+    #[structopt(name = "trust", author = "", version = "")]
+    Trust(command::Trust),
+
+    /// Prints the environment for running your project's tools without the shims
+    #[structopt(name = "env", author = "", version = "")]
+    Env(command::Env),
+
+    /// Checks your Volta environment for problems
+    #[structopt(name = "doctor", author = "", version = "")]
+    Doctor(command::Doctor),
+
+    /// Run a command with custom Node, npm, pnpm, and/or Yarn versions
     #[structopt(name = "run", author = "", version = "")]
     #[structopt(raw(setting = "structopt::clap::AppSettings::AllowLeadingHyphen"))]
     #[structopt(raw(setting = "structopt::clap::AppSettings::TrailingVarArg"))]
@@ -135,12 +188,21 @@ impl Subcommand {
             Subcommand::Fetch(fetch) => fetch.run(session),
             Subcommand::Install(install) => install.run(session),
             Subcommand::Uninstall(uninstall) => uninstall.run(session),
+            Subcommand::Prune(prune) => prune.run(session),
             Subcommand::Pin(pin) => pin.run(session),
             Subcommand::List(list) => list.run(session),
+            Subcommand::ListRemote(list_remote) => list_remote.run(session),
+            Subcommand::Outdated(outdated) => outdated.run(session),
+            Subcommand::Upgrade(upgrade) => upgrade.run(session),
+            Subcommand::Export(export) => export.run(session),
+            Subcommand::Import(import) => import.run(session),
             Subcommand::Completions(completions) => completions.run(session),
             Subcommand::Which(which) => which.run(session),
             Subcommand::Use(r#use) => r#use.run(session),
             Subcommand::Setup(setup) => setup.run(session),
+            Subcommand::Trust(trust) => trust.run(session),
+            Subcommand::Env(env) => env.run(session),
+            Subcommand::Doctor(doctor) => doctor.run(session),
             Subcommand::Run(run) => run.run(session),
         }
     }
@@ -0,0 +1,429 @@
+//! Provides the `volta doctor` command, which checks the Volta environment for common problems
+//! and can repair the ones that are safe to repair automatically.
+
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use log::{info, warn};
+use structopt::StructOpt;
+
+use crate::command::Command;
+use crate::common::{ensure_layout, Error};
+use volta_core::error::{Context, ErrorKind, ExitCode, Fallible};
+use volta_core::fs::{
+    ensure_dir_does_not_exist, ensure_file_does_not_exist, lock_volta_home, read_dir_eager,
+};
+use volta_core::inventory::package_configs;
+use volta_core::layout::{volta_home, volta_install};
+use volta_core::session::{ActivityKind, Session};
+use volta_core::shim;
+use volta_core::style::{note_prefix, success_prefix};
+use volta_core::tool::BinConfig;
+
+/// The tools that always have a shim, regardless of which packages are installed
+const CORE_SHIMS: [&str; 5] = ["node", "npm", "npx", "pnpm", "yarn"];
+
+/// Staging directories younger than this may belong to a fetch that is still running
+const STALE_STAGING_AGE: Duration = Duration::from_secs(60 * 60);
+
+#[derive(StructOpt)]
+pub(crate) struct Doctor {
+    /// Repair the problems that can be fixed safely
+    #[structopt(long = "fix")]
+    fix: bool,
+}
+
+/// A problem with the Volta environment, and how it can be repaired
+struct Problem {
+    description: String,
+    repair: Repair,
+}
+
+/// How a problem can be repaired
+enum Repair {
+    /// The problem can't be repaired automatically, the message describes what to do instead
+    Manual(String),
+    /// Migrate the Volta directory to the current layout
+    Migrate,
+    /// Replace a shim whose target no longer exists
+    RecreateShim(String),
+    /// Remove a shim, and its bin config, for a package that is no longer installed
+    RemoveShim(String),
+    /// Remove a file or directory left behind by an interrupted operation
+    Remove(PathBuf),
+}
+
+impl Problem {
+    fn new(description: String, repair: Repair) -> Self {
+        Problem {
+            description,
+            repair,
+        }
+    }
+
+    /// Whether repairing the problem changes files that other Volta commands may be using
+    ///
+    /// Migrating isn't included, since the migration locks the Volta directory itself.
+    fn repair_needs_lock(&self) -> bool {
+        match self.repair {
+            Repair::Manual(_) | Repair::Migrate => false,
+            Repair::RecreateShim(_) | Repair::RemoveShim(_) | Repair::Remove(_) => true,
+        }
+    }
+
+    /// Attempt to repair the problem, returning whether it was repaired
+    fn repair(&self) -> Fallible<bool> {
+        let home = volta_home()?;
+
+        match &self.repair {
+            Repair::Manual(_) => return Ok(false),
+            Repair::Migrate => match ensure_layout() {
+                Ok(()) => {}
+                Err(Error::Volta(err)) => return Err(err),
+                Err(Error::Tool(_)) => return Ok(false),
+            },
+            Repair::RecreateShim(name) => {
+                shim::delete(name)?;
+                shim::create(name)?;
+            }
+            Repair::RemoveShim(name) => {
+                shim::delete(name)?;
+                ensure_file_does_not_exist(&home.default_tool_bin_config(name))?;
+            }
+            Repair::Remove(path) => {
+                if path.is_dir() {
+                    ensure_dir_does_not_exist(path)?;
+                } else {
+                    ensure_file_does_not_exist(path)?;
+                }
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+impl Command for Doctor {
+    fn run(self, session: &mut Session) -> Fallible<ExitCode> {
+        session.add_event_start(ActivityKind::Doctor);
+
+        let mut problems = Vec::new();
+        check_path(&mut problems)?;
+        check_layout(&mut problems)?;
+        check_shims(&mut problems)?;
+        check_package_bins(&mut problems)?;
+        check_staging(&mut problems)?;
+
+        #[cfg(unix)]
+        report_profiles()?;
+
+        let mut unresolved = 0;
+        let mut repairable = 0;
+
+        // The lock is only acquired once it is needed, as the layout problem is always repaired
+        // first, and the migration would wait forever for a lock that is held here
+        let mut lock = None;
+
+        for problem in &problems {
+            if self.fix {
+                if lock.is_none() && problem.repair_needs_lock() {
+                    lock = Some(lock_volta_home()?);
+                }
+
+                if problem.repair()? {
+                    info!("{} Fixed: {}", success_prefix(), problem.description);
+                    continue;
+                }
+            }
+
+            unresolved += 1;
+            match &problem.repair {
+                Repair::Manual(hint) => warn!("{}\n    {}", problem.description, hint),
+                _ => {
+                    repairable += 1;
+                    warn!("{}", problem.description);
+                }
+            }
+        }
+
+        if problems.is_empty() {
+            info!("{} No problems found", success_prefix());
+        } else if repairable > 0 && !self.fix {
+            info!(
+                "{} Run `volta doctor --fix` to repair {} of these problems",
+                note_prefix(),
+                repairable
+            );
+        }
+
+        let exit_code = if unresolved == 0 {
+            ExitCode::Success
+        } else {
+            ExitCode::EnvironmentError
+        };
+
+        session.add_event_end(ActivityKind::Doctor, exit_code);
+        Ok(exit_code)
+    }
+}
+
+/// Check that the shim directory is on the PATH, and that no other Node tools come before it
+fn check_path(problems: &mut Vec<Problem>) -> Fallible<()> {
+    let shim_dir = volta_home()?.shim_dir();
+    let path = env::var_os("PATH").unwrap_or_default();
+    let dirs: Vec<PathBuf> = env::split_paths(&path).collect();
+
+    let position = match dirs.iter().position(|dir| dir == shim_dir) {
+        Some(position) => position,
+        None => {
+            problems.push(Problem::new(
+                format!("{} is not on your PATH", shim_dir.display()),
+                Repair::Manual("Run `volta setup`, then open a new terminal.".into()),
+            ));
+            return Ok(());
+        }
+    };
+
+    for dir in &dirs[..position] {
+        for tool in CORE_SHIMS.iter() {
+            let executable = dir.join(format!("{}{}", tool, env::consts::EXE_SUFFIX));
+            if executable.is_file() {
+                problems.push(Problem::new(
+                    format!(
+                        "{} comes before Volta's shims on your PATH",
+                        executable.display()
+                    ),
+                    Repair::Manual(format!(
+                        "Move {} after {} in your PATH, or remove it.",
+                        dir.display(),
+                        shim_dir.display()
+                    )),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that the Volta directory has been migrated to the current layout
+fn check_layout(problems: &mut Vec<Problem>) -> Fallible<()> {
+    let home = volta_home()?;
+    if home.layout_file().exists() {
+        return Ok(());
+    }
+
+    let description = match fs::read_dir(home.root()) {
+        Ok(entries) => {
+            let previous = entries
+                .filter_map(|entry| entry.ok())
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .find(|name| name.starts_with("layout."));
+
+            match previous {
+                Some(name) => format!(
+                    "The Volta directory uses layout {}, which is out of date",
+                    &name["layout.".len()..]
+                ),
+                None => "The Volta directory has not been migrated to the current layout".into(),
+            }
+        }
+        Err(_) => format!(
+            "The Volta directory {} does not exist",
+            home.root().display()
+        ),
+    };
+
+    problems.push(Problem::new(description, Repair::Migrate));
+    Ok(())
+}
+
+/// Check for shims that are broken, or that belong to packages which are no longer installed
+///
+/// Shims are usually symlinks to `volta-shim`, but they can also be regular files, such as the
+/// scripts for Git Bash on Windows. Each shim is only reported once, however many files it has.
+fn check_shims(problems: &mut Vec<Problem>) -> Fallible<()> {
+    let home = volta_home()?;
+    let shim_dir = home.shim_dir();
+    if !shim_dir.exists() {
+        return Ok(());
+    }
+
+    // Volta itself may be installed into the shim directory
+    let install = volta_install()?;
+    let executables = [
+        install.shim_executable(),
+        install.main_executable(),
+        install.migrate_executable(),
+    ];
+
+    let entries = read_dir_eager(shim_dir).with_context(|| ErrorKind::ReadDirError {
+        dir: shim_dir.to_owned(),
+    })?;
+
+    let mut names = BTreeSet::new();
+    let mut broken = BTreeSet::new();
+    for (entry, metadata) in entries {
+        let path = entry.path();
+        if metadata.is_dir() || executables.iter().any(|executable| *executable == path) {
+            continue;
+        }
+
+        let name = match path.file_stem().and_then(|stem| stem.to_str()) {
+            Some(name) => name.to_string(),
+            None => continue,
+        };
+
+        // A symlink whose target can't be read points to a Volta install that has been moved
+        if metadata.file_type().is_symlink() && fs::metadata(&path).is_err() {
+            broken.insert(name.clone());
+        }
+        names.insert(name);
+    }
+
+    for name in names {
+        if !CORE_SHIMS.contains(&name.as_str()) {
+            let bin_config_file = home.default_tool_bin_config(&name);
+            if !bin_config_file.exists() {
+                problems.push(Problem::new(
+                    format!("The shim for '{}' does not belong to any package", name),
+                    Repair::RemoveShim(name),
+                ));
+                continue;
+            }
+
+            let config = match BinConfig::from_file(bin_config_file) {
+                Ok(config) => config,
+                Err(_) => {
+                    problems.push(Problem::new(
+                        format!("The configuration for the '{}' shim can't be read", name),
+                        Repair::RemoveShim(name),
+                    ));
+                    continue;
+                }
+            };
+
+            if !home.default_package_config_file(&config.package).exists() {
+                problems.push(Problem::new(
+                    format!(
+                        "The shim for '{}' belongs to {}, which is not installed",
+                        name, config.package
+                    ),
+                    Repair::RemoveShim(name),
+                ));
+                continue;
+            }
+        }
+
+        if broken.contains(&name) {
+            problems.push(Problem::new(
+                format!("The shim for '{}' is broken", name),
+                Repair::RecreateShim(name),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that the image of each installed package contains the executables for its bins
+fn check_package_bins(problems: &mut Vec<Problem>) -> Fallible<()> {
+    let home = volta_home()?;
+
+    for package in package_configs()? {
+        let version = package.version.to_string();
+        let image_dir = home.package_image_dir(&package.name, &version);
+
+        for bin in &package.bins {
+            let bin_config_file = home.default_tool_bin_config(bin);
+            if !bin_config_file.exists() {
+                continue;
+            }
+
+            let description = match BinConfig::from_file(bin_config_file) {
+                Ok(config) if image_dir.join(&config.path).exists() => continue,
+                Ok(_) => format!(
+                    "{}@{} is missing its '{}' executable",
+                    package.name, version, bin
+                ),
+                Err(_) => format!(
+                    "The configuration for the '{}' executable of {}@{} can't be read",
+                    bin, package.name, version
+                ),
+            };
+
+            problems.push(Problem::new(
+                description,
+                Repair::Manual(format!(
+                    "Run `volta install {}@{}` to reinstall it.",
+                    package.name, version
+                )),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check for staging files and directories left behind by interrupted fetches
+fn check_staging(problems: &mut Vec<Problem>) -> Fallible<()> {
+    let tmp_dir = volta_home()?.tmp_dir();
+    if !tmp_dir.exists() {
+        return Ok(());
+    }
+
+    let entries = read_dir_eager(tmp_dir).with_context(|| ErrorKind::ReadDirError {
+        dir: tmp_dir.to_owned(),
+    })?;
+
+    let now = SystemTime::now();
+    for (entry, metadata) in entries {
+        let stale = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok())
+            .map_or(false, |age| age > STALE_STAGING_AGE);
+
+        if stale {
+            let path = entry.path();
+            problems.push(Problem::new(
+                format!("{} was left behind by an interrupted fetch", path.display()),
+                Repair::Remove(path),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Report which shell profiles `volta setup` has configured
+#[cfg(unix)]
+fn report_profiles() -> Fallible<()> {
+    let configured: Vec<PathBuf> = super::setup::os::determine_profiles()?
+        .into_iter()
+        .filter(|profile| sets_volta_home(profile))
+        .collect();
+
+    if configured.is_empty() {
+        info!(
+            "{} None of your shell profiles set up Volta. If Volta isn't working in new terminals, run `volta setup`.",
+            note_prefix()
+        );
+    } else {
+        for profile in configured {
+            info!("Volta is set up in {}", profile.display());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn sets_volta_home(profile: &std::path::Path) -> bool {
+    fs::read_to_string(profile)
+        .map(|contents| contents.contains("VOLTA_HOME"))
+        .unwrap_or(false)
+}
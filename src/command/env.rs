@@ -0,0 +1,270 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::ffi::{OsStr, OsString};
+use std::iter;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use structopt::StructOpt;
+
+use volta_core::error::{Context, ErrorKind, ExitCode, Fallible};
+use volta_core::layout::{volta_home, volta_install};
+use volta_core::platform::{Image, Platform};
+use volta_core::session::{ActivityKind, Session};
+
+use crate::command::Command;
+
+#[derive(Copy, Clone, PartialEq)]
+enum Shell {
+    Bash,
+    Fish,
+    Json,
+    Zsh,
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bash" => Ok(Shell::Bash),
+            "fish" => Ok(Shell::Fish),
+            "json" => Ok(Shell::Json),
+            "zsh" => Ok(Shell::Zsh),
+            _ => Err("No".into()),
+        }
+    }
+}
+
+#[derive(StructOpt)]
+pub(crate) struct Env {
+    /// The shell to print commands for
+    ///
+    /// Defaults to the shell in `SHELL`. The `json` format prints the variables as an object, for
+    /// editors and other tools that set up the environment themselves.
+    #[structopt(
+        long = "shell",
+        raw(possible_values = r#"&["bash", "zsh", "fish", "json"]"#)
+    )]
+    shell: Option<Shell>,
+
+    /// Print a hook that updates the environment whenever the shell changes directories
+    #[structopt(long = "hook")]
+    hook: bool,
+}
+
+impl Env {
+    fn shell(&self) -> Shell {
+        self.shell.unwrap_or_else(|| match env::var("SHELL") {
+            Ok(shell) if shell.ends_with("fish") => Shell::Fish,
+            Ok(shell) if shell.ends_with("zsh") => Shell::Zsh,
+            _ => Shell::Bash,
+        })
+    }
+}
+
+impl Command for Env {
+    fn run(self, session: &mut Session) -> Fallible<ExitCode> {
+        session.add_event_start(ActivityKind::Env);
+
+        let shell = self.shell();
+        let output = if self.hook {
+            hook(shell)?
+        } else {
+            let vars = match Platform::current(session)? {
+                Some(platform) => image_vars(&platform.checkout(session)?)?,
+                None => no_image_vars()?,
+            };
+            format(shell, &vars)
+        };
+
+        println!("{}", output);
+
+        session.add_event_end(ActivityKind::Env, ExitCode::Success);
+        Ok(ExitCode::Success)
+    }
+}
+
+/// The environment variables to set, in order. `None` means the variable should be unset.
+type Vars = Vec<(&'static str, Option<String>)>;
+
+/// Determines the environment for running the tools in an image directly
+fn image_vars(image: &Image) -> Fallible<Vars> {
+    remove_previous_images()?;
+
+    // `Image::path` removes the shim directory entirely, but the shims for installed packages
+    // should still be available, so it goes back on the `PATH` after the image's directories
+    let home = volta_home()?;
+    let (images, rest): (Vec<PathBuf>, Vec<PathBuf>) =
+        env::split_paths(&image.path()?).partition(|dir| dir.starts_with(home.image_dir()));
+    let path = env::join_paths(
+        images
+            .iter()
+            .map(PathBuf::as_path)
+            .chain(iter::once(home.shim_dir()))
+            .chain(rest.iter().map(PathBuf::as_path)),
+    )
+    .with_context(|| ErrorKind::BuildPathError)?;
+    let npm = image.resolve_npm()?;
+
+    Ok(vec![
+        ("PATH", Some(path.to_string_lossy().into_owned())),
+        ("VOLTA_NODE_VERSION", Some(image.node.value.to_string())),
+        ("VOLTA_NPM_VERSION", Some(npm.value.to_string())),
+        (
+            "VOLTA_PNPM_VERSION",
+            image.pnpm.as_ref().map(|pnpm| pnpm.value.to_string()),
+        ),
+        (
+            "VOLTA_YARN_VERSION",
+            image.yarn.as_ref().map(|yarn| yarn.value.to_string()),
+        ),
+        ("VOLTA_PLATFORM_SOURCE", Some(image.node.source.to_string())),
+    ])
+}
+
+/// Determines the environment when there is no platform, which leaves running tools to the shims
+fn no_image_vars() -> Fallible<Vars> {
+    let path = remove_previous_images()?;
+
+    Ok(vec![
+        ("PATH", Some(path.to_string_lossy().into_owned())),
+        ("VOLTA_NODE_VERSION", None),
+        ("VOLTA_NPM_VERSION", None),
+        ("VOLTA_PNPM_VERSION", None),
+        ("VOLTA_YARN_VERSION", None),
+        ("VOLTA_PLATFORM_SOURCE", None),
+    ])
+}
+
+/// Removes the image directories added by a previous `volta env` from the `PATH`
+///
+/// When the hook re-evaluates the environment in a different project, the old project's versions
+/// would otherwise stay on the `PATH` behind the new ones. This updates the `PATH` of this
+/// process, so that `Image::path` builds on the cleaned-up value, and returns it.
+fn remove_previous_images() -> Fallible<OsString> {
+    let image_dir = volta_home()?.image_dir();
+    let path = env::var_os("PATH").unwrap_or_default();
+
+    let cleaned =
+        env::join_paths(env::split_paths(&path).filter(|dir| !dir.starts_with(image_dir)))
+            .with_context(|| ErrorKind::BuildPathError)?;
+
+    env::set_var("PATH", &cleaned);
+    Ok(cleaned)
+}
+
+fn format(shell: Shell, vars: &[(&'static str, Option<String>)]) -> String {
+    match shell {
+        Shell::Bash | Shell::Zsh => vars
+            .iter()
+            .map(|(name, value)| match value {
+                Some(value) => format!("export {}={};", name, quote(value)),
+                None => format!("unset {};", name),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Shell::Fish => vars
+            .iter()
+            .map(|(name, value)| match value {
+                // fish treats PATH as a list, so each directory is a separate element
+                Some(value) if *name == "PATH" => format!(
+                    "set -gx PATH {};",
+                    env::split_paths(OsStr::new(value))
+                        .map(|dir| quote(&dir.to_string_lossy()))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                ),
+                Some(value) => format!("set -gx {} {};", name, quote(value)),
+                None => format!("set -e {};", name),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Shell::Json => {
+            let object: BTreeMap<_, _> = vars.iter().cloned().collect();
+            // Serializing a map of strings can't fail
+            serde_json::to_string_pretty(&object).unwrap_or_default()
+        }
+    }
+}
+
+/// Prints the shell code to re-evaluate `volta env` each time the current directory changes
+fn hook(shell: Shell) -> Fallible<String> {
+    // The hook removes the shim directory from the `PATH`, which is where `volta` itself is often
+    // installed, so it needs to run the executable by its full path
+    let volta = quote(&volta_install()?.main_executable().to_string_lossy());
+
+    match shell {
+        Shell::Bash => Ok(format!(
+            r#"_volta_hook() {{
+  local previous_exit_status=$?
+  if [[ "${{_VOLTA_HOOK_PWD:-}}" != "$PWD" ]]; then
+    _VOLTA_HOOK_PWD="$PWD"
+    eval "$({} env --shell bash)"
+  fi
+  return $previous_exit_status
+}}
+if [[ ";${{PROMPT_COMMAND:-}};" != *";_volta_hook;"* ]]; then
+  PROMPT_COMMAND="_volta_hook${{PROMPT_COMMAND:+;$PROMPT_COMMAND}}"
+fi"#,
+            volta
+        )),
+        Shell::Zsh => Ok(format!(
+            r#"_volta_hook() {{
+  eval "$({} env --shell zsh)"
+}}
+autoload -U add-zsh-hook
+add-zsh-hook chpwd _volta_hook
+_volta_hook"#,
+            volta
+        )),
+        Shell::Fish => Ok(format!(
+            r#"function __volta_hook --on-variable PWD
+  {} env --shell fish | source
+end
+__volta_hook"#,
+            volta
+        )),
+        Shell::Json => Err(ErrorKind::NoShellHook {
+            shell: "json".into(),
+        }
+        .into()),
+    }
+}
+
+/// Quotes a value for a shell, using single quotes so that nothing in it is expanded
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote("/usr/bin"), "'/usr/bin'");
+        assert_eq!(quote("it's"), r"'it'\''s'");
+    }
+
+    #[test]
+    fn test_format_unsets_missing_versions() {
+        let vars = vec![
+            ("VOLTA_NODE_VERSION", Some("12.16.1".to_string())),
+            ("VOLTA_YARN_VERSION", None),
+        ];
+
+        assert_eq!(
+            format(Shell::Bash, &vars),
+            "export VOLTA_NODE_VERSION='12.16.1';\nunset VOLTA_YARN_VERSION;"
+        );
+        assert_eq!(
+            format(Shell::Fish, &vars),
+            "set -gx VOLTA_NODE_VERSION '12.16.1';\nset -e VOLTA_YARN_VERSION;"
+        );
+        assert_eq!(
+            format(Shell::Json, &vars),
+            "{\n  \"VOLTA_NODE_VERSION\": \"12.16.1\",\n  \"VOLTA_YARN_VERSION\": null\n}"
+        );
+    }
+}
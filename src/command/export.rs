@@ -0,0 +1,98 @@
+//! Provides the `volta export` command, which writes the default toolchain and installed packages
+//! to a file that `volta import` can reproduce on another machine.
+
+use std::fs::{read_to_string, write};
+use std::path::{Path, PathBuf};
+
+use log::info;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use structopt::StructOpt;
+
+use crate::command::Command;
+use volta_core::error::{Context, ErrorKind, ExitCode, Fallible};
+use volta_core::inventory::package_configs;
+use volta_core::session::{ActivityKind, Session};
+use volta_core::style::success_prefix;
+use volta_core::toolchain::serial::Platform;
+use volta_core::version::version_serde;
+
+#[derive(StructOpt)]
+pub(crate) struct Export {
+    /// The file to write the toolchain to. Defaults to printing it.
+    #[structopt(name = "file", parse(from_os_str))]
+    file: Option<PathBuf>,
+}
+
+/// A portable description of a default toolchain and its installed packages
+#[derive(Serialize, Deserialize)]
+pub(crate) struct ToolchainFile {
+    pub(crate) platform: Platform,
+    #[serde(default)]
+    pub(crate) packages: Vec<ExportedPackage>,
+}
+
+/// An installed package, along with the version of Node it was installed with
+#[derive(Serialize, Deserialize)]
+pub(crate) struct ExportedPackage {
+    pub(crate) name: String,
+    #[serde(with = "version_serde")]
+    pub(crate) version: Version,
+    #[serde(with = "version_serde")]
+    pub(crate) node: Version,
+}
+
+impl ToolchainFile {
+    pub(crate) fn from_file(file: &Path) -> Fallible<Self> {
+        let src = read_to_string(file).with_context(|| ErrorKind::ReadToolchainFileError {
+            file: file.to_owned(),
+        })?;
+
+        serde_json::from_str(&src).with_context(|| ErrorKind::ParseToolchainFileError {
+            file: file.to_owned(),
+        })
+    }
+}
+
+impl Command for Export {
+    fn run(self, session: &mut Session) -> Fallible<ExitCode> {
+        session.add_event_start(ActivityKind::Export);
+
+        let platform = match session.default_platform()? {
+            Some(platform) => Platform::of(platform),
+            None => Platform {
+                node: None,
+                yarn: None,
+                pnpm: None,
+            },
+        };
+        let packages = package_configs()?
+            .into_iter()
+            .map(|config| ExportedPackage {
+                name: config.name,
+                version: config.version,
+                node: config.platform.node,
+            })
+            .collect();
+
+        // Serializing these types can't fail: they contain only strings and versions
+        let src =
+            serde_json::to_string_pretty(&ToolchainFile { platform, packages }).unwrap_or_default();
+
+        match self.file {
+            Some(file) => {
+                write(&file, src)
+                    .with_context(|| ErrorKind::WriteToolchainFileError { file: file.clone() })?;
+                info!(
+                    "{} Exported your toolchain to {}",
+                    success_prefix(),
+                    file.display()
+                );
+            }
+            None => println!("{}", src),
+        }
+
+        session.add_event_end(ActivityKind::Export, ExitCode::Success);
+        Ok(ExitCode::Success)
+    }
+}
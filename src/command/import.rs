@@ -0,0 +1,256 @@
+//! Provides the `volta import` command, which reproduces a toolchain written by `volta export`.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::vec::IntoIter;
+
+use log::{info, warn};
+use semver::Version;
+use structopt::StructOpt;
+
+use super::export::{ExportedPackage, ToolchainFile};
+use crate::command::Command;
+use volta_core::error::{ExitCode, Fallible};
+use volta_core::inventory::{
+    node_versions, npm_versions, package_configs, pnpm_versions, yarn_versions,
+};
+use volta_core::platform::PlatformSpec;
+use volta_core::session::{ActivityKind, Session};
+use volta_core::style::success_prefix;
+use volta_core::tool::{package, Package, PackageDetails, Spec, Tool};
+use volta_core::version::VersionSpec;
+
+/// The number of tools that are fetched at the same time
+const FETCH_WORKERS: usize = 4;
+
+#[derive(StructOpt)]
+pub(crate) struct Import {
+    /// The toolchain file written by `volta export`
+    #[structopt(name = "file", parse(from_os_str))]
+    file: PathBuf,
+
+    /// Uninstall the packages that are not listed in the file
+    #[structopt(long = "prune")]
+    prune: bool,
+}
+
+impl Command for Import {
+    fn run(self, session: &mut Session) -> Fallible<ExitCode> {
+        session.add_event_start(ActivityKind::Import);
+
+        let toolchain = ToolchainFile::from_file(&self.file)?;
+        let platform = toolchain.platform.into_platform();
+        let installed = package_configs()?;
+
+        let packages: Vec<&ExportedPackage> = toolchain
+            .packages
+            .iter()
+            .filter(|package| {
+                !installed
+                    .iter()
+                    .any(|config| config.name == package.name && config.version == package.version)
+            })
+            .collect();
+
+        let mut fetched = fetch_all(unfetched_tools(platform.as_ref(), &packages)?);
+
+        if let Some(platform) = platform {
+            install_platform(platform, session)?;
+        }
+
+        for package in packages {
+            let details = match fetched.remove(&package.name) {
+                Some(details) => details,
+                None => {
+                    let version = VersionSpec::Exact(package.version.clone());
+                    package::resolve(&package.name, version, session)?
+                }
+            };
+            let tool = Package::new(package.name.clone(), details).with_node(package.node.clone());
+            Box::new(tool).install(session)?;
+        }
+
+        if self.prune {
+            for config in installed {
+                if !toolchain
+                    .packages
+                    .iter()
+                    .any(|package| package.name == config.name)
+                {
+                    package::uninstall(&config.name)?;
+                }
+            }
+        }
+
+        info!(
+            "{} Imported the toolchain from {}",
+            success_prefix(),
+            self.file.display()
+        );
+
+        session.add_event_end(ActivityKind::Import, ExitCode::Success);
+        Ok(ExitCode::Success)
+    }
+}
+
+/// Determines the tools that haven't been fetched yet, given the packages that aren't installed
+fn unfetched_tools(
+    platform: Option<&PlatformSpec>,
+    packages: &[&ExportedPackage],
+) -> Fallible<Vec<Spec>> {
+    let mut nodes: BTreeSet<Version> = packages
+        .iter()
+        .map(|package| package.node.clone())
+        .collect();
+    let mut specs = Vec::new();
+
+    if let Some(platform) = platform {
+        nodes.insert(platform.node.clone());
+        push_unfetched(
+            &mut specs,
+            platform.npm.as_ref(),
+            &npm_versions()?,
+            Spec::Npm,
+        );
+        push_unfetched(
+            &mut specs,
+            platform.pnpm.as_ref(),
+            &pnpm_versions()?,
+            Spec::Pnpm,
+        );
+        push_unfetched(
+            &mut specs,
+            platform.yarn.as_ref(),
+            &yarn_versions()?,
+            Spec::Yarn,
+        );
+    }
+
+    let fetched = node_versions()?;
+    for node in nodes {
+        push_unfetched(&mut specs, Some(&node), &fetched, Spec::Node);
+    }
+
+    specs.extend(packages.iter().map(|package| {
+        Spec::Package(
+            package.name.clone(),
+            VersionSpec::Exact(package.version.clone()),
+        )
+    }));
+
+    Ok(specs)
+}
+
+/// Adds a spec for the version, if there is one and it hasn't been fetched
+fn push_unfetched(
+    specs: &mut Vec<Spec>,
+    version: Option<&Version>,
+    fetched: &BTreeSet<Version>,
+    spec: fn(VersionSpec) -> Spec,
+) {
+    if let Some(version) = version {
+        if !fetched.contains(version) {
+            specs.push(spec(VersionSpec::Exact(version.clone())));
+        }
+    }
+}
+
+/// Fetches the tools in parallel, using a few workers that each have their own session
+///
+/// Returns the registry details of the packages that were fetched, so that they don't need to be
+/// resolved again to install them. A tool that fails to fetch here is fetched again when it is
+/// installed, which reports the error.
+fn fetch_all(specs: Vec<Spec>) -> HashMap<String, PackageDetails> {
+    let count = specs.len().min(FETCH_WORKERS);
+    let queue = Arc::new(Mutex::new(specs.into_iter()));
+
+    let workers: Vec<_> = (0..count)
+        .map(|_| {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || {
+                let mut session = Session::init();
+                let mut fetched = Vec::new();
+
+                while let Some(spec) = next_spec(&queue) {
+                    let display = spec.to_string();
+                    match fetch(spec, &mut session) {
+                        Ok(Some(package)) => fetched.push(package),
+                        Ok(None) => {}
+                        Err(error) => warn!("Could not fetch {}: {}", display, error),
+                    }
+                }
+
+                fetched
+            })
+        })
+        .collect();
+
+    workers
+        .into_iter()
+        .filter_map(|worker| worker.join().ok())
+        .flatten()
+        .collect()
+}
+
+/// Takes the next tool from the queue, so that the queue is only locked while taking it and
+/// not while fetching
+fn next_spec(queue: &Mutex<IntoIter<Spec>>) -> Option<Spec> {
+    queue.lock().ok().and_then(|mut specs| specs.next())
+}
+
+/// Fetches a tool, returning the registry details if it is a package
+fn fetch(spec: Spec, session: &mut Session) -> Fallible<Option<(String, PackageDetails)>> {
+    match spec {
+        Spec::Package(name, version) => {
+            let details = package::resolve(&name, version, session)?;
+            Box::new(Package::new(name.clone(), details.clone())).fetch(session)?;
+            Ok(Some((name, details)))
+        }
+        spec => {
+            spec.resolve(session)?.fetch(session)?;
+            Ok(None)
+        }
+    }
+}
+
+/// Makes the default platform match the imported one, installing only the tools that differ
+fn install_platform(platform: PlatformSpec, session: &mut Session) -> Fallible<()> {
+    let current = session.default_platform()?.cloned();
+    let current = current.as_ref();
+
+    if current.map(|current| &current.node) != Some(&platform.node) {
+        install(Spec::Node(VersionSpec::Exact(platform.node)), session)?;
+    }
+
+    match platform.npm {
+        Some(npm) if current.and_then(|current| current.npm.as_ref()) != Some(&npm) => {
+            install(Spec::Npm(VersionSpec::Exact(npm)), session)?
+        }
+        Some(_) => {}
+        None => session.toolchain_mut()?.set_active_npm(None)?,
+    }
+
+    match platform.pnpm {
+        Some(pnpm) if current.and_then(|current| current.pnpm.as_ref()) != Some(&pnpm) => {
+            install(Spec::Pnpm(VersionSpec::Exact(pnpm)), session)?
+        }
+        Some(_) => {}
+        None => session.toolchain_mut()?.set_active_pnpm(None)?,
+    }
+
+    match platform.yarn {
+        Some(yarn) if current.and_then(|current| current.yarn.as_ref()) != Some(&yarn) => {
+            install(Spec::Yarn(VersionSpec::Exact(yarn)), session)?
+        }
+        Some(_) => {}
+        None => session.toolchain_mut()?.set_active_yarn(None)?,
+    }
+
+    Ok(())
+}
+
+fn install(spec: Spec, session: &mut Session) -> Fallible<()> {
+    spec.resolve(session)?.install(session)
+}
@@ -311,6 +311,7 @@ fn format_package_manager(package_manager: &PackageManager) -> String {
 fn format_package_manager_kind(kind: PackageManagerKind) -> String {
     match kind {
         PackageManagerKind::Npm => "npm".into(),
+        PackageManagerKind::Pnpm => "pnpm".into(),
         PackageManagerKind::Yarn => "Yarn".into(),
     }
 }
@@ -935,6 +936,19 @@ See `volta help install` for details and more options.";
             );
         }
 
+        #[test]
+        fn none_installed_pnpm() {
+            let expected = "⚡️ No pnpm versions installed.
+
+You can install a pnpm version by running `volta install pnpm`.
+See `volta help install` for details and more options.";
+
+            assert_eq!(
+                display_package_managers(PackageManagerKind::Pnpm, &[]),
+                expected
+            );
+        }
+
         #[test]
         fn none_installed_yarn() {
             let expected = "⚡️ No Yarn versions installed.
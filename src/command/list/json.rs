@@ -0,0 +1,279 @@
+//! Define the "json" format style for list commands.
+
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::{Node, Package, PackageManager, Source, Toolchain};
+
+/// The version of the JSON output schema
+///
+/// This should be incremented whenever a change to the output would break an existing consumer,
+/// such as removing or renaming a field. Adding fields does not require a new version.
+const SCHEMA_VERSION: u32 = 1;
+
+pub(super) fn format(toolchain: &Toolchain) -> Option<String> {
+    let (runtimes, package_managers, packages): (&[Node], &[PackageManager], &[Package]) =
+        match toolchain {
+            Toolchain::Node(runtimes) => (runtimes, &[], &[]),
+            Toolchain::PackageManagers { managers, .. } => (&[], managers, &[]),
+            Toolchain::Packages(packages) => (&[], &[], packages),
+            Toolchain::Tool { host_packages, .. } => (&[], &[], host_packages),
+            Toolchain::Active {
+                runtime,
+                package_managers,
+                packages,
+            } => (
+                runtime.as_ref().map_or(&[], |r| std::slice::from_ref(&**r)),
+                package_managers,
+                packages,
+            ),
+            Toolchain::All {
+                runtimes,
+                package_managers,
+                packages,
+            } => (runtimes, package_managers, packages),
+        };
+
+    let output = JsonToolchain {
+        schema_version: SCHEMA_VERSION,
+        runtimes: runtimes.iter().map(JsonRuntime::from).collect(),
+        package_managers: package_managers
+            .iter()
+            .map(JsonPackageManager::from)
+            .collect(),
+        packages: packages.iter().map(JsonPackage::from).collect(),
+    };
+
+    // Serializing these types can't fail: they contain only strings, numbers, and sequences
+    serde_json::to_string_pretty(&output).ok()
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonToolchain<'a> {
+    schema_version: u32,
+    runtimes: Vec<JsonRuntime<'a>>,
+    package_managers: Vec<JsonPackageManager<'a>>,
+    packages: Vec<JsonPackage<'a>>,
+}
+
+/// The source of an item, tagged with its kind so that consumers can match on it
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum JsonSource<'a> {
+    Project { path: &'a Path },
+    Default,
+    Fetched,
+}
+
+impl<'a> From<&'a Source> for JsonSource<'a> {
+    fn from(source: &'a Source) -> Self {
+        match source {
+            Source::Project(path) => JsonSource::Project { path },
+            Source::Default => JsonSource::Default,
+            Source::None => JsonSource::Fetched,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonRuntime<'a> {
+    name: &'static str,
+    version: String,
+    source: JsonSource<'a>,
+}
+
+impl<'a> From<&'a Node> for JsonRuntime<'a> {
+    fn from(node: &'a Node) -> Self {
+        JsonRuntime {
+            name: "node",
+            version: node.version.to_string(),
+            source: (&node.source).into(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonPackageManager<'a> {
+    name: String,
+    version: String,
+    source: JsonSource<'a>,
+}
+
+impl<'a> From<&'a PackageManager> for JsonPackageManager<'a> {
+    fn from(package_manager: &'a PackageManager) -> Self {
+        JsonPackageManager {
+            name: package_manager.kind.to_string(),
+            version: package_manager.version.to_string(),
+            source: (&package_manager.source).into(),
+        }
+    }
+}
+
+/// A package entry
+///
+/// Packages from a project are managed by the project itself, so they have no version or Node
+/// platform of their own, and fetched packages have no bins until they are installed.
+#[derive(Serialize)]
+struct JsonPackage<'a> {
+    name: &'a str,
+    version: Option<String>,
+    source: JsonSource<'a>,
+    node: Option<String>,
+    bins: &'a [String],
+}
+
+impl<'a> From<&'a Package> for JsonPackage<'a> {
+    fn from(package: &'a Package) -> Self {
+        match package {
+            Package::Default {
+                details,
+                node,
+                tools,
+            } => JsonPackage {
+                name: &details.name,
+                version: Some(details.version.to_string()),
+                source: JsonSource::Default,
+                node: Some(node.to_string()),
+                bins: tools,
+            },
+            Package::Project { name, tools, path } => JsonPackage {
+                name,
+                version: None,
+                source: JsonSource::Project { path },
+                node: None,
+                bins: tools,
+            },
+            Package::Fetched(details) => JsonPackage {
+                name: &details.name,
+                version: Some(details.version.to_string()),
+                source: JsonSource::Fetched,
+                node: None,
+                bins: &[],
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use lazy_static::lazy_static;
+    use semver::Version;
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::command::list::{PackageDetails, PackageManagerKind};
+
+    lazy_static! {
+        static ref NODE_VERSION: Version = Version::from((12, 4, 0));
+        static ref TYPESCRIPT_VERSION: Version = Version::from((3, 4, 1));
+        static ref YARN_VERSION: Version = Version::from((1, 16, 0));
+        static ref PROJECT_PATH: PathBuf = PathBuf::from("/a/b/c");
+    }
+
+    fn parse(toolchain: &Toolchain) -> Value {
+        let output = format(toolchain).expect("`format` always returns `Some`");
+        serde_json::from_str(&output).expect("output is valid JSON")
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(
+            parse(&Toolchain::Node(vec![])),
+            json!({
+                "schemaVersion": 1,
+                "runtimes": [],
+                "packageManagers": [],
+                "packages": []
+            })
+        );
+    }
+
+    #[test]
+    fn active() {
+        assert_eq!(
+            parse(&Toolchain::Active {
+                runtime: Some(Box::new(Node {
+                    source: Source::Project(PROJECT_PATH.clone()),
+                    version: NODE_VERSION.clone(),
+                })),
+                package_managers: vec![PackageManager {
+                    kind: PackageManagerKind::Yarn,
+                    source: Source::Default,
+                    version: YARN_VERSION.clone(),
+                }],
+                packages: vec![
+                    Package::Project {
+                        name: "ember-cli".into(),
+                        path: PROJECT_PATH.clone(),
+                        tools: vec!["ember".into()],
+                    },
+                    Package::Default {
+                        details: PackageDetails {
+                            name: "typescript".into(),
+                            version: TYPESCRIPT_VERSION.clone(),
+                        },
+                        node: NODE_VERSION.clone(),
+                        tools: vec!["tsc".into(), "tsserver".into()],
+                    },
+                ],
+            }),
+            json!({
+                "schemaVersion": 1,
+                "runtimes": [{
+                    "name": "node",
+                    "version": "12.4.0",
+                    "source": { "type": "project", "path": "/a/b/c" }
+                }],
+                "packageManagers": [{
+                    "name": "yarn",
+                    "version": "1.16.0",
+                    "source": { "type": "default" }
+                }],
+                "packages": [
+                    {
+                        "name": "ember-cli",
+                        "version": null,
+                        "source": { "type": "project", "path": "/a/b/c" },
+                        "node": null,
+                        "bins": ["ember"]
+                    },
+                    {
+                        "name": "typescript",
+                        "version": "3.4.1",
+                        "source": { "type": "default" },
+                        "node": "12.4.0",
+                        "bins": ["tsc", "tsserver"]
+                    }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn fetched() {
+        assert_eq!(
+            parse(&Toolchain::Packages(vec![Package::Fetched(
+                PackageDetails {
+                    name: "typescript".into(),
+                    version: TYPESCRIPT_VERSION.clone(),
+                }
+            )])),
+            json!({
+                "schemaVersion": 1,
+                "runtimes": [],
+                "packageManagers": [],
+                "packages": [{
+                    "name": "typescript",
+                    "version": "3.4.1",
+                    "source": { "type": "fetched" },
+                    "node": null,
+                    "bins": []
+                }]
+            })
+        );
+    }
+}
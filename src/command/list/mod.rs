@@ -1,4 +1,5 @@
 mod human;
+mod json;
 mod plain;
 mod toolchain;
 
@@ -18,6 +19,7 @@ use volta_core::tool::PackageConfig;
 #[derive(Copy, Clone, PartialEq)]
 enum Format {
     Human,
+    Json,
     Plain,
 }
 
@@ -27,6 +29,7 @@ impl FromStr for Format {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "human" => Ok(Format::Human),
+            "json" => Ok(Format::Json),
             "plain" => Ok(Format::Plain),
             _ => Err("No".into()),
         }
@@ -41,7 +44,8 @@ impl FromStr for Format {
 #[derive(Clone, PartialEq, Debug)]
 enum Source {
     /// The item is from a project. The wrapped `PathBuf` is the path to the
-    /// project's `package.json`.
+    /// project's `package.json`, or to the `.node-version` or `.nvmrc` file
+    /// that the project's Node version was read from.
     Project(PathBuf),
 
     /// The item is the user's default.
@@ -157,6 +161,7 @@ struct Node {
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 enum PackageManagerKind {
     Npm,
+    Pnpm,
     Yarn,
 }
 
@@ -167,6 +172,7 @@ impl fmt::Display for PackageManagerKind {
             "{}",
             match self {
                 PackageManagerKind::Npm => "npm",
+                PackageManagerKind::Pnpm => "pnpm",
                 PackageManagerKind::Yarn => "yarn",
             }
         )
@@ -204,14 +210,18 @@ pub(crate) struct List {
     // `Option<Subcommand>` with `impl FromStr for Subcommand` for `StructOpt`
     // because StructOpt does not currently support custom parsing for enum
     // variants (as detailed in commit 5f9214ae).
-    /// The tool to lookup: `all`, `node`, `yarn`, or the name of a package or binary.
+    /// The tool to lookup: `all`, `node`, `npm`, `pnpm`, `yarn`, or the name of a package or binary.
     #[structopt(name = "tool")]
     subcommand: Option<String>,
 
     /// Specify the output format.
     ///
-    /// Defaults to `human` for TTYs, `plain` otherwise.
-    #[structopt(long = "format", raw(possible_values = r#"&["human", "plain"]"#))]
+    /// Defaults to `human` for TTYs, `plain` otherwise. The `json` format is intended for scripts,
+    /// and includes a `schemaVersion` that changes only when the output changes incompatibly.
+    #[structopt(
+        long = "format",
+        raw(possible_values = r#"&["human", "json", "plain"]"#)
+    )]
     format: Option<Format>,
 
     /// Show the currently-active tool(s).
@@ -236,6 +246,9 @@ enum Subcommand {
     /// Show locally cached npm versions.
     Npm,
 
+    /// Show locally cached pnpm versions.
+    Pnpm,
+
     /// Show locally cached Yarn versions.
     Yarn,
 
@@ -249,6 +262,7 @@ impl From<&str> for Subcommand {
             "all" => Subcommand::All,
             "node" => Subcommand::Node,
             "npm" => Subcommand::Npm,
+            "pnpm" => Subcommand::Pnpm,
             "yarn" => Subcommand::Yarn,
             s => Subcommand::PackageOrTool { name: s.into() },
         }
@@ -278,9 +292,14 @@ impl Command for List {
         session.add_event_start(ActivityKind::List);
 
         let project = session.project()?;
+        if let Some(project) = project {
+            project.check_engines();
+        }
+
         let default_platform = session.default_platform()?;
         let format = match self.output_format() {
             Format::Human => human::format,
+            Format::Json => json::format,
             Format::Plain => plain::format,
         };
 
@@ -297,6 +316,7 @@ impl Command for List {
             Some(Subcommand::All) => Toolchain::all(project, default_platform)?,
             Some(Subcommand::Node) => Toolchain::node(project, default_platform, &filter)?,
             Some(Subcommand::Npm) => Toolchain::npm(project, default_platform, &filter)?,
+            Some(Subcommand::Pnpm) => Toolchain::pnpm(project, default_platform, &filter)?,
             Some(Subcommand::Yarn) => Toolchain::yarn(project, default_platform, &filter)?,
             Some(Subcommand::PackageOrTool { name }) => {
                 Toolchain::package_or_tool(&name, project, &filter)?
@@ -212,6 +212,7 @@ mod tests {
         static ref NODE_VERSION: Version = Version::from((12, 4, 0));
         static ref TYPESCRIPT_VERSION: Version = Version::from((3, 4, 1));
         static ref NPM_VERSION: Version = Version::from((6, 13, 4));
+        static ref PNPM_VERSION: Version = Version::from((5, 18, 9));
         static ref YARN_VERSION: Version = Version::from((1, 16, 0));
         static ref PROJECT_PATH: PathBuf = PathBuf::from("/a/b/c");
     }
@@ -293,6 +294,38 @@ mod tests {
         }
     }
 
+    mod pnpm {
+        use super::super::*;
+        use super::*;
+        use crate::command::list::*;
+
+        #[test]
+        fn default() {
+            assert_eq!(
+                display_package_manager(&PackageManager {
+                    kind: PackageManagerKind::Pnpm,
+                    source: Source::Default,
+                    version: PNPM_VERSION.clone(),
+                })
+                .as_str(),
+                "package-manager pnpm@5.18.9 (default)"
+            );
+        }
+
+        #[test]
+        fn project() {
+            assert_eq!(
+                display_package_manager(&PackageManager {
+                    kind: PackageManagerKind::Pnpm,
+                    source: Source::Project(PROJECT_PATH.clone()),
+                    version: PNPM_VERSION.clone(),
+                })
+                .as_str(),
+                "package-manager pnpm@5.18.9 (current @ /a/b/c)"
+            );
+        }
+    }
+
     mod yarn {
         use super::super::*;
         use super::*;
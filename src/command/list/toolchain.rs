@@ -1,8 +1,12 @@
+use std::path::PathBuf;
+
 use super::{Filter, Node, Package, PackageManager, Source};
 use crate::command::list::PackageManagerKind;
 use semver::Version;
 use volta_core::error::Fallible;
-use volta_core::inventory::{node_versions, npm_versions, package_configs, yarn_versions};
+use volta_core::inventory::{
+    node_versions, npm_versions, package_configs, pnpm_versions, yarn_versions,
+};
 use volta_core::platform::PlatformSpec;
 use volta_core::project::Project;
 use volta_core::tool::PackageConfig;
@@ -36,6 +40,8 @@ enum Lookup {
     Runtime,
     /// Look up the npm package manager
     Npm,
+    /// Look up the pnpm package manager
+    Pnpm,
     /// Look up the Yarn package manager
     Yarn,
 }
@@ -45,10 +51,19 @@ impl Lookup {
         move |spec| match self {
             Lookup::Runtime => Some(spec.node.clone()),
             Lookup::Npm => spec.npm.clone(),
+            Lookup::Pnpm => spec.pnpm.clone(),
             Lookup::Yarn => spec.yarn.clone(),
         }
     }
 
+    /// The file in a project that the version of this kind of tool comes from
+    fn project_file(&self, project: &Project) -> PathBuf {
+        match self {
+            Lookup::Runtime => project.node_version_source().to_owned(),
+            Lookup::Npm | Lookup::Pnpm | Lookup::Yarn => project.manifest_file().to_owned(),
+        }
+    }
+
     fn version_source(
         self,
         project: Option<&Project>,
@@ -56,16 +71,19 @@ impl Lookup {
         version: &Version,
     ) -> Source {
         match project {
-            Some(project) => project
-                .platform()
-                .and_then(self.version_from_spec())
-                .and_then(|project_version| {
-                    if &project_version == version {
-                        Some(Source::Project(project.manifest_file().to_owned()))
-                    } else {
-                        None
-                    }
-                }),
+            Some(project) => {
+                let file = self.project_file(project);
+                project
+                    .platform()
+                    .and_then(self.version_from_spec())
+                    .and_then(|project_version| {
+                        if &project_version == version {
+                            Some(Source::Project(file))
+                        } else {
+                            None
+                        }
+                    })
+            }
             None => default_platform
                 .clone()
                 .and_then(self.version_from_spec())
@@ -87,10 +105,13 @@ impl Lookup {
         default: Option<&PlatformSpec>,
     ) -> Option<(Source, Version)> {
         match project {
-            Some(project) => project
-                .platform()
-                .and_then(self.version_from_spec())
-                .map(|version| (Source::Project(project.manifest_file().to_owned()), version)),
+            Some(project) => {
+                let file = self.project_file(project);
+                project
+                    .platform()
+                    .and_then(self.version_from_spec())
+                    .map(|version| (Source::Project(file), version))
+            }
             None => default
                 .clone()
                 .and_then(self.version_from_spec())
@@ -131,6 +152,13 @@ impl Toolchain {
                     version,
                 })
                 .into_iter()
+                .chain(Lookup::Pnpm.active_tool(project, default_platform).map(
+                    |(source, version)| PackageManager {
+                        kind: PackageManagerKind::Pnpm,
+                        source,
+                        version,
+                    },
+                ))
                 .chain(Lookup::Yarn.active_tool(project, default_platform).map(
                     |(source, version)| PackageManager {
                         kind: PackageManagerKind::Yarn,
@@ -168,6 +196,11 @@ impl Toolchain {
                 source: Lookup::Npm.version_source(project, default_platform, version),
                 version: version.clone(),
             })
+            .chain(pnpm_versions()?.iter().map(|version| PackageManager {
+                kind: PackageManagerKind::Pnpm,
+                source: Lookup::Pnpm.version_source(project, default_platform, version),
+                version: version.clone(),
+            }))
             .chain(yarn_versions()?.iter().map(|version| PackageManager {
                 kind: PackageManagerKind::Yarn,
                 source: Lookup::Yarn.version_source(project, default_platform, version),
@@ -232,6 +265,33 @@ impl Toolchain {
         })
     }
 
+    pub(super) fn pnpm(
+        project: Option<&Project>,
+        default_platform: Option<&PlatformSpec>,
+        filter: &Filter,
+    ) -> Fallible<Toolchain> {
+        let managers = pnpm_versions()?
+            .iter()
+            .filter_map(|version| {
+                let source = Lookup::Pnpm.version_source(project, default_platform, version);
+                if source.allowed_with(filter) {
+                    Some(PackageManager {
+                        kind: PackageManagerKind::Pnpm,
+                        source,
+                        version: version.clone(),
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        Ok(Toolchain::PackageManagers {
+            kind: PackageManagerKind::Pnpm,
+            managers,
+        })
+    }
+
     pub(super) fn yarn(
         project: Option<&Project>,
         default_platform: Option<&PlatformSpec>,
@@ -0,0 +1,261 @@
+//! Provides the `volta list-remote` command, which lists the versions of a tool that are available
+//! to fetch, so that a version can be chosen without looking it up elsewhere.
+
+use std::path::Path;
+use std::str::FromStr;
+
+use semver::Version;
+use serde::Serialize;
+use structopt::StructOpt;
+
+use crate::command::Command;
+use volta_core::error::{ErrorKind, ExitCode, Fallible};
+use volta_core::inventory::{
+    node_versions, npm_versions, package_configs, package_versions, pnpm_versions, yarn_versions,
+};
+use volta_core::platform::PlatformSpec;
+use volta_core::project::Project;
+use volta_core::session::{ActivityKind, Session};
+use volta_core::style::{note_prefix, tool_version};
+use volta_core::tool::{node, npm, package, pnpm, yarn, RemoteVersion};
+use volta_core::version::parse_requirements;
+
+/// The version of the JSON output schema, which follows the same rules as `volta list`
+const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Copy, Clone, PartialEq)]
+enum Format {
+    Human,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Format::Human),
+            "json" => Ok(Format::Json),
+            _ => Err("No".into()),
+        }
+    }
+}
+
+#[derive(StructOpt)]
+pub(crate) struct ListRemote {
+    /// The tool to look up: `node`, `npm`, `pnpm`, `yarn`, or the name of a package
+    #[structopt(name = "tool")]
+    tool: String,
+
+    /// Only show the versions matching a semver range, like `^14` or `>=1.22 <2`
+    #[structopt(name = "range")]
+    range: Option<String>,
+
+    /// Only show the long-term support releases of Node
+    #[structopt(long = "lts")]
+    lts: bool,
+
+    /// Specify the output format
+    #[structopt(long = "format", raw(possible_values = r#"&["human", "json"]"#))]
+    format: Option<Format>,
+}
+
+/// A version available to fetch, along with how it is used locally
+struct Listed {
+    remote: RemoteVersion,
+    /// Whether the version is already in the local inventory
+    fetched: bool,
+    /// Whether the version is the user's default
+    default: bool,
+}
+
+impl Command for ListRemote {
+    fn run(self, session: &mut Session) -> Fallible<ExitCode> {
+        session.add_event_start(ActivityKind::ListRemote);
+
+        if self.lts && self.tool != "node" {
+            return Err(ErrorKind::LtsOnlyForNode { tool: self.tool }.into());
+        }
+
+        let range = self.range.as_ref().map(parse_requirements).transpose()?;
+        let (remote, fetched, local) = match self.tool.as_str() {
+            "node" => (
+                node::remote_versions(session)?,
+                node_versions()?,
+                Local::Platform(|spec: &PlatformSpec| Some(spec.node.clone())),
+            ),
+            "npm" => (
+                npm::remote_versions(session)?,
+                npm_versions()?,
+                Local::Platform(|spec: &PlatformSpec| spec.npm.clone()),
+            ),
+            "pnpm" => (
+                pnpm::remote_versions(session)?,
+                pnpm_versions()?,
+                Local::Platform(|spec: &PlatformSpec| spec.pnpm.clone()),
+            ),
+            "yarn" => (
+                yarn::remote_versions(session)?,
+                yarn_versions()?,
+                Local::Platform(|spec: &PlatformSpec| spec.yarn.clone()),
+            ),
+            name => (
+                package::remote_versions(name, session)?,
+                package_versions()?.remove(name).unwrap_or_default(),
+                Local::Package(default_package_version(name)?),
+            ),
+        };
+
+        let project = session.project()?;
+        let default = local.default_version(session.default_platform()?);
+        let pinned = local.pinned_version(project);
+
+        let listed: Vec<Listed> = remote
+            .into_iter()
+            .filter(|remote| !self.lts || remote.lts)
+            .filter(|remote| {
+                range
+                    .as_ref()
+                    .map_or(true, |req| req.matches(&remote.version))
+            })
+            .map(|remote| Listed {
+                fetched: fetched.contains(&remote.version),
+                default: default.as_ref() == Some(&remote.version),
+                remote,
+            })
+            .collect();
+
+        // Node can be pinned by `.node-version` or `.nvmrc`, everything else only by `package.json`
+        let pinned_file = project.map(|project| match self.tool.as_str() {
+            "node" => project.node_version_source(),
+            _ => project.manifest_file(),
+        });
+        let output = match self.format.unwrap_or(Format::Human) {
+            Format::Human => human(&self.tool, &listed, pinned.as_ref(), pinned_file),
+            Format::Json => json(&self.tool, &listed, pinned.as_ref(), pinned_file),
+        };
+        println!("{}", output);
+
+        session.add_event_end(ActivityKind::ListRemote, ExitCode::Success);
+        Ok(ExitCode::Success)
+    }
+}
+
+/// Where the local default and pinned versions of the tool come from
+enum Local {
+    /// The runtime and package managers are part of the default and project platforms
+    Platform(fn(&PlatformSpec) -> Option<Version>),
+    /// Packages have a single default version, from their global install, and aren't pinned
+    Package(Option<Version>),
+}
+
+impl Local {
+    fn default_version(&self, platform: Option<&PlatformSpec>) -> Option<Version> {
+        match self {
+            Local::Platform(version) => platform.and_then(version),
+            Local::Package(version) => version.clone(),
+        }
+    }
+
+    fn pinned_version(&self, project: Option<&Project>) -> Option<Version> {
+        match self {
+            Local::Platform(version) => project.and_then(Project::platform).and_then(version),
+            Local::Package(_) => None,
+        }
+    }
+}
+
+/// The version of a package that is installed globally, if any
+fn default_package_version(name: &str) -> Fallible<Option<Version>> {
+    Ok(package_configs()?
+        .into_iter()
+        .find(|config| config.name == name)
+        .map(|config| config.version))
+}
+
+fn human(
+    tool: &str,
+    listed: &[Listed],
+    pinned: Option<&Version>,
+    pinned_file: Option<&Path>,
+) -> String {
+    if listed.is_empty() {
+        return format!("{} No matching versions of {} found", note_prefix(), tool);
+    }
+
+    let lines: Vec<String> = listed
+        .iter()
+        .map(|item| {
+            let mut line = format!("    {}", tool_version(tool, &item.remote.version));
+            if !item.remote.tags.is_empty() {
+                line.push_str(&format!(" ({})", item.remote.tags.join(", ")));
+            }
+
+            let mut marks = Vec::new();
+            if item.fetched {
+                marks.push("fetched".to_string());
+            }
+            if item.default {
+                marks.push("default".to_string());
+            }
+            if let (Some(version), Some(file)) = (pinned, pinned_file) {
+                if *version == item.remote.version {
+                    marks.push(format!("current @ {}", file.display()));
+                }
+            }
+            if !marks.is_empty() {
+                line.push_str(&format!(" [{}]", marks.join(", ")));
+            }
+
+            line
+        })
+        .collect();
+
+    format!("Available versions of {}:\n{}", tool, lines.join("\n"))
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonOutput<'a> {
+    schema_version: u32,
+    tool: &'a str,
+    versions: Vec<JsonVersion<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonVersion<'a> {
+    version: String,
+    tags: &'a [String],
+    lts: bool,
+    fetched: bool,
+    default: bool,
+    /// The file that pins this version in the current project, or `null` if it isn't pinned
+    pinned: Option<&'a Path>,
+}
+
+fn json(
+    tool: &str,
+    listed: &[Listed],
+    pinned: Option<&Version>,
+    pinned_file: Option<&Path>,
+) -> String {
+    let output = JsonOutput {
+        schema_version: SCHEMA_VERSION,
+        tool,
+        versions: listed
+            .iter()
+            .map(|item| JsonVersion {
+                version: item.remote.version.to_string(),
+                tags: &item.remote.tags,
+                lts: item.remote.lts,
+                fetched: item.fetched,
+                default: item.default,
+                pinned: pinned_file.filter(|_| pinned == Some(&item.remote.version)),
+            })
+            .collect(),
+    };
+
+    // Serializing these types can't fail: they contain only strings, booleans, and sequences
+    serde_json::to_string_pretty(&output).unwrap_or_default()
+}
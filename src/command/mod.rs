@@ -1,24 +1,42 @@
 pub(crate) mod completions;
+pub(crate) mod doctor;
+pub(crate) mod env;
+pub(crate) mod export;
 pub(crate) mod fetch;
+pub(crate) mod import;
 pub(crate) mod install;
 pub(crate) mod list;
+pub(crate) mod list_remote;
+pub(crate) mod outdated;
 pub(crate) mod pin;
+pub(crate) mod prune;
 pub(crate) mod run;
 pub(crate) mod setup;
+pub(crate) mod trust;
 pub(crate) mod uninstall;
+pub(crate) mod upgrade;
 pub(crate) mod r#use;
 pub(crate) mod which;
 
 pub(crate) use self::which::Which;
 pub(crate) use completions::Completions;
+pub(crate) use doctor::Doctor;
+pub(crate) use env::Env;
+pub(crate) use export::Export;
 pub(crate) use fetch::Fetch;
+pub(crate) use import::Import;
 pub(crate) use install::Install;
 pub(crate) use list::List;
+pub(crate) use list_remote::ListRemote;
+pub(crate) use outdated::Outdated;
 pub(crate) use pin::Pin;
+pub(crate) use prune::Prune;
 pub(crate) use r#use::Use;
 pub(crate) use run::Run;
 pub(crate) use setup::Setup;
+pub(crate) use trust::Trust;
 pub(crate) use uninstall::Uninstall;
+pub(crate) use upgrade::Upgrade;
 
 use volta_core::error::{ExitCode, Fallible};
 use volta_core::session::Session;
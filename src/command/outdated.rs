@@ -0,0 +1,251 @@
+//! Provides the `volta outdated` command, which compares the default toolchain and installed
+//! packages against the newest versions available to them.
+
+use log::{info, warn};
+use semver::Version;
+use structopt::StructOpt;
+
+use crate::command::Command;
+use volta_core::error::{ExitCode, Fallible};
+use volta_core::inventory::package_configs;
+use volta_core::session::{ActivityKind, Session};
+use volta_core::style::{success_prefix, tool_version};
+use volta_core::tool::{node, npm, package, pnpm, yarn, PackageDetails, Spec};
+use volta_core::version::{parse_requirements, VersionSpec, VersionTag};
+
+#[derive(StructOpt)]
+pub(crate) struct Outdated {
+    /// Tools to check, like `node`, `typescript` or `yarn@^2`. Defaults to your whole default
+    /// toolchain and every installed package.
+    ///
+    /// Without a version, each tool is compared against the newest release with the same major
+    /// version, or the newest LTS release with the same major version for Node.
+    #[structopt(name = "tool[@version]")]
+    tools: Vec<String>,
+}
+
+impl Command for Outdated {
+    fn run(self, session: &mut Session) -> Fallible<ExitCode> {
+        session.add_event_start(ActivityKind::Outdated);
+
+        let updates = find_updates(&self.tools, "outdated", session)?;
+        if updates.is_empty() {
+            info!("{} Everything is up to date", success_prefix());
+        } else {
+            for update in &updates {
+                println!(
+                    "{} -> {}",
+                    tool_version(update.installed.name(), update.installed.version()),
+                    update.latest.version()
+                );
+            }
+        }
+
+        session.add_event_end(ActivityKind::Outdated, ExitCode::Success);
+        Ok(ExitCode::Success)
+    }
+}
+
+/// A tool in the default toolchain, or an installed package
+pub(crate) enum Installed {
+    Node(Version),
+    Npm(Version),
+    Pnpm(Version),
+    Yarn(Version),
+    Package {
+        name: String,
+        version: Version,
+        /// The version of Node that the package was installed with
+        node: Version,
+    },
+}
+
+impl Installed {
+    pub(crate) fn name(&self) -> &str {
+        match self {
+            Installed::Node(_) => "node",
+            Installed::Npm(_) => "npm",
+            Installed::Pnpm(_) => "pnpm",
+            Installed::Yarn(_) => "yarn",
+            Installed::Package { name, .. } => name,
+        }
+    }
+
+    pub(crate) fn version(&self) -> &Version {
+        match self {
+            Installed::Node(version)
+            | Installed::Npm(version)
+            | Installed::Pnpm(version)
+            | Installed::Yarn(version)
+            | Installed::Package { version, .. } => version,
+        }
+    }
+
+    /// The versions this can be updated to when no version is requested: those with the same
+    /// major version, since a new major version is likely to break something
+    fn default_spec(&self) -> Fallible<VersionSpec> {
+        let major = self.version().major;
+        let same_major = parse_requirements(format!(">={}.0.0 <{}.0.0", major, major + 1))?;
+
+        Ok(match self {
+            Installed::Node(_) => VersionSpec::Tag(VersionTag::LtsRequirement(same_major)),
+            _ => VersionSpec::Semver(same_major),
+        })
+    }
+}
+
+/// An installed tool that has a newer version available
+pub(crate) struct Update {
+    pub(crate) installed: Installed,
+    pub(crate) latest: Latest,
+}
+
+/// The newer version of an installed tool
+pub(crate) enum Latest {
+    Node(Version),
+    Npm(Version),
+    Pnpm(Version),
+    Yarn(Version),
+    Package {
+        /// The registry details of the newer version
+        details: PackageDetails,
+        /// The version of Node that the package was installed with
+        node: Version,
+    },
+}
+
+impl Latest {
+    pub(crate) fn version(&self) -> &Version {
+        match self {
+            Latest::Node(version)
+            | Latest::Npm(version)
+            | Latest::Pnpm(version)
+            | Latest::Yarn(version) => version,
+            Latest::Package { details, .. } => details.version(),
+        }
+    }
+}
+
+/// Finds the tools with newer versions available, either all of them or the ones requested
+pub(crate) fn find_updates(
+    tools: &[String],
+    action: &str,
+    session: &mut Session,
+) -> Fallible<Vec<Update>> {
+    let installed = installed(session)?;
+    let requested = if tools.is_empty() {
+        installed.into_iter().map(|item| (item, None)).collect()
+    } else {
+        select(installed, Spec::from_strings(tools, action)?)
+    };
+
+    // A tool that can't be checked, e.g. because its registry is unavailable, is skipped so that
+    // the rest can still be checked
+    let mut updates = Vec::new();
+    for (installed, spec) in requested {
+        let latest = match resolve_latest(&installed, spec, session) {
+            Ok(Some(latest)) => latest,
+            Ok(None) => continue,
+            Err(error) => {
+                warn!(
+                    "Could not check {} for updates: {}",
+                    tool_version(installed.name(), installed.version()),
+                    error
+                );
+                continue;
+            }
+        };
+
+        if latest.version() > installed.version() {
+            updates.push(Update { installed, latest });
+        }
+    }
+
+    Ok(updates)
+}
+
+/// Resolves the newest version of an installed tool that matches the spec, or the default spec if
+/// no version was requested
+fn resolve_latest(
+    installed: &Installed,
+    spec: Option<VersionSpec>,
+    session: &mut Session,
+) -> Fallible<Option<Latest>> {
+    let spec = match spec {
+        Some(VersionSpec::None) | None => installed.default_spec()?,
+        Some(spec) => spec,
+    };
+
+    let latest = match installed {
+        Installed::Node(_) => Latest::Node(node::resolve(spec, session)?),
+        // A spec that resolves to the bundled npm has nothing to compare against
+        Installed::Npm(_) => match npm::resolve(spec, session)? {
+            Some(version) => Latest::Npm(version),
+            None => return Ok(None),
+        },
+        Installed::Pnpm(_) => Latest::Pnpm(pnpm::resolve(spec, session)?),
+        Installed::Yarn(_) => Latest::Yarn(yarn::resolve(spec, session)?),
+        Installed::Package { name, node, .. } => Latest::Package {
+            details: package::resolve(name, spec, session)?,
+            node: node.clone(),
+        },
+    };
+
+    Ok(Some(latest))
+}
+
+/// Collects the default toolchain and installed packages
+fn installed(session: &Session) -> Fallible<Vec<Installed>> {
+    let mut installed = Vec::new();
+
+    if let Some(platform) = session.default_platform()? {
+        installed.push(Installed::Node(platform.node.clone()));
+        installed.extend(platform.npm.clone().map(Installed::Npm));
+        installed.extend(platform.pnpm.clone().map(Installed::Pnpm));
+        installed.extend(platform.yarn.clone().map(Installed::Yarn));
+    }
+
+    installed.extend(
+        package_configs()?
+            .into_iter()
+            .map(|config| Installed::Package {
+                name: config.name,
+                version: config.version,
+                node: config.platform.node,
+            }),
+    );
+
+    Ok(installed)
+}
+
+/// Selects the installed tools that match the requested specs, along with the version requested
+fn select(installed: Vec<Installed>, specs: Vec<Spec>) -> Vec<(Installed, Option<VersionSpec>)> {
+    let mut specs: Vec<(String, VersionSpec)> = specs
+        .into_iter()
+        .map(|spec| match spec {
+            Spec::Node(version) => ("node".to_string(), version),
+            Spec::Npm(version) => ("npm".to_string(), version),
+            Spec::Pnpm(version) => ("pnpm".to_string(), version),
+            Spec::Yarn(version) => ("yarn".to_string(), version),
+            Spec::Package(name, version) => (name, version),
+        })
+        .collect();
+
+    let selected = installed
+        .into_iter()
+        .filter_map(|item| {
+            let position = specs.iter().position(|(name, _)| name == item.name())?;
+            let (_, version) = specs.remove(position);
+            Some((item, Some(version)))
+        })
+        .collect();
+
+    for (name, _) in specs {
+        warn!(
+            "Skipping {}, because it is not in your default toolchain or installed",
+            name
+        );
+    }
+
+    selected
+}
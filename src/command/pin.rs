@@ -8,9 +8,13 @@ use crate::command::Command;
 
 #[derive(StructOpt)]
 pub(crate) struct Pin {
-    /// Tools to pin, like `node@lts` or `yarn@^1.14`.
+    /// Tools to pin, like `node@lts`, `pnpm@^5` or `yarn@^1.14`.
     #[structopt(name = "tool[@version]", required = true, min_values = 1)]
     tools: Vec<String>,
+
+    /// Allow pinning a release candidate or nightly build of Node
+    #[structopt(long = "allow-prerelease")]
+    allow_prerelease: bool,
 }
 
 impl Command for Pin {
@@ -18,7 +22,12 @@ impl Command for Pin {
         session.add_event_start(ActivityKind::Pin);
 
         for tool in Spec::from_strings(&self.tools, "pin")? {
-            tool.resolve(session)?.pin(session)?;
+            tool.resolve_pin(session, self.allow_prerelease)?
+                .pin(session)?;
+        }
+
+        if let Some(project) = session.project()? {
+            project.check_engines();
         }
 
         session.add_event_end(ActivityKind::Pin, ExitCode::Success);
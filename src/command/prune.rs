@@ -0,0 +1,382 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use log::info;
+use semver::Version;
+use structopt::StructOpt;
+use walkdir::WalkDir;
+
+use crate::command::Command;
+use volta_core::error::{Context, ErrorKind, ExitCode, Fallible};
+use volta_core::fs::{
+    ensure_dir_does_not_exist, ensure_file_does_not_exist, lock_tool_version, lock_volta_home,
+    read_dir_eager,
+};
+use volta_core::inventory::{
+    node_versions, npm_versions, package_configs, package_versions, pnpm_versions, yarn_versions,
+};
+use volta_core::layout::volta_home;
+use volta_core::platform::PlatformSpec;
+use volta_core::project::Project;
+use volta_core::session::{ActivityKind, Session};
+use volta_core::style::{success_prefix, tool_version};
+use volta_core::tool::NODE_DISTRO_OS;
+use volta_core::version::parse_version;
+
+#[derive(StructOpt)]
+pub(crate) struct Prune {
+    /// Show what would be removed and how much space would be reclaimed, without removing anything
+    #[structopt(long = "dry-run")]
+    dry_run: bool,
+
+    /// Additional project directories whose pinned tools should be kept
+    #[structopt(long = "project", parse(from_os_str), number_of_values = 1)]
+    projects: Vec<PathBuf>,
+}
+
+/// The tool versions that are still in use, and so must not be pruned.
+#[derive(Default)]
+struct Reachable {
+    node: BTreeSet<Version>,
+    npm: BTreeSet<Version>,
+    pnpm: BTreeSet<Version>,
+    yarn: BTreeSet<Version>,
+    packages: BTreeSet<(String, Version)>,
+}
+
+impl Reachable {
+    /// Determine the reachable tools: the default platform, the platforms that installed
+    /// packages were built with, and the platforms of the current and any additional projects.
+    fn current(session: &Session, projects: Vec<PathBuf>) -> Fallible<Self> {
+        let mut reachable = Reachable::default();
+
+        if let Some(platform) = session.toolchain()?.platform() {
+            reachable.add_platform(platform);
+        }
+
+        for config in package_configs()? {
+            reachable.add_platform(&config.platform);
+            reachable.packages.insert((config.name, config.version));
+        }
+
+        if let Some(platform) = session.project()?.and_then(Project::platform) {
+            reachable.add_platform(platform);
+        }
+
+        for root in projects {
+            if let Some(project) = Project::for_dir(root)? {
+                if let Some(platform) = project.platform() {
+                    reachable.add_platform(platform);
+                }
+            }
+        }
+
+        Ok(reachable)
+    }
+
+    fn add_platform(&mut self, platform: &PlatformSpec) {
+        self.node.insert(platform.node.clone());
+        self.npm.extend(platform.npm.clone());
+        self.pnpm.extend(platform.pnpm.clone());
+        self.yarn.extend(platform.yarn.clone());
+    }
+}
+
+/// A fetched tool version that is no longer reachable, along with the files it occupies.
+struct Unreachable {
+    tool: String,
+    version: String,
+    image_dir: PathBuf,
+    cached_files: Vec<PathBuf>,
+}
+
+impl Unreachable {
+    /// The number of bytes that removing this tool would reclaim
+    fn size(&self) -> u64 {
+        dir_size(&self.image_dir)
+            + self
+                .cached_files
+                .iter()
+                .filter_map(|file| fs::metadata(file).ok())
+                .map(|metadata| metadata.len())
+                .sum::<u64>()
+    }
+
+    fn remove(&self) -> Fallible<()> {
+        // A shim may be fetching this version right now, so wait for it to finish first
+        let _lock = lock_tool_version(&self.tool, &self.version)?;
+
+        ensure_dir_does_not_exist(&self.image_dir)?;
+        for file in &self.cached_files {
+            ensure_file_does_not_exist(file)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Unreachable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&tool_version(&self.tool, &self.version))
+    }
+}
+
+/// Collect every fetched tool version that isn't reachable
+fn unreachable(reachable: &Reachable) -> Fallible<Vec<Unreachable>> {
+    let home = volta_home()?;
+    let mut tools = Vec::new();
+
+    // A version may have an archive in the inventory without an image, e.g. if it was fetched
+    // but unpacking was interrupted, so both are checked
+    collect_unreachable(
+        &mut tools,
+        "node",
+        node_versions()?,
+        inventory_files(home.node_inventory_dir(), node_file_version)?,
+        &reachable.node,
+        |version| home.node_image_dir(version),
+    );
+    collect_unreachable(
+        &mut tools,
+        "npm",
+        npm_versions()?,
+        inventory_files(home.npm_inventory_dir(), |name| {
+            archive_version(name, "npm-", ".tgz")
+        })?,
+        &reachable.npm,
+        |version| home.npm_image_dir(version),
+    );
+    collect_unreachable(
+        &mut tools,
+        "pnpm",
+        pnpm_versions()?,
+        inventory_files(home.pnpm_inventory_dir(), |name| {
+            archive_version(name, "pnpm-", ".tgz")
+        })?,
+        &reachable.pnpm,
+        |version| home.pnpm_image_dir(version),
+    );
+    collect_unreachable(
+        &mut tools,
+        "yarn",
+        yarn_versions()?,
+        inventory_files(home.yarn_inventory_dir(), |name| {
+            archive_version(name, "yarn-v", ".tar.gz")
+        })?,
+        &reachable.yarn,
+        |version| home.yarn_image_dir(version),
+    );
+
+    for (name, versions) in package_versions()? {
+        for version in versions {
+            if reachable
+                .packages
+                .contains(&(name.clone(), version.clone()))
+            {
+                continue;
+            }
+
+            let version = version.to_string();
+            tools.push(Unreachable {
+                image_dir: home.package_image_dir(&name, &version),
+                cached_files: vec![
+                    home.package_distro_file(&name, &version),
+                    home.package_distro_shasum(&name, &version),
+                ],
+                tool: name.clone(),
+                version,
+            });
+        }
+    }
+
+    Ok(tools)
+}
+
+/// Add the versions of a tool that have an image or inventory files, but aren't reachable
+fn collect_unreachable<F>(
+    tools: &mut Vec<Unreachable>,
+    tool: &str,
+    images: BTreeSet<Version>,
+    mut files: BTreeMap<Version, Vec<PathBuf>>,
+    reachable: &BTreeSet<Version>,
+    image_dir: F,
+) where
+    F: Fn(&str) -> PathBuf,
+{
+    let fetched: BTreeSet<Version> = images.into_iter().chain(files.keys().cloned()).collect();
+
+    for version in fetched.difference(reachable) {
+        let cached_files = files.remove(version).unwrap_or_default();
+        let version = version.to_string();
+        tools.push(Unreachable {
+            tool: tool.into(),
+            image_dir: image_dir(&version),
+            cached_files,
+            version,
+        });
+    }
+}
+
+/// Collect the files in an inventory directory, keyed by the version in their names
+fn inventory_files<F>(dir: &Path, version_of: F) -> Fallible<BTreeMap<Version, Vec<PathBuf>>>
+where
+    F: Fn(&str) -> Option<&str>,
+{
+    let mut files: BTreeMap<Version, Vec<PathBuf>> = BTreeMap::new();
+    if !dir.exists() {
+        return Ok(files);
+    }
+
+    let entries = read_dir_eager(dir).with_context(|| ErrorKind::ReadDirError {
+        dir: dir.to_owned(),
+    })?;
+
+    for (entry, metadata) in entries {
+        if !metadata.is_file() {
+            continue;
+        }
+
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if let Some(version) = version_of(&name).and_then(|v| parse_version(v).ok()) {
+            files.entry(version).or_default().push(entry.path());
+        }
+    }
+
+    Ok(files)
+}
+
+/// Find the version in the name of a Node inventory file: the archive for any distro or its
+/// checksum, or the record of the bundled npm version or the distro of the image
+fn node_file_version(name: &str) -> Option<&str> {
+    let rest = archive_version(name, "node-v", "")?;
+
+    if rest.ends_with("-npm") {
+        Some(&rest[..rest.len() - "-npm".len()])
+    } else if rest.ends_with("-distro") {
+        Some(&rest[..rest.len() - "-distro".len()])
+    } else {
+        rest.find(&format!("-{}-", NODE_DISTRO_OS))
+            .map(|end| &rest[..end])
+    }
+}
+
+/// Find the version in the name of an archive, between the given prefix and suffix
+fn archive_version<'a>(name: &'a str, prefix: &str, suffix: &str) -> Option<&'a str> {
+    if name.len() > prefix.len() + suffix.len()
+        && name.starts_with(prefix)
+        && name.ends_with(suffix)
+    {
+        Some(&name[prefix.len()..name.len() - suffix.len()])
+    } else {
+        None
+    }
+}
+
+/// The total size in bytes of the files in a directory, or 0 if it doesn't exist
+fn dir_size(dir: &Path) -> u64 {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Format a number of bytes for display, e.g. `12.3 MiB`
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+    for next in &UNITS[1..] {
+        if size < 1024.0 {
+            break;
+        }
+        size /= 1024.0;
+        unit = next;
+    }
+
+    format!("{:.1} {}", size, unit)
+}
+
+impl Command for Prune {
+    fn run(self, session: &mut Session) -> Fallible<ExitCode> {
+        session.add_event_start(ActivityKind::Prune);
+
+        // Hold the lock while deciding what to remove, so that nothing is installed in the meantime
+        let _lock = if self.dry_run {
+            None
+        } else {
+            Some(lock_volta_home()?)
+        };
+
+        let reachable = Reachable::current(session, self.projects)?;
+        let tools = unreachable(&reachable)?;
+        let mut reclaimed = 0;
+
+        for tool in &tools {
+            let size = tool.size();
+            if self.dry_run {
+                info!("Would remove {} ({})", tool, format_size(size));
+            } else {
+                tool.remove()?;
+                info!("Removed {} ({})", tool, format_size(size));
+            }
+            reclaimed += size;
+        }
+
+        if tools.is_empty() {
+            info!("Nothing to prune, all fetched tools are in use");
+        } else if self.dry_run {
+            info!("Pruning would reclaim {}", format_size(reclaimed));
+        } else {
+            info!(
+                "{} pruned unused tools, reclaiming {}",
+                success_prefix(),
+                format_size(reclaimed)
+            );
+        }
+
+        session.add_event_end(ActivityKind::Prune, ExitCode::Success);
+        Ok(ExitCode::Success)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{archive_version, format_size, node_file_version};
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1024), "1.0 KiB");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MiB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+
+    #[test]
+    fn test_inventory_file_versions() {
+        let os = volta_core::tool::NODE_DISTRO_OS;
+        let archive = format!("node-v12.16.1-{}-x64-musl.tar.gz", os);
+        assert_eq!(node_file_version(&archive), Some("12.16.1"));
+        let checksum = format!("node-v16.0.0-rc.1-{}-x64.tar.gz.sha256", os);
+        assert_eq!(node_file_version(&checksum), Some("16.0.0-rc.1"));
+        assert_eq!(node_file_version("node-v12.16.1-npm"), Some("12.16.1"));
+        assert_eq!(node_file_version("node-v12.16.1-distro"), Some("12.16.1"));
+        assert_eq!(node_file_version("index.json"), None);
+
+        assert_eq!(
+            archive_version("yarn-v1.22.4.tar.gz", "yarn-v", ".tar.gz"),
+            Some("1.22.4")
+        );
+        assert_eq!(archive_version("npm-.tgz", "npm-", ".tgz"), None);
+    }
+}
@@ -1,3 +1,4 @@
+use std::env;
 use std::ffi::OsString;
 
 use crate::command::Command;
@@ -5,10 +6,10 @@ use crate::common::{Error, IntoResult};
 use log::warn;
 use structopt::StructOpt;
 use volta_core::error::{report_error, ExitCode, Fallible};
-use volta_core::platform::{CliPlatform, InheritOption};
+use volta_core::platform::{CliPlatform, InheritOption, VOLTA_CLI_PLATFORM};
 use volta_core::run::execute_tool;
 use volta_core::session::{ActivityKind, Session};
-use volta_core::tool::{node, npm, yarn};
+use volta_core::tool::{node, npm, pnpm, yarn};
 
 #[derive(Debug, StructOpt)]
 pub(crate) struct Run {
@@ -24,6 +25,14 @@ pub(crate) struct Run {
     #[structopt(long = "bundled-npm", conflicts_with = "npm")]
     bundled_npm: bool,
 
+    /// Set the custom pnpm version
+    #[structopt(long = "pnpm", value_name = "version", conflicts_with = "no_pnpm")]
+    pnpm: Option<String>,
+
+    /// Disables pnpm
+    #[structopt(long = "no-pnpm", conflicts_with = "pnpm")]
+    no_pnpm: bool,
+
     /// Set the custom Yarn version
     #[structopt(long = "yarn", value_name = "version", conflicts_with = "no_yarn")]
     yarn: Option<String>,
@@ -32,6 +41,10 @@ pub(crate) struct Run {
     #[structopt(long = "no-yarn", conflicts_with = "yarn")]
     no_yarn: bool,
 
+    /// Ignore the versions set by an enclosing `volta run`
+    #[structopt(long = "reset-platform")]
+    reset_platform: bool,
+
     /// Set an environment variable (can be used multiple times)
     #[structopt(long = "env", value_name = "NAME=value", raw(number_of_values = "1"))]
     envs: Vec<String>,
@@ -49,9 +62,18 @@ impl Command for Run {
     fn run(self, session: &mut Session) -> Fallible<ExitCode> {
         session.add_event_start(ActivityKind::Run);
 
+        if self.reset_platform {
+            // Removing the variable from this process means the command won't inherit it either
+            env::remove_var(VOLTA_CLI_PLATFORM);
+        }
+
         let envs = self.parse_envs();
         let platform = self.parse_platform(session)?;
 
+        if let Some(project) = session.project()? {
+            project.check_engines();
+        }
+
         match execute_tool(&self.command, &self.args, envs, platform, session).into_result() {
             Ok(()) => {
                 session.add_event_end(ActivityKind::Run, ExitCode::Success);
@@ -91,6 +113,14 @@ impl Run {
             },
         };
 
+        let pnpm = match (self.no_pnpm, &self.pnpm) {
+            (true, _) => InheritOption::None,
+            (false, None) => InheritOption::Inherit,
+            (false, Some(version)) => {
+                InheritOption::Some(pnpm::resolve(version.parse()?, session)?)
+            }
+        };
+
         let yarn = match (self.no_yarn, &self.yarn) {
             (true, _) => InheritOption::None,
             (false, None) => InheritOption::Inherit,
@@ -99,7 +129,12 @@ impl Run {
             }
         };
 
-        Ok(CliPlatform { node, npm, yarn })
+        Ok(CliPlatform {
+            node,
+            npm,
+            pnpm,
+            yarn,
+        })
     }
 
     /// Convert the environment variable settings passed to the command line into (Key, Value) pairs
@@ -29,7 +29,7 @@ impl Command for Setup {
 }
 
 #[cfg(unix)]
-mod os {
+pub(crate) mod os {
     use std::env;
     use std::fs::File;
     use std::io::{self, BufRead, BufReader, Write};
@@ -81,7 +81,7 @@ mod os {
     /// Returns a list of profile files to modify / create.
     ///
     /// Any file in the list should be created if it doesn't already exist
-    fn determine_profiles() -> Fallible<Vec<PathBuf>> {
+    pub(crate) fn determine_profiles() -> Fallible<Vec<PathBuf>> {
         let home_dir = dirs::home_dir().ok_or(ErrorKind::NoHomeEnvironmentVar)?;
         let shell = env::var("SHELL").unwrap_or_else(|_| String::new());
         let mut profiles = Vec::new();
@@ -203,7 +203,7 @@ If you run into problems running Volta, create {} and run `volta setup` again.",
 }
 
 #[cfg(windows)]
-mod os {
+pub(crate) mod os {
     use std::process::Command;
 
     use log::debug;
@@ -0,0 +1,43 @@
+use log::info;
+use structopt::StructOpt;
+use volta_core::error::{ErrorKind, ExitCode, Fallible};
+use volta_core::hook::project_hooks_file;
+use volta_core::hook::trust::trust;
+use volta_core::project::Project;
+use volta_core::session::{ActivityKind, Session};
+use volta_core::style::success_prefix;
+
+use crate::command::Command;
+
+#[derive(StructOpt)]
+pub(crate) struct Trust {}
+
+impl Command for Trust {
+    fn run(self, session: &mut Session) -> Fallible<ExitCode> {
+        session.add_event_start(ActivityKind::Trust);
+
+        let hooks_files: Vec<_> = session
+            .project()?
+            .into_iter()
+            .flat_map(Project::workspace_roots)
+            .map(project_hooks_file)
+            .filter(|file| file.is_file())
+            .collect();
+
+        if hooks_files.is_empty() {
+            return Err(ErrorKind::NoProjectHooks.into());
+        }
+
+        for hooks_file in hooks_files {
+            trust(&hooks_file)?;
+            info!(
+                "{} Trusted the hooks in {}",
+                success_prefix(),
+                hooks_file.display()
+            );
+        }
+
+        session.add_event_end(ActivityKind::Trust, ExitCode::Success);
+        Ok(ExitCode::Success)
+    }
+}
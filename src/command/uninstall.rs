@@ -3,13 +3,12 @@ use structopt::StructOpt;
 use volta_core::error::{ExitCode, Fallible};
 use volta_core::session::{ActivityKind, Session};
 use volta_core::tool;
-use volta_core::version::VersionSpec;
 
 use crate::command::Command;
 
 #[derive(StructOpt)]
 pub(crate) struct Uninstall {
-    /// The tool to uninstall, e.g. `node`, `npm`, `yarn`, or <package>
+    /// The tool to uninstall, e.g. `node@12.16.1`, `npm@6.13.4`, `pnpm@5.18.9`, `yarn@1.22.0`, or <package>
     tool: String,
 }
 
@@ -17,10 +16,9 @@ impl Command for Uninstall {
     fn run(self, session: &mut Session) -> Fallible<ExitCode> {
         session.add_event_start(ActivityKind::Uninstall);
 
-        let version = VersionSpec::default();
-        let tool = tool::Spec::from_str_and_version(&self.tool, version);
+        let tool = tool::Spec::try_from_str(&self.tool)?;
 
-        tool.uninstall()?;
+        tool.uninstall(session)?;
 
         session.add_event_end(ActivityKind::Uninstall, ExitCode::Success);
         Ok(ExitCode::Success)
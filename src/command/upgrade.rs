@@ -0,0 +1,67 @@
+//! Provides the `volta upgrade` command, which installs the newer versions that `volta outdated`
+//! reports.
+
+use log::info;
+use structopt::StructOpt;
+
+use super::outdated::{find_updates, Latest, Update};
+use crate::command::Command;
+use volta_core::error::{ExitCode, Fallible};
+use volta_core::session::{ActivityKind, Session};
+use volta_core::style::success_prefix;
+use volta_core::tool::{Node, Npm, Package, Pnpm, Tool, Yarn};
+
+#[derive(StructOpt)]
+pub(crate) struct Upgrade {
+    /// Tools to upgrade, like `node`, `typescript` or `yarn@^2`. Defaults to your whole default
+    /// toolchain and every installed package.
+    ///
+    /// Without a version, each tool is upgraded to the newest release with the same major
+    /// version, or the newest LTS release with the same major version for Node.
+    #[structopt(name = "tool[@version]")]
+    tools: Vec<String>,
+
+    /// Install upgraded packages with the Node version they would get from a new install, instead
+    /// of the one they were installed with
+    #[structopt(long = "update-node")]
+    update_node: bool,
+}
+
+impl Command for Upgrade {
+    fn run(self, session: &mut Session) -> Fallible<ExitCode> {
+        session.add_event_start(ActivityKind::Upgrade);
+
+        let updates = find_updates(&self.tools, "upgrade", session)?;
+        if updates.is_empty() {
+            info!("{} Everything is up to date", success_prefix());
+        }
+
+        for update in updates {
+            self.tool(update).install(session)?;
+        }
+
+        session.add_event_end(ActivityKind::Upgrade, ExitCode::Success);
+        Ok(ExitCode::Success)
+    }
+}
+
+impl Upgrade {
+    fn tool(&self, update: Update) -> Box<dyn Tool> {
+        let Update { installed, latest } = update;
+
+        match latest {
+            Latest::Node(version) => Box::new(Node::new(version)),
+            Latest::Npm(version) => Box::new(Npm::new(version)),
+            Latest::Pnpm(version) => Box::new(Pnpm::new(version)),
+            Latest::Yarn(version) => Box::new(Yarn::new(version)),
+            Latest::Package { details, node } => {
+                let package = Package::new(installed.name().to_string(), details);
+                if self.update_node {
+                    Box::new(package)
+                } else {
+                    Box::new(package.with_node(node))
+                }
+            }
+        }
+    }
+}
@@ -2,10 +2,13 @@
 mod command;
 mod cli;
 
+use std::env;
+
 use structopt::StructOpt;
 
 use volta_core::error::report_error;
 use volta_core::log::{LogContext, LogVerbosity, Logger};
+use volta_core::offline::VOLTA_OFFLINE;
 use volta_core::session::{ActivityKind, Session};
 
 mod common;
@@ -24,10 +27,20 @@ pub fn main() {
     };
     Logger::init(LogContext::Volta, verbosity).expect("Only a single logger should be initialized");
 
+    // Offline mode is read from the environment, which also passes it along to any shims we run
+    if volta.offline {
+        env::set_var(VOLTA_OFFLINE, "1");
+    }
+
     let mut session = Session::init();
     session.add_event_start(ActivityKind::Volta);
 
-    let result = ensure_layout().and_then(|()| volta.run(&mut session).map_err(Error::Volta));
+    let layout = if volta.needs_layout() {
+        ensure_layout()
+    } else {
+        Ok(())
+    };
+    let result = layout.and_then(|()| volta.run(&mut session).map_err(Error::Volta));
     match result {
         Ok(exit_code) => {
             session.add_event_end(ActivityKind::Volta, exit_code);
@@ -4,6 +4,7 @@ use hamcrest2::prelude::*;
 use test_support::matchers::execs;
 
 use volta_core::error::ExitCode;
+use volta_core::tool::Node;
 
 const NODE_VERSION_INFO: &str = r#"[
 {"version":"v10.99.1040","npm":"6.2.26","lts": "Dubnium","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
@@ -70,6 +71,77 @@ fn install_valid_node_saves_to_inventory() {
     );
 
     assert!(s.node_inventory_archive_exists("10.99.1040"));
+    assert!(s.node_inventory_checksum_exists("10.99.1040"));
+}
+
+#[test]
+fn install_node_from_distro_hook_saves_checksum() {
+    // The public checksums don't apply to a custom distro, so the archive's own digest is saved
+    let hooks_json = format!(
+        r#"{{
+    "node": {{
+        "distro": {{
+            "prefix": "{}/v10.99.1040/"
+        }}
+    }}
+}}"#,
+        mockito::SERVER_URL
+    );
+    let s = sandbox()
+        .default_hooks(&hooks_json)
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("install node@10.99.1040"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert!(s.node_inventory_archive_exists("10.99.1040"));
+    assert!(s.node_inventory_checksum_exists("10.99.1040"));
+}
+
+#[test]
+fn install_node_with_mismatched_checksum_leaves_inventory_unchanged() {
+    let checksums = format!(
+        "{}  {}\n",
+        "0".repeat(64),
+        Node::archive_filename("10.99.1040")
+    );
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .node_checksums("10.99.1040", &checksums)
+        .build();
+
+    assert_that!(
+        s.volta("install node@10.99.1040"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Checksum verification failed for node@10.99.1040")
+    );
+
+    assert!(!s.node_inventory_archive_exists("10.99.1040"));
+    assert!(!s.node_inventory_checksum_exists("10.99.1040"));
+}
+
+#[test]
+fn install_node_missing_from_checksums_fails() {
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .node_checksums("10.99.1040", "")
+        .build();
+
+    assert_that!(
+        s.volta("install node@10.99.1040"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Could not find a checksum for[..]")
+    );
+
+    assert!(!s.node_inventory_archive_exists("10.99.1040"));
 }
 
 #[test]
@@ -0,0 +1,143 @@
+use crate::support::sandbox::sandbox;
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const PACKAGE_JSON_ENGINES_ONLY: &str = r#"{
+    "name": "engines-only",
+    "engines": {
+        "node": "^10.0.0",
+        "yarn": "1.x"
+    }
+}"#;
+
+const PACKAGE_JSON_ENGINES_MISMATCH: &str = r#"{
+    "name": "engines-mismatch",
+    "engines": {
+        "node": ">=10.0.0"
+    },
+    "volta": {
+        "node": "9.27.6"
+    }
+}"#;
+
+const PACKAGE_JSON_ENGINES_MATCH: &str = r#"{
+    "name": "engines-match",
+    "engines": {
+        "node": ">=8.0.0"
+    },
+    "volta": {
+        "node": "9.27.6"
+    }
+}"#;
+
+const PACKAGE_JSON_LEGACY_ENGINES: &str = r#"{
+    "name": "legacy-engines",
+    "engines": ["node >= 0.8"],
+    "volta": {
+        "node": "9.27.6"
+    }
+}"#;
+
+const NODE_VERSION_INFO: &str = r#"[
+{"version":"v12.99.1","npm":"6.14.4","lts": false,"files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
+{"version":"v10.99.1040","npm":"6.2.26","lts": "Dubnium","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
+{"version":"v9.27.6","npm":"5.6.17","lts": false,"files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]}
+]
+"#;
+
+const YARN_VERSION_INFO: &str = r#"[
+    {"tag_name":"v1.22.4","assets":[{"name":"yarn-v1.22.4.tar.gz"}]},
+    {"tag_name":"v1.2.42","assets":[{"name":"yarn-v1.2.42.tar.gz"}]},
+    {"tag_name":"v0.27.5","assets":[{"name":"yarn-v0.27.5.tar.gz"}]}
+]"#;
+
+const USE_ENGINES: &str = "VOLTA_USE_ENGINES";
+
+#[test]
+fn warns_when_pin_does_not_satisfy_engines() {
+    let s = sandbox()
+        .package_json(PACKAGE_JSON_ENGINES_MISMATCH)
+        .build();
+
+    assert_that!(
+        s.volta("list --format plain"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("runtime node@9.27.6 (current @ [..]package.json)")
+            .with_stderr_contains(
+                "[..]node@9.27.6 does not satisfy the `engines.node` requirement '[..]' in [..]package.json"
+            )
+    );
+}
+
+#[test]
+fn shims_do_not_warn_about_engines() {
+    let s = sandbox()
+        .package_json(PACKAGE_JSON_ENGINES_MISMATCH)
+        .build();
+
+    assert_that!(
+        s.npm("i -g ember-cli"),
+        execs()
+            .with_status(ExitCode::ExecutionFailure as i32)
+            .with_stderr_contains("[..]Global package installs are not supported.")
+            .with_stderr_does_not_contain("[..]does not satisfy[..]")
+    );
+}
+
+#[test]
+fn no_warning_when_pin_satisfies_engines() {
+    let s = sandbox().package_json(PACKAGE_JSON_ENGINES_MATCH).build();
+
+    assert_that!(
+        s.volta("list --format plain"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stderr_does_not_contain("[..]does not satisfy[..]")
+    );
+}
+
+#[test]
+fn ignores_legacy_engines_format() {
+    let s = sandbox().package_json(PACKAGE_JSON_LEGACY_ENGINES).build();
+
+    assert_that!(
+        s.volta("list --format plain"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("runtime node@9.27.6 (current @ [..]package.json)")
+    );
+}
+
+#[test]
+fn ignores_engines_without_pin_by_default() {
+    let s = sandbox().package_json(PACKAGE_JSON_ENGINES_ONLY).build();
+
+    assert_that!(
+        s.volta("list --format plain"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_does_not_contain("[..]current @[..]")
+    );
+}
+
+#[test]
+fn resolves_engines_when_enabled() {
+    let s = sandbox()
+        .package_json(PACKAGE_JSON_ENGINES_ONLY)
+        .node_available_versions(NODE_VERSION_INFO)
+        .yarn_available_versions(YARN_VERSION_INFO)
+        .env(USE_ENGINES, "1")
+        .build();
+
+    assert_that!(
+        s.volta("list --format plain"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("runtime node@10.99.1040 (current @ [..]package.json)")
+            .with_stdout_contains("package-manager yarn@1.22.4 (current @ [..]package.json)")
+    );
+}
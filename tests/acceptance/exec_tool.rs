@@ -0,0 +1,42 @@
+#![cfg(unix)]
+
+use std::process::Stdio;
+
+use crate::support::sandbox::{sandbox, shim_exe};
+use volta_core::fs::symlink_file;
+
+const PLATFORM: &str = r#"{
+    "node":{
+        "runtime":"10.99.1040",
+        "npm":"6.2.26"
+    }
+}"#;
+
+/// A stand-in for Node that prints its own process ID
+const PRINT_PID: &str = "#!/bin/sh\necho $$\n";
+
+#[test]
+fn shim_runs_tool_in_its_own_process() {
+    let s = sandbox()
+        .platform(PLATFORM)
+        .node_image_executable("10.99.1040", "node", PRINT_PID)
+        .node_npm_version_file("10.99.1040", "6.2.26")
+        .build();
+    let node_shim = s.root().join("node");
+    symlink_file(shim_exe(), &node_shim).expect("could not create the node shim");
+
+    let child = s
+        .process(&node_shim)
+        .build_command()
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("could not start the node shim");
+    let shim_pid = child.id();
+    let output = child.wait_with_output().expect("node shim did not exit");
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        shim_pid.to_string()
+    );
+}
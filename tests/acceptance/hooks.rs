@@ -3,6 +3,7 @@ use std::path::PathBuf;
 use crate::support::sandbox::sandbox;
 use hamcrest2::assert_that;
 use hamcrest2::prelude::*;
+use mockito::{mock, Matcher};
 use test_support::matchers::execs;
 use volta_core::error::ExitCode;
 
@@ -77,6 +78,33 @@ fn workspace_hooks_json() -> String {
     )
 }
 
+fn bin_hooks_json() -> String {
+    r#"
+{
+    "yarn": {
+        "distro": {
+            "bin": "/bin/echo"
+        }
+    }
+}"#
+    .to_string()
+}
+
+fn events_hooks_json(path: &str) -> String {
+    format!(
+        r#"
+{{
+    "events": {{
+        "publish": {{
+            "url": "{}{}"
+        }}
+    }}
+}}"#,
+        mockito::SERVER_URL,
+        path
+    )
+}
+
 #[test]
 fn redirects_download() {
     let s = sandbox().default_hooks(&default_hooks_json()).build();
@@ -90,6 +118,93 @@ fn redirects_download() {
     );
 }
 
+#[test]
+fn expands_musl_arch_when_forced() {
+    let hooks_json = format!(
+        r#"
+{{
+    "node": {{
+        "distro": {{
+            "template": "{}/hook/default/node/{{{{os}}}}/{{{{arch}}}}/{{{{version}}}}"
+        }}
+    }}
+}}"#,
+        mockito::SERVER_URL
+    );
+    let s = sandbox()
+        .default_hooks(&hooks_json)
+        .env("VOLTA_NODE_DISTRO", "musl")
+        .build();
+
+    assert_that!(
+        s.volta("install node@1.2.3"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Could not download node@1.2.3")
+            .with_stderr_contains("[..]/hook/default/node/[..]-musl/1.2.3")
+    );
+}
+
+#[test]
+fn does_not_reuse_image_of_other_distro() {
+    let hooks_json = format!(
+        r#"
+{{
+    "node": {{
+        "distro": {{
+            "template": "{}/hook/default/node/{{{{os}}}}/{{{{arch}}}}/{{{{version}}}}"
+        }}
+    }}
+}}"#,
+        mockito::SERVER_URL
+    );
+    // Images without a recorded distro are official builds, which can't run on musl
+    let s = sandbox()
+        .default_hooks(&hooks_json)
+        .node_image("1.2.3")
+        .node_npm_version_file("1.2.3", "1.4.5")
+        .env("VOLTA_NODE_DISTRO", "musl")
+        .build();
+
+    assert_that!(
+        s.volta("install node@1.2.3"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Could not download node@1.2.3")
+            .with_stderr_contains("[..]/hook/default/node/[..]-musl/1.2.3")
+    );
+}
+
+#[test]
+fn uses_recorded_distro() {
+    let hooks_json = format!(
+        r#"
+{{
+    "node": {{
+        "distro": {{
+            "template": "{}/hook/default/node/{{{{os}}}}/{{{{arch}}}}/{{{{version}}}}"
+        }}
+    }}
+}}"#,
+        mockito::SERVER_URL
+    );
+    // The flavor detected by an earlier run is reused, and the official image doesn't match it
+    let s = sandbox()
+        .default_hooks(&hooks_json)
+        .file(".volta/cache/node/distro", "musl")
+        .node_image("1.2.3")
+        .node_npm_version_file("1.2.3", "1.4.5")
+        .build();
+
+    assert_that!(
+        s.volta("install node@1.2.3"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Could not download node@1.2.3")
+            .with_stderr_contains("[..]/hook/default/node/[..]-musl/1.2.3")
+    );
+}
+
 #[test]
 fn merges_project_and_default_hooks() {
     let local_hooks: PathBuf = [".volta", "hooks.json"].iter().collect();
@@ -99,6 +214,11 @@ fn merges_project_and_default_hooks() {
         .project_file(&local_hooks.to_string_lossy(), &project_hooks_json())
         .build();
 
+    assert_that!(
+        s.volta("trust"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
     // Project defines yarn hooks, so those should be used
     assert_that!(
         s.volta("install yarn@3.2.1"),
@@ -134,6 +254,11 @@ fn merges_workspace_hooks() {
         .project_file(&workspace_hooks.to_string_lossy(), &workspace_hooks_json())
         .build();
 
+    assert_that!(
+        s.volta("trust"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
     // Project defines yarn hooks, so those should be used
     assert_that!(
         s.volta("pin yarn@3.1.4"),
@@ -161,3 +286,133 @@ fn merges_workspace_hooks() {
             .with_stderr_contains("[..]/hook/default/node/11.11.2")
     );
 }
+
+#[test]
+fn publishes_events_to_url() {
+    let events_mock = mock("POST", "/events/publish")
+        .match_header("content-type", "application/json")
+        .match_body(Matcher::Regex(
+            r#"^\[\{"timestamp":\d+,"name":"volta","event":"start"\}"#.into(),
+        ))
+        .with_status(200)
+        .expect(1)
+        .create();
+    let s = sandbox()
+        .default_hooks(&events_hooks_json("/events/publish"))
+        .build();
+
+    assert_that!(
+        s.volta("install node@1.2.3"),
+        execs().with_status(ExitCode::NetworkError as i32)
+    );
+
+    events_mock.assert();
+}
+
+#[test]
+fn publish_failure_does_not_change_exit_code() {
+    let events_mock = mock("POST", "/events/unavailable")
+        .with_status(500)
+        .expect(1)
+        .create();
+    let s = sandbox()
+        .default_hooks(&events_hooks_json("/events/unavailable"))
+        .build();
+
+    assert_that!(
+        s.volta("install node@1.2.3 --verbose"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Unable to publish events to '[..]/events/unavailable'")
+    );
+
+    events_mock.assert();
+}
+
+#[test]
+fn ignores_untrusted_project_bin_hooks() {
+    let local_hooks: PathBuf = [".volta", "hooks.json"].iter().collect();
+    let s = sandbox()
+        .package_json("{}")
+        .default_hooks(&default_hooks_json())
+        .project_file(&local_hooks.to_string_lossy(), &bin_hooks_json())
+        .build();
+
+    assert_that!(
+        s.volta("install yarn@3.2.1"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains(
+                "[..]Ignoring the hooks in [..], because they have not been trusted."
+            )
+            .with_stderr_contains("[..]/hook/default/yarn/3.2.1")
+    );
+}
+
+#[test]
+fn ignores_untrusted_project_download_hooks() {
+    let local_hooks: PathBuf = [".volta", "hooks.json"].iter().collect();
+    let s = sandbox()
+        .package_json("{}")
+        .default_hooks(&default_hooks_json())
+        .project_file(&local_hooks.to_string_lossy(), &project_hooks_json())
+        .build();
+
+    assert_that!(
+        s.volta("install yarn@3.2.1"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains(
+                "[..]Ignoring the hooks in [..], because they have not been trusted."
+            )
+            .with_stderr_contains("[..]/hook/default/yarn/3.2.1")
+    );
+}
+
+#[test]
+fn trusted_project_hooks_require_trust_after_changes() {
+    let local_hooks: PathBuf = [".volta", "hooks.json"].iter().collect();
+    let s = sandbox()
+        .package_json("{}")
+        .default_hooks(&default_hooks_json())
+        .project_file(&local_hooks.to_string_lossy(), &bin_hooks_json())
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("trust"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]Trusted the hooks in [..]")
+    );
+
+    assert_that!(
+        s.volta("install yarn@3.2.1"),
+        execs().with_stderr_does_not_contain("[..]Ignoring the hooks[..]")
+    );
+
+    std::fs::write(
+        s.root().join(&local_hooks),
+        bin_hooks_json().replace("/bin/echo", "/bin/true"),
+    )
+    .unwrap();
+
+    assert_that!(
+        s.volta("install yarn@3.2.1"),
+        execs().with_stderr_contains(
+            "[..]Ignoring the hooks in [..], because they have changed since they were trusted."
+        )
+    );
+}
+
+#[test]
+fn trust_requires_project_hooks() {
+    let s = sandbox().package_json("{}").build();
+
+    assert_that!(
+        s.volta("trust"),
+        execs()
+            .with_status(ExitCode::ConfigurationError as i32)
+            .with_stderr_contains("[..]Could not find any project hooks to trust.")
+    );
+}
@@ -13,6 +13,14 @@ const PACKAGE_JSON: &str = r#"{
     }
 }"#;
 
+const PACKAGE_JSON_WITH_PNPM: &str = r#"{
+    "name": "text-package",
+    "volta": {
+        "node": "10.22.123",
+        "pnpm": "5.18.9"
+    }
+}"#;
+
 #[test]
 fn npm_prevents_global_install() {
     let s = sandbox().package_json(PACKAGE_JSON).build();
@@ -126,3 +134,46 @@ fn yarn_allows_global_add_with_env_variable() {
             .with_stderr_contains("[..]Could not download node@[..]")
     );
 }
+
+#[test]
+fn pnpm_prevents_global_add() {
+    let s = sandbox().package_json(PACKAGE_JSON_WITH_PNPM).build();
+
+    assert_that!(
+        s.pnpm("add ember-cli --global"),
+        execs()
+            .with_status(ExitCode::ExecutionFailure as i32)
+            .with_stderr_contains("[..]Global package installs are not supported.")
+    );
+
+    assert_that!(
+        s.pnpm("add -g ember-cli"),
+        execs()
+            .with_status(ExitCode::ExecutionFailure as i32)
+            .with_stderr_contains("[..]Global package installs are not supported.")
+    );
+
+    assert_that!(
+        s.pnpm("-g i ember-cli"),
+        execs()
+            .with_status(ExitCode::ExecutionFailure as i32)
+            .with_stderr_contains("[..]Global package installs are not supported.")
+    );
+}
+
+#[test]
+fn pnpm_allows_global_add_with_env_variable() {
+    let s = sandbox()
+        .package_json(PACKAGE_JSON_WITH_PNPM)
+        .env("VOLTA_UNSAFE_GLOBAL", "1")
+        .build();
+
+    // Since we are using a fake pnpm/Node version, we expect to get an error about being unable to download
+    assert_that!(
+        s.pnpm("add -g ember-cli"),
+        execs()
+            .with_status(ExitCode::ExecutionFailure as i32)
+            .with_stderr_does_not_contain("[..]Global package installs are not supported.")
+            .with_stderr_contains("[..]Could not download node@[..]")
+    );
+}
@@ -6,16 +6,29 @@ cfg_if! {
 
         // test files
         mod corrupted_download;
+        mod engines;
+        mod exec_tool;
         mod hooks;
         mod intercept_global_installs;
         mod merged_platform;
         mod migrations;
+        mod node_version_file;
+        mod offline;
+        mod package_download;
         mod run_shim_directly;
         mod verbose_errors;
         mod volta_bypass;
+        mod volta_doctor;
+        mod volta_env;
+        mod volta_export;
+        mod volta_import;
         mod volta_install;
+        mod volta_list_remote;
+        mod volta_outdated;
         mod volta_pin;
+        mod volta_prune;
         mod volta_run;
         mod volta_uninstall;
+        mod yarn_berry;
     }
 }
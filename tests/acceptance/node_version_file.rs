@@ -0,0 +1,181 @@
+use crate::support::sandbox::sandbox;
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const PACKAGE_JSON_NO_VOLTA: &str = r#"{
+    "name": "no-volta"
+}"#;
+
+const PACKAGE_JSON_WITH_NODE: &str = r#"{
+    "name": "with-node",
+    "volta": {
+        "node": "9.27.6"
+    }
+}"#;
+
+const PACKAGE_JSON_YARN_ONLY: &str = r#"{
+    "name": "yarn-only",
+    "volta": {
+        "yarn": "1.2.42"
+    }
+}"#;
+
+const NODE_VERSION_INFO: &str = r#"[
+{"version":"v12.99.1","npm":"6.14.4","lts": false,"files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
+{"version":"v10.99.1040","npm":"6.2.26","lts": "Dubnium","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
+{"version":"v8.9.10","npm":"5.6.7","lts": "Carbon","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]}
+]
+"#;
+
+const NODE_VERSION_FILES: &str = "VOLTA_NODE_VERSION_FILES";
+
+#[test]
+fn ignores_nvmrc_by_default() {
+    let s = sandbox()
+        .package_json(PACKAGE_JSON_NO_VOLTA)
+        .project_file(".nvmrc", "10.22.123")
+        .build();
+
+    assert_that!(
+        s.volta("list --format plain"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_does_not_contain("[..]node@10.22.123[..]")
+    );
+}
+
+#[test]
+fn ignores_nvmrc_when_opted_out() {
+    let s = sandbox()
+        .package_json(PACKAGE_JSON_NO_VOLTA)
+        .project_file(".nvmrc", "10.22.123")
+        .env(NODE_VERSION_FILES, "0")
+        .build();
+
+    assert_that!(
+        s.volta("list --format plain"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_does_not_contain("[..]node@10.22.123[..]")
+    );
+}
+
+#[test]
+fn uses_nvmrc_when_enabled() {
+    let s = sandbox()
+        .package_json(PACKAGE_JSON_NO_VOLTA)
+        .project_file(".nvmrc", "v10.22.123\n")
+        .env(NODE_VERSION_FILES, "1")
+        .build();
+
+    assert_that!(
+        s.volta("list --format plain"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("runtime node@10.22.123 (current @ [..].nvmrc)")
+    );
+}
+
+#[test]
+fn prefers_node_version_over_nvmrc() {
+    let s = sandbox()
+        .package_json(PACKAGE_JSON_NO_VOLTA)
+        .project_file(".nvmrc", "8.9.10")
+        .project_file(".node-version", "10.22.123")
+        .env(NODE_VERSION_FILES, "1")
+        .build();
+
+    assert_that!(
+        s.volta("list --format plain"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("runtime node@10.22.123 (current @ [..].node-version)")
+    );
+}
+
+#[test]
+fn prefers_volta_pin_over_nvmrc() {
+    let s = sandbox()
+        .package_json(PACKAGE_JSON_WITH_NODE)
+        .project_file(".nvmrc", "10.22.123")
+        .env(NODE_VERSION_FILES, "1")
+        .build();
+
+    assert_that!(
+        s.volta("list --format plain"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("runtime node@9.27.6 (current @ [..]package.json)")
+    );
+}
+
+#[test]
+fn merges_nvmrc_with_pinned_yarn() {
+    let s = sandbox()
+        .package_json(PACKAGE_JSON_YARN_ONLY)
+        .project_file(".nvmrc", "10.22.123")
+        .env(NODE_VERSION_FILES, "1")
+        .build();
+
+    assert_that!(
+        s.volta("list --format plain"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("runtime node@10.22.123 (current @ [..].nvmrc)")
+            .with_stdout_contains("package-manager yarn@1.2.42 (current @ [..]package.json)")
+    );
+}
+
+#[test]
+fn resolves_lts_alias() {
+    let s = sandbox()
+        .package_json(PACKAGE_JSON_NO_VOLTA)
+        .project_file(".nvmrc", "lts/*")
+        .node_available_versions(NODE_VERSION_INFO)
+        .env(NODE_VERSION_FILES, "1")
+        .build();
+
+    assert_that!(
+        s.volta("list --format plain"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("runtime node@10.99.1040 (current @ [..].nvmrc)")
+    );
+}
+
+#[test]
+fn resolves_lts_codename() {
+    let s = sandbox()
+        .package_json(PACKAGE_JSON_NO_VOLTA)
+        .project_file(".node-version", "lts/carbon")
+        .node_available_versions(NODE_VERSION_INFO)
+        .env(NODE_VERSION_FILES, "1")
+        .build();
+
+    assert_that!(
+        s.volta("list --format plain"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("runtime node@8.9.10 (current @ [..].node-version)")
+    );
+}
+
+#[test]
+fn resolves_node_alias() {
+    let s = sandbox()
+        .package_json(PACKAGE_JSON_NO_VOLTA)
+        .project_file(".nvmrc", "node")
+        .node_available_versions(NODE_VERSION_INFO)
+        .env(NODE_VERSION_FILES, "1")
+        .build();
+
+    assert_that!(
+        s.volta("list --format plain"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("runtime node@12.99.1 (current @ [..].nvmrc)")
+    );
+}
@@ -0,0 +1,143 @@
+use crate::support::sandbox::{sandbox, Sandbox, SandboxBuilder};
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const VOLTA_LOGLEVEL: &str = "VOLTA_LOGLEVEL";
+const VOLTA_OFFLINE: &str = "VOLTA_OFFLINE";
+
+fn sandbox_with_inventory() -> SandboxBuilder {
+    sandbox()
+        .node_image("10.99.1040")
+        .node_npm_version_file("10.99.1040", "6.2.26")
+        .node_image("10.1.0")
+        .node_npm_version_file("10.1.0", "5.6.0")
+        .node_image("11.2.0")
+        .node_npm_version_file("11.2.0", "6.4.1")
+        .yarn_image("1.2.42")
+        .yarn_image("1.7.71")
+        .env(VOLTA_LOGLEVEL, "info")
+}
+
+#[test]
+fn install_node_semver_from_inventory() {
+    let s = sandbox_with_inventory().build();
+
+    assert_that!(
+        s.volta("install node@10 --offline"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains(
+                "[..]installed and set node@10.99.1040 (with npm@6.2.26) as default"
+            )
+    );
+}
+
+#[test]
+fn install_node_lts_from_inventory() {
+    let s = sandbox_with_inventory().env(VOLTA_OFFLINE, "1").build();
+
+    assert_that!(
+        s.volta("install node"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains(
+                "[..]installed and set node@10.99.1040 (with npm@6.2.26) as default"
+            )
+    );
+}
+
+const NODE_INDEX_CACHE: &str = r#"http://localhost/node-dist/index.json
+[
+{"version":"v11.2.0","npm":"6.4.1","lts": "Eleven","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
+{"version":"v10.99.1040","npm":"6.2.26","lts": false,"files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
+{"version":"v10.1.0","npm":"5.6.0","lts": false,"files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]}
+]
+"#;
+
+#[test]
+fn install_node_lts_from_cached_index() {
+    let s = sandbox_with_inventory()
+        .node_cache(NODE_INDEX_CACHE, true)
+        .env(VOLTA_OFFLINE, "1")
+        .build();
+
+    assert_that!(
+        s.volta("install node@lts"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]installed and set node@11.2.0 (with npm@6.4.1) as default")
+    );
+}
+
+#[test]
+fn no_lts_in_inventory_without_cached_index() {
+    let s = sandbox()
+        .node_image("11.2.0")
+        .node_npm_version_file("11.2.0", "6.4.1")
+        .env(VOLTA_OFFLINE, "1")
+        .build();
+
+    assert_that!(
+        s.volta("install node@lts"),
+        execs()
+            .with_status(ExitCode::NoVersionMatch as i32)
+            .with_stderr_contains("[..]LTS versions were assumed to be the even major versions.")
+    );
+}
+
+#[test]
+fn install_node_latest_from_inventory() {
+    let s = sandbox_with_inventory().env(VOLTA_OFFLINE, "1").build();
+
+    assert_that!(
+        s.volta("install node@latest"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]installed and set node@11.2.0 (with npm@6.4.1) as default")
+    );
+}
+
+#[test]
+fn install_yarn_from_inventory() {
+    let s = sandbox_with_inventory().env(VOLTA_OFFLINE, "1").build();
+
+    assert_that!(
+        s.volta("install yarn"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]installed and set yarn@1.7.71 as default")
+    );
+}
+
+#[test]
+fn no_matching_version_in_inventory() {
+    let s = sandbox_with_inventory().env(VOLTA_OFFLINE, "1").build();
+
+    assert_that!(
+        s.volta("install node@12"),
+        execs()
+            .with_status(ExitCode::NoVersionMatch as i32)
+            .with_stderr_contains(
+            "[..]Could not find a version of node matching \"[..]12[..]\" in the local inventory."
+        )
+    );
+}
+
+#[test]
+fn exact_version_not_fetched() {
+    let s = sandbox_with_inventory().build();
+
+    assert_that!(
+        s.volta("install node@12.16.1 --offline"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains(
+                "[..]Could not fetch node@12.16.1 because Volta is in offline mode."
+            )
+    );
+
+    assert!(!Sandbox::node_image_exists("12.16.1"));
+}
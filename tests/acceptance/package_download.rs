@@ -0,0 +1,229 @@
+use crate::support::sandbox::{sandbox, Sandbox};
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use mockito::{mock, Mock};
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const PACKAGE_FIXTURE: &str = "tests/fixtures/npm-1.2.3.tgz";
+const PACKAGE_SHASUM: &str = "ef4bd799face7cf0e7853e8338ce390ccc6418a7";
+const PACKAGE_INTEGRITY: &str =
+    "sha512-r884eoatiKewflK12cn3lMwGhS0fOjpInNn+ngQACCzdyGUJGQ4460xEnBXmC5FzfjuLsrRqmHi8dKdgSQVShA==";
+
+/// Registry metadata for `tarball-test@1.2.3`, with the given `dist` checksums
+fn package_metadata(shasum: &str, integrity: Option<&str>) -> String {
+    let integrity = integrity
+        .map(|integrity| format!(r#", "integrity": "{}""#, integrity))
+        .unwrap_or_default();
+
+    format!(
+        r#"{{
+    "name": "tarball-test",
+    "dist-tags": {{ "latest": "1.2.3" }},
+    "versions": {{
+        "1.2.3": {{
+            "version": "1.2.3",
+            "dist": {{
+                "shasum": "{}",
+                "tarball": "{}/tarball-test/-/tarball-test-1.2.3.tgz"{}
+            }}
+        }}
+    }}
+}}"#,
+        shasum,
+        mockito::SERVER_URL,
+        integrity
+    )
+}
+
+/// Mocks the registry metadata and the tarball for `tarball-test@1.2.3`
+fn mock_package(metadata: &str) -> (Mock, Mock) {
+    let metadata = mock("GET", "/tarball-test")
+        .with_status(200)
+        .with_header("Content-Type", "application/json")
+        .with_body(metadata)
+        .create();
+    let tarball = mock("GET", "/tarball-test/-/tarball-test-1.2.3.tgz")
+        .with_status(200)
+        .with_body_from_file(PACKAGE_FIXTURE)
+        .create();
+
+    (metadata, tarball)
+}
+
+#[test]
+fn fetch_verifies_integrity() {
+    let _mocks = mock_package(&package_metadata(PACKAGE_SHASUM, Some(PACKAGE_INTEGRITY)));
+    let s = sandbox().build();
+
+    assert_that!(
+        s.volta("fetch tarball-test@1.2.3"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert!(Sandbox::pkg_inventory_tarball_exists(
+        "tarball-test",
+        "1.2.3"
+    ));
+    assert!(Sandbox::pkg_inventory_shasum_exists(
+        "tarball-test",
+        "1.2.3"
+    ));
+}
+
+#[test]
+fn fetch_verifies_shasum_without_integrity() {
+    let _mocks = mock_package(&package_metadata(PACKAGE_SHASUM, None));
+    let s = sandbox().build();
+
+    assert_that!(
+        s.volta("fetch tarball-test@1.2.3"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert!(Sandbox::pkg_inventory_tarball_exists(
+        "tarball-test",
+        "1.2.3"
+    ));
+    assert!(Sandbox::pkg_inventory_shasum_exists(
+        "tarball-test",
+        "1.2.3"
+    ));
+}
+
+#[test]
+fn fetch_rejects_integrity_mismatch() {
+    let _mocks = mock_package(&package_metadata(
+        PACKAGE_SHASUM,
+        Some("sha512-AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=="),
+    ));
+    let s = sandbox().build();
+
+    assert_that!(
+        s.volta("fetch tarball-test@1.2.3"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Integrity check failed for tarball-test@1.2.3")
+    );
+
+    assert!(!Sandbox::pkg_inventory_tarball_exists(
+        "tarball-test",
+        "1.2.3"
+    ));
+}
+
+#[test]
+fn fetch_rejects_shasum_mismatch() {
+    let _mocks = mock_package(&package_metadata(
+        "0000000000000000000000000000000000000000",
+        None,
+    ));
+    let s = sandbox().build();
+
+    assert_that!(
+        s.volta("fetch tarball-test@1.2.3"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Integrity check failed for tarball-test@1.2.3")
+    );
+
+    assert!(!Sandbox::pkg_inventory_tarball_exists(
+        "tarball-test",
+        "1.2.3"
+    ));
+}
+
+const PRIVATE_PACKAGE_JSON: &str = r#"{
+    "name": "private-registry"
+}"#;
+
+#[test]
+fn fetch_scoped_package_from_private_registry() {
+    let metadata = format!(
+        r#"{{
+    "name": "@private/tool",
+    "dist-tags": {{ "latest": "1.2.3" }},
+    "versions": {{
+        "1.2.3": {{
+            "version": "1.2.3",
+            "dist": {{
+                "shasum": "{}",
+                "tarball": "{}/private/@private/tool/-/tool-1.2.3.tgz"
+            }}
+        }}
+    }}
+}}"#,
+        PACKAGE_SHASUM,
+        mockito::SERVER_URL
+    );
+    let _metadata = mock("GET", "/private/@private%2ftool")
+        .match_header("Authorization", "Bearer secret-token")
+        .with_status(200)
+        .with_header("Content-Type", "application/json")
+        .with_body(&metadata)
+        .create();
+    let _tarball = mock("GET", "/private/@private/tool/-/tool-1.2.3.tgz")
+        .match_header("Authorization", "Bearer secret-token")
+        .with_status(200)
+        .with_body_from_file(PACKAGE_FIXTURE)
+        .create();
+
+    // Tokens are configured by the registry URL without its protocol
+    let registry = format!("{}/private/", mockito::SERVER_URL);
+    let npmrc = format!(
+        "@private:registry={}\n{}:_authToken=secret-token\n",
+        registry,
+        &registry["http:".len()..]
+    );
+    let s = sandbox()
+        .package_json(PRIVATE_PACKAGE_JSON)
+        .project_file(".npmrc", &npmrc)
+        .build();
+
+    assert_that!(
+        s.volta("fetch @private/tool@1.2.3"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert!(Sandbox::pkg_inventory_tarball_exists(
+        "@private/tool",
+        "1.2.3"
+    ));
+}
+
+#[test]
+fn concurrent_fetches_download_once() {
+    let _metadata = mock("GET", "/tarball-test")
+        .with_status(200)
+        .with_header("Content-Type", "application/json")
+        .with_body(&package_metadata(PACKAGE_SHASUM, Some(PACKAGE_INTEGRITY)))
+        .create();
+    let tarball = mock("GET", "/tarball-test/-/tarball-test-1.2.3.tgz")
+        .with_status(200)
+        .with_body_from_file(PACKAGE_FIXTURE)
+        .expect(1)
+        .create();
+    let s = sandbox().build();
+
+    // The process that waits for the lock should use the image unpacked by the other one
+    let mut first = s
+        .volta("fetch tarball-test@1.2.3")
+        .build_command()
+        .spawn()
+        .expect("could not start the first fetch");
+    let mut second = s
+        .volta("fetch tarball-test@1.2.3")
+        .build_command()
+        .spawn()
+        .expect("could not start the second fetch");
+
+    assert!(first.wait().unwrap().success());
+    assert!(second.wait().unwrap().success());
+
+    tarball.assert();
+    assert!(Sandbox::pkg_inventory_tarball_exists(
+        "tarball-test",
+        "1.2.3"
+    ));
+}
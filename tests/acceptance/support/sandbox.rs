@@ -82,6 +82,7 @@ impl EnvVar {
 pub struct FileBuilder {
     path: PathBuf,
     contents: String,
+    executable: bool,
 }
 
 impl FileBuilder {
@@ -89,9 +90,15 @@ impl FileBuilder {
         FileBuilder {
             path,
             contents: contents.to_string(),
+            executable: false,
         }
     }
 
+    pub fn executable(mut self) -> FileBuilder {
+        self.executable = true;
+        self
+    }
+
     pub fn build(&self) {
         self.dirname().mkdir_p();
 
@@ -99,6 +106,15 @@ impl FileBuilder {
             .unwrap_or_else(|e| panic!("could not create file {}: {}", self.path.display(), e));
 
         ok_or_panic! { file.write_all(self.contents.as_bytes()) };
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            if self.executable {
+                ok_or_panic! { fs::set_permissions(&self.path, fs::Permissions::from_mode(0o755)) };
+            }
+        }
     }
 
     fn dirname(&self) -> &Path {
@@ -118,6 +134,11 @@ pub trait DistroFixture: From<DistroMetadata> {
     fn server_path(&self) -> String;
     fn fixture_path(&self) -> String;
     fn metadata(&self) -> &DistroMetadata;
+
+    /// The server path and fixture path of the checksums published for this distro, if any
+    fn checksum_paths(&self) -> Option<(String, String)> {
+        None
+    }
 }
 
 #[derive(Clone)]
@@ -135,6 +156,10 @@ pub struct NpmFixture {
     pub metadata: DistroMetadata,
 }
 
+pub struct PnpmFixture {
+    pub metadata: DistroMetadata,
+}
+
 pub struct YarnFixture {
     pub metadata: DistroMetadata,
 }
@@ -151,6 +176,12 @@ impl From<DistroMetadata> for NpmFixture {
     }
 }
 
+impl From<DistroMetadata> for PnpmFixture {
+    fn from(metadata: DistroMetadata) -> Self {
+        Self { metadata }
+    }
+}
+
 impl From<DistroMetadata> for YarnFixture {
     fn from(metadata: DistroMetadata) -> Self {
         Self { metadata }
@@ -160,10 +191,7 @@ impl From<DistroMetadata> for YarnFixture {
 impl DistroFixture for NodeFixture {
     fn server_path(&self) -> String {
         let version = &self.metadata.version;
-        format!(
-            "/v{}/node-v{}-{}-{}.{}",
-            version, version, NODE_DISTRO_OS, NODE_DISTRO_ARCH, NODE_DISTRO_EXTENSION
-        )
+        format!("/v{}/{}", version, Node::archive_filename(version))
     }
 
     fn fixture_path(&self) -> String {
@@ -177,6 +205,14 @@ impl DistroFixture for NodeFixture {
     fn metadata(&self) -> &DistroMetadata {
         &self.metadata
     }
+
+    fn checksum_paths(&self) -> Option<(String, String)> {
+        let version = &self.metadata.version;
+        Some((
+            format!("/v{}/SHASUMS256.txt", version),
+            format!("tests/fixtures/node-v{}-SHASUMS256.txt", version),
+        ))
+    }
 }
 
 impl DistroFixture for NpmFixture {
@@ -193,14 +229,43 @@ impl DistroFixture for NpmFixture {
     }
 }
 
+impl DistroFixture for PnpmFixture {
+    fn server_path(&self) -> String {
+        format!("/pnpm/-/pnpm-{}.tgz", self.metadata.version)
+    }
+
+    fn fixture_path(&self) -> String {
+        format!("tests/fixtures/pnpm-{}.tgz", self.metadata.version)
+    }
+
+    fn metadata(&self) -> &DistroMetadata {
+        &self.metadata
+    }
+}
+
+impl YarnFixture {
+    /// Yarn 2+ is distributed through the npm registry, rather than as a classic release
+    fn is_berry(&self) -> bool {
+        !self.metadata.version.starts_with("0.") && !self.metadata.version.starts_with("1.")
+    }
+}
+
 impl DistroFixture for YarnFixture {
     fn server_path(&self) -> String {
         let version = &self.metadata.version;
-        format!("/v{}/yarn-v{}.tar.gz", version, version)
+        if self.is_berry() {
+            format!("/@yarnpkg/cli-dist/-/cli-dist-{}.tgz", version)
+        } else {
+            format!("/v{}/yarn-v{}.tar.gz", version, version)
+        }
     }
 
     fn fixture_path(&self) -> String {
-        format!("tests/fixtures/yarn-v{}.tar.gz", self.metadata.version)
+        if self.is_berry() {
+            format!("tests/fixtures/cli-dist-{}.tgz", self.metadata.version)
+        } else {
+            format!("tests/fixtures/yarn-v{}.tar.gz", self.metadata.version)
+        }
     }
 
     fn metadata(&self) -> &DistroMetadata {
@@ -285,6 +350,18 @@ impl SandboxBuilder {
         self
     }
 
+    /// Setup mock to return the published checksums for a node version (chainable)
+    /// Note: Mocks are matched in reverse order, so this must be created _after_ any distro mocks
+    pub fn node_checksums(mut self, version: &str, body: &str) -> Self {
+        let mock = mock("GET", &format!("/v{}/SHASUMS256.txt", version)[..])
+            .with_status(200)
+            .with_body(body)
+            .create();
+        self.root.mocks.push(mock);
+
+        self
+    }
+
     /// Setup mock to return the available yarn versions (chainable)
     pub fn yarn_available_versions(mut self, body: &str) -> Self {
         let mock = mock("GET", "/yarn-releases/index.json")
@@ -306,6 +383,29 @@ impl SandboxBuilder {
         self
     }
 
+    /// Setup mock to return the available Yarn 2+ versions from the npm registry (chainable)
+    pub fn yarn_berry_available_versions(mut self, body: &str) -> Self {
+        let mock = mock("GET", "/@yarnpkg%2fcli-dist")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(body)
+            .create();
+        self.root.mocks.push(mock);
+        self
+    }
+
+    /// Setup mock to return the available pnpm versions (chainable)
+    pub fn pnpm_available_versions(mut self, body: &str) -> Self {
+        let mock = mock("GET", "/pnpm")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(body)
+            .create();
+        self.root.mocks.push(mock);
+
+        self
+    }
+
     /// Setup mock to return the available npm versions (chainable)
     pub fn npm_available_versions(mut self, body: &str) -> Self {
         let mock = mock("GET", "/npm")
@@ -358,6 +458,13 @@ impl SandboxBuilder {
             .create();
         self.root.mocks.push(file_mock);
 
+        if let Some((checksum_server_path, checksum_fixture_path)) = fx.checksum_paths() {
+            let checksum_mock = mock("GET", &checksum_server_path[..])
+                .with_body_from_file(&checksum_fixture_path)
+                .create();
+            self.root.mocks.push(checksum_mock);
+        }
+
         self
     }
 
@@ -429,6 +536,52 @@ impl SandboxBuilder {
         self
     }
 
+    /// Set an unpacked Node version for the sandbox (chainable)
+    pub fn node_image(mut self, version: &str) -> Self {
+        let readme = node_image_dir(version).join("README.md");
+        self.files.push(FileBuilder::new(readme, "Node.js"));
+        self
+    }
+
+    /// Set an executable in the bin directory of an unpacked Node version (chainable)
+    #[cfg(unix)]
+    pub fn node_image_executable(mut self, version: &str, name: &str, contents: &str) -> Self {
+        let executable = node_image_dir(version).join("bin").join(name);
+        self.files
+            .push(FileBuilder::new(executable, contents).executable());
+        self
+    }
+
+    /// Set a cached Node archive for the sandbox (chainable)
+    pub fn node_inventory(mut self, version: &str) -> Self {
+        let archive = node_inventory_dir().join(Node::archive_filename(version));
+        self.files
+            .push(FileBuilder::new(archive, "archive contents"));
+        self
+    }
+
+    /// Set an unpacked pnpm version for the sandbox (chainable)
+    pub fn pnpm_image(mut self, version: &str) -> Self {
+        let readme = pnpm_image_dir(version).join("README.md");
+        self.files.push(FileBuilder::new(readme, "pnpm"));
+        self
+    }
+
+    /// Set an unpacked Yarn version for the sandbox (chainable)
+    pub fn yarn_image(mut self, version: &str) -> Self {
+        let readme = yarn_image_dir(version).join("README.md");
+        self.files.push(FileBuilder::new(readme, "Yarn"));
+        self
+    }
+
+    /// Set a cached Yarn archive for the sandbox (chainable)
+    pub fn yarn_inventory(mut self, version: &str) -> Self {
+        let archive = yarn_inventory_dir().join(Yarn::archive_filename(version));
+        self.files
+            .push(FileBuilder::new(archive, "archive contents"));
+        self
+    }
+
     /// Write the "default npm" file for a node version (chainable)
     pub fn node_npm_version_file(mut self, node_version: &str, npm_version: &str) -> Self {
         let npm_file = node_npm_version_file(node_version);
@@ -451,8 +604,9 @@ impl SandboxBuilder {
         ok_or_panic! { fs::create_dir_all(yarn_inventory_dir()) };
         ok_or_panic! { fs::create_dir_all(volta_tmp_dir()) };
 
-        // Make sure the shims to npm and yarn exist
+        // Make sure the shims to npm, pnpm, and yarn exist
         ok_or_panic! { symlink_file(shim_exe(), self.root.npm_exe()) };
+        ok_or_panic! { symlink_file(shim_exe(), self.root.pnpm_exe()) };
         ok_or_panic! { symlink_file(shim_exe(), self.root.yarn_exe()) };
 
         // write node and yarn caches
@@ -548,6 +702,15 @@ fn shim_file(name: &str) -> PathBuf {
 fn package_image_dir(name: &str, version: &str) -> PathBuf {
     image_dir().join("packages").join(name).join(version)
 }
+fn node_image_dir(version: &str) -> PathBuf {
+    image_dir().join("node").join(version)
+}
+fn pnpm_image_dir(version: &str) -> PathBuf {
+    image_dir().join("pnpm").join(version)
+}
+fn yarn_image_dir(version: &str) -> PathBuf {
+    image_dir().join("yarn").join(version)
+}
 fn default_platform_file() -> PathBuf {
     user_dir().join("platform.json")
 }
@@ -632,6 +795,20 @@ impl Sandbox {
         self.root().join(format!("npm{}", env::consts::EXE_SUFFIX))
     }
 
+    /// Create a `ProcessBuilder` to run the volta pnpm shim.
+    /// Arguments can be separated by spaces.
+    /// Example:
+    ///     assert_that(p.pnpm("add ember-cli"), execs());
+    pub fn pnpm(&self, cmd: &str) -> ProcessBuilder {
+        let mut p = self.process(&self.pnpm_exe());
+        split_and_add_args(&mut p, cmd);
+        p
+    }
+
+    pub fn pnpm_exe(&self) -> PathBuf {
+        self.root().join(format!("pnpm{}", env::consts::EXE_SUFFIX))
+    }
+
     /// Create a `ProcessBuilder` to run the volta yarn shim.
     /// Arguments can be separated by spaces.
     /// Example:
@@ -667,6 +844,12 @@ impl Sandbox {
             .exists()
     }
 
+    pub fn node_inventory_checksum_exists(&self, version: &str) -> bool {
+        node_inventory_dir()
+            .join(format!("{}.sha256", Node::archive_filename(version)))
+            .exists()
+    }
+
     pub fn yarn_inventory_archive_exists(&self, version: &str) -> bool {
         yarn_inventory_dir()
             .join(Yarn::archive_filename(version))
@@ -701,6 +884,21 @@ impl Sandbox {
             .join(format!("{}-{}.shasum", name, version))
             .exists()
     }
+    pub fn node_image_exists(version: &str) -> bool {
+        node_image_dir(version).exists()
+    }
+    pub fn node_index_cache_exists() -> bool {
+        node_index_file().exists()
+    }
+    pub fn node_npm_version_file_exists(version: &str) -> bool {
+        node_npm_version_file(version).exists()
+    }
+    pub fn pnpm_image_exists(version: &str) -> bool {
+        pnpm_image_dir(version).exists()
+    }
+    pub fn yarn_image_exists(version: &str) -> bool {
+        yarn_image_dir(version).exists()
+    }
     pub fn read_default_platform() -> String {
         read_file_to_string(default_platform_file())
     }
@@ -0,0 +1,134 @@
+use crate::support::sandbox::{sandbox, Sandbox};
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const PKG_CONFIG_COWSAY: &str = r#"{
+  "name": "cowsay",
+  "version": "1.4.0",
+  "platform": {
+    "node": {
+      "runtime": "11.10.1",
+      "npm": "6.7.0"
+    },
+    "yarn": null
+  },
+  "bins": [
+    "cowsay"
+  ]
+}"#;
+
+const BIN_CONFIG_COWSAY: &str = r#"{
+  "name": "cowsay",
+  "package": "cowsay",
+  "version": "1.4.0",
+  "path": "./cli.js",
+  "platform": {
+    "node": {
+      "runtime": "11.10.1",
+      "npm": "6.7.0"
+    },
+    "yarn": null
+  }
+}"#;
+
+const VOLTA_LOGLEVEL: &str = "VOLTA_LOGLEVEL";
+
+#[test]
+fn doctor_finds_no_problems() {
+    let s = sandbox()
+        .layout_file("v2")
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("doctor"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]No problems found")
+    );
+}
+
+#[test]
+fn doctor_reports_outdated_layout() {
+    let s = sandbox()
+        .layout_file("v1")
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("doctor"),
+        execs()
+            .with_status(ExitCode::EnvironmentError as i32)
+            .with_stderr_contains("[..]The Volta directory uses layout v1, which is out of date")
+            .with_stdout_contains("[..]Run `volta doctor --fix` to repair 1 of these problems")
+    );
+}
+
+#[test]
+fn doctor_reports_missing_package_executables() {
+    let s = sandbox()
+        .layout_file("v2")
+        .package_config("cowsay", PKG_CONFIG_COWSAY)
+        .binary_config("cowsay", BIN_CONFIG_COWSAY)
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("doctor --fix"),
+        execs()
+            .with_status(ExitCode::EnvironmentError as i32)
+            .with_stderr_contains("[..]cowsay@1.4.0 is missing its 'cowsay' executable")
+            .with_stderr_contains("[..]Run `volta install cowsay@1.4.0` to reinstall it.")
+    );
+}
+
+#[test]
+fn doctor_fix_removes_orphaned_shims() {
+    let s = sandbox()
+        .layout_file("v2")
+        .shim("cowsay")
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("doctor"),
+        execs()
+            .with_status(ExitCode::EnvironmentError as i32)
+            .with_stderr_contains("[..]The shim for 'cowsay' does not belong to any package")
+    );
+    assert!(Sandbox::shim_exists("cowsay"));
+
+    assert_that!(
+        s.volta("doctor --fix"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains(
+                "[..]Fixed: The shim for 'cowsay' does not belong to any package"
+            )
+    );
+    assert!(!Sandbox::shim_exists("cowsay"));
+}
+
+#[test]
+fn doctor_fix_removes_shims_with_unreadable_config() {
+    let s = sandbox()
+        .layout_file("v2")
+        .binary_config("cowsay", "not json")
+        .shim("cowsay")
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("doctor --fix"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains(
+                "[..]Fixed: The configuration for the 'cowsay' shim can't be read"
+            )
+    );
+    assert!(!Sandbox::shim_exists("cowsay"));
+    assert!(!Sandbox::bin_config_exists("cowsay"));
+}
@@ -0,0 +1,44 @@
+use crate::support::sandbox::sandbox;
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+#[test]
+fn env_without_platform_unsets_versions() {
+    let s = sandbox().build();
+
+    assert_that!(
+        s.volta("env --shell bash"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("export PATH=[..]")
+            .with_stdout_contains("unset VOLTA_NODE_VERSION;")
+            .with_stdout_contains("unset VOLTA_PLATFORM_SOURCE;")
+    );
+}
+
+#[test]
+fn env_hook_prints_shell_function() {
+    let s = sandbox().build();
+
+    assert_that!(
+        s.volta("env --shell zsh --hook"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("add-zsh-hook chpwd _volta_hook")
+    );
+}
+
+#[test]
+fn env_hook_requires_shell() {
+    let s = sandbox().build();
+
+    assert_that!(
+        s.volta("env --shell json --hook"),
+        execs()
+            .with_status(ExitCode::InvalidArguments as i32)
+            .with_stderr_contains("[..]Could not print a hook for json.")
+    );
+}
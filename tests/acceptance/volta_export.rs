@@ -0,0 +1,61 @@
+use crate::support::sandbox::sandbox;
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const PLATFORM_WITH_YARN: &str = r#"{
+  "node": {
+    "runtime": "10.99.1040",
+    "npm": null
+  },
+  "yarn": "1.2.42"
+}"#;
+
+const PKG_CONFIG_COWSAY: &str = r#"{
+  "name": "cowsay",
+  "version": "1.4.0",
+  "platform": {
+    "node": {
+      "runtime": "11.10.1",
+      "npm": "6.7.0"
+    },
+    "yarn": null
+  },
+  "bins": [
+    "cowsay"
+  ]
+}"#;
+
+#[test]
+fn exports_platform_and_packages() {
+    let s = sandbox()
+        .platform(PLATFORM_WITH_YARN)
+        .package_config("cowsay", PKG_CONFIG_COWSAY)
+        .build();
+
+    assert_that!(
+        s.volta("export"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]\"runtime\": \"10.99.1040\",")
+            .with_stdout_contains("[..]\"yarn\": \"1.2.42\"")
+            .with_stdout_contains("[..]\"name\": \"cowsay\",")
+            .with_stdout_contains("[..]\"version\": \"1.4.0\",")
+            .with_stdout_contains("[..]\"node\": \"11.10.1\"")
+    );
+}
+
+#[test]
+fn exports_empty_toolchain() {
+    let s = sandbox().build();
+
+    assert_that!(
+        s.volta("export"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]\"node\": null,")
+            .with_stdout_contains("[..]\"packages\": []")
+    );
+}
@@ -0,0 +1,258 @@
+use crate::support::sandbox::{sandbox, DistroMetadata, NodeFixture, Sandbox};
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const NODE_VERSION_INFO: &str = r#"[
+{"version":"v10.99.1040","npm":"6.2.26","lts": "Dubnium","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
+{"version":"v9.27.6","npm":"5.6.17","lts": false,"files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
+{"version":"v8.9.10","npm":"5.6.7","lts": false,"files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
+{"version":"v6.19.62","npm":"3.10.1066","lts": false,"files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]}
+]
+"#;
+
+const PLATFORM_NODE_ONLY: &str = r#"{
+  "node": {
+    "runtime": "10.99.1040",
+    "npm": null
+  },
+  "yarn": null
+}"#;
+
+const TOOLCHAIN_NODE_ONLY: &str = r#"{
+  "platform": {
+    "node": {
+      "runtime": "10.99.1040",
+      "npm": null
+    },
+    "yarn": null
+  },
+  "packages": []
+}"#;
+
+const PKG_CONFIG_COWSAY: &str = r#"{
+  "name": "cowsay",
+  "version": "1.4.0",
+  "platform": {
+    "node": {
+      "runtime": "10.99.1040",
+      "npm": null
+    },
+    "yarn": null
+  },
+  "bins": [
+    "cowsay"
+  ]
+}"#;
+
+const BIN_CONFIG_COWSAY: &str = r#"{
+  "name": "cowsay",
+  "package": "cowsay",
+  "version": "1.4.0",
+  "path": "./cli.js",
+  "platform": {
+    "node": {
+      "runtime": "10.99.1040",
+      "npm": null
+    },
+    "yarn": null
+  }
+}"#;
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "macos")] {
+        const NODE_VERSION_FIXTURES: [DistroMetadata; 4] = [
+            DistroMetadata {
+                version: "10.99.1040",
+                compressed_size: 273,
+                uncompressed_size: Some(0x0028_0000),
+            },
+            DistroMetadata {
+                version: "9.27.6",
+                compressed_size: 272,
+                uncompressed_size: Some(0x0028_0000),
+            },
+            DistroMetadata {
+                version: "8.9.10",
+                compressed_size: 272,
+                uncompressed_size: Some(0x0028_0000),
+            },
+            DistroMetadata {
+                version: "6.19.62",
+                compressed_size: 273,
+                uncompressed_size: Some(0x0028_0000),
+            },
+        ];
+    } else if #[cfg(target_os = "linux")] {
+        const NODE_VERSION_FIXTURES: [DistroMetadata; 4] = [
+            DistroMetadata {
+                version: "10.99.1040",
+                compressed_size: 273,
+                uncompressed_size: Some(0x0028_0000),
+            },
+            DistroMetadata {
+                version: "9.27.6",
+                compressed_size: 272,
+                uncompressed_size: Some(0x0028_0000),
+            },
+            DistroMetadata {
+                version: "8.9.10",
+                compressed_size: 270,
+                uncompressed_size: Some(0x0028_0000),
+            },
+            DistroMetadata {
+                version: "6.19.62",
+                compressed_size: 273,
+                uncompressed_size: Some(0x0028_0000),
+            },
+        ];
+    } else if #[cfg(target_os = "windows")] {
+        const NODE_VERSION_FIXTURES: [DistroMetadata; 4] = [
+            DistroMetadata {
+                version: "10.99.1040",
+                compressed_size: 1096,
+                uncompressed_size: None,
+            },
+            DistroMetadata {
+                version: "9.27.6",
+                compressed_size: 1068,
+                uncompressed_size: None,
+            },
+            DistroMetadata {
+                version: "8.9.10",
+                compressed_size: 1055,
+                uncompressed_size: None,
+            },
+            DistroMetadata {
+                version: "6.19.62",
+                compressed_size: 1056,
+                uncompressed_size: None,
+            },
+        ];
+    } else {
+        compile_error!("Unsupported target_os for tests (expected 'macos', 'linux', or 'windows').");
+    }
+}
+
+#[test]
+fn import_installs_platform() {
+    let s = sandbox()
+        .project_file("toolchain.json", TOOLCHAIN_NODE_ONLY)
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("import toolchain.json"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert!(Sandbox::node_image_exists("10.99.1040"));
+    assert_eq!(Sandbox::read_default_platform(), PLATFORM_NODE_ONLY);
+}
+
+#[test]
+fn import_skips_existing_toolchain() {
+    let s = sandbox()
+        .project_file("toolchain.json", TOOLCHAIN_NODE_ONLY)
+        .platform(PLATFORM_NODE_ONLY)
+        .node_image("10.99.1040")
+        .env("VOLTA_LOGLEVEL", "info")
+        .build();
+
+    assert_that!(
+        s.volta("import toolchain.json"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]Imported the toolchain from toolchain.json")
+            .with_stdout_does_not_contain("[..]installed and set[..]")
+    );
+}
+
+#[test]
+fn import_prune_removes_unlisted_packages() {
+    let s = sandbox()
+        .project_file("toolchain.json", TOOLCHAIN_NODE_ONLY)
+        .platform(PLATFORM_NODE_ONLY)
+        .node_image("10.99.1040")
+        .package_config("cowsay", PKG_CONFIG_COWSAY)
+        .binary_config("cowsay", BIN_CONFIG_COWSAY)
+        .shim("cowsay")
+        .build();
+
+    assert_that!(
+        s.volta("import --prune toolchain.json"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert!(!Sandbox::package_config_exists("cowsay"));
+    assert!(!Sandbox::shim_exists("cowsay"));
+}
+
+#[test]
+fn import_keeps_unlisted_packages_without_prune() {
+    let s = sandbox()
+        .project_file("toolchain.json", TOOLCHAIN_NODE_ONLY)
+        .platform(PLATFORM_NODE_ONLY)
+        .node_image("10.99.1040")
+        .package_config("cowsay", PKG_CONFIG_COWSAY)
+        .binary_config("cowsay", BIN_CONFIG_COWSAY)
+        .shim("cowsay")
+        .build();
+
+    assert_that!(
+        s.volta("import toolchain.json"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert!(Sandbox::package_config_exists("cowsay"));
+}
+
+const TOOLCHAIN_MISSING_PACKAGE: &str = r#"{
+  "platform": {
+    "node": {
+      "runtime": "10.99.1040",
+      "npm": null
+    },
+    "yarn": null
+  },
+  "packages": [
+    {
+      "name": "missing-package",
+      "version": "1.0.0",
+      "node": "10.99.1040"
+    }
+  ]
+}"#;
+
+#[test]
+fn import_warns_about_failed_fetches() {
+    let s = sandbox()
+        .project_file("toolchain.json", TOOLCHAIN_MISSING_PACKAGE)
+        .platform(PLATFORM_NODE_ONLY)
+        .node_image("10.99.1040")
+        .build();
+
+    assert_that!(
+        s.volta("import toolchain.json"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Could not fetch missing-package@1.0.0[..]")
+    );
+}
+
+#[test]
+fn import_invalid_file() {
+    let s = sandbox()
+        .project_file("toolchain.json", "not a toolchain")
+        .build();
+
+    assert_that!(
+        s.volta("import toolchain.json"),
+        execs()
+            .with_status(ExitCode::ConfigurationError as i32)
+            .with_stderr_contains("[..]Could not parse toolchain file")
+    );
+}
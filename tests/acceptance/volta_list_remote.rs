@@ -0,0 +1,123 @@
+use crate::support::sandbox::sandbox;
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+const NODE_VERSION_INFO: &str = r#"[
+{"version":"v10.99.1040","npm":"6.2.26","lts": "Dubnium","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
+{"version":"v9.27.6","npm":"5.6.17","lts": false,"files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
+{"version":"v8.9.10","npm":"5.6.7","lts": "Carbon","files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
+{"version":"v6.19.62","npm":"3.10.1066","lts": false,"files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]}
+]
+"#;
+
+const PLATFORM: &str = r#"{
+  "node": {
+    "runtime": "9.27.6",
+    "npm": null
+  }
+}"#;
+
+const PACKAGE_JSON_WITH_PINNED_NODE: &str = r#"{
+  "name": "test-package",
+  "volta": {
+    "node": "8.9.10"
+  }
+}"#;
+
+#[test]
+fn lists_node_versions() {
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .platform(PLATFORM)
+        .node_image("9.27.6")
+        .build();
+
+    assert_that!(
+        s.volta("list-remote node"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("Available versions of node:")
+            .with_stdout_contains("    node@10.99.1040 (lts/dubnium)")
+            .with_stdout_contains("    node@9.27.6 [fetched, default]")
+            .with_stdout_contains("    node@6.19.62")
+    );
+}
+
+#[test]
+fn filters_lts_and_range() {
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .package_json(PACKAGE_JSON_WITH_PINNED_NODE)
+        .build();
+
+    assert_that!(
+        s.volta("list-remote node --lts <10"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("    node@8.9.10 (lts/carbon) [current @ [..]package.json]")
+            .with_stdout_does_not_contain("[..]node@10.99.1040[..]")
+            .with_stdout_does_not_contain("[..]node@9.27.6[..]")
+    );
+}
+
+#[test]
+fn rejects_lts_for_other_tools() {
+    let s = sandbox().build();
+
+    assert_that!(
+        s.volta("list-remote yarn --lts"),
+        execs()
+            .with_status(ExitCode::InvalidArguments as i32)
+            .with_stderr_contains("[..]Only Node has long-term support releases")
+    );
+}
+
+#[test]
+fn prints_json() {
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .platform(PLATFORM)
+        .build();
+
+    assert_that!(
+        s.volta("list-remote node ^9 --format json"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("[..]\"schemaVersion\": 1,")
+            .with_stdout_contains("[..]\"version\": \"9.27.6\",")
+            .with_stdout_contains("[..]\"default\": true,")
+            .with_stdout_contains("[..]\"pinned\": null")
+    );
+}
+
+#[test]
+fn requires_network() {
+    let s = sandbox().env("VOLTA_OFFLINE", "1").build();
+
+    assert_that!(
+        s.volta("list-remote node"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains(
+            "[..]Could not fetch the available versions of node because Volta is in offline mode."
+        )
+    );
+}
+
+#[test]
+fn uses_network_when_offline_is_disabled() {
+    let s = sandbox()
+        .node_available_versions(NODE_VERSION_INFO)
+        .env("VOLTA_OFFLINE", "0")
+        .build();
+
+    assert_that!(
+        s.volta("list-remote node"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("    node@10.99.1040 (lts/dubnium)")
+    );
+}
@@ -1,6 +1,9 @@
-use crate::support::sandbox::{sandbox, DistroMetadata, NodeFixture, NpmFixture, YarnFixture};
+use crate::support::sandbox::{
+    sandbox, DistroMetadata, NodeFixture, NpmFixture, PnpmFixture, Sandbox, YarnFixture,
+};
 use hamcrest2::assert_that;
 use hamcrest2::prelude::*;
+use mockito::mock;
 use test_support::matchers::execs;
 
 use volta_core::error::ExitCode;
@@ -45,6 +48,19 @@ fn package_json_with_pinned_node_npm(node: &str, npm: &str) -> String {
     )
 }
 
+fn package_json_with_pinned_node_pnpm(node: &str, pnpm: &str) -> String {
+    format!(
+        r#"{{
+  "name": "test-package",
+  "volta": {{
+    "node": "{}",
+    "pnpm": "{}"
+  }}
+}}"#,
+        node, pnpm
+    )
+}
+
 fn package_json_with_pinned_node_yarn(node_version: &str, yarn_version: &str) -> String {
     format!(
         r#"{{
@@ -84,6 +100,12 @@ const NODE_VERSION_INFO: &str = r#"[
 ]
 "#;
 
+const NODE_RC_VERSION_INFO: &str = r#"[
+{"version":"v11.0.0-rc.2","npm":"6.4.1","lts": false,"files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]},
+{"version":"v11.0.0-rc.1","npm":"6.4.1","lts": false,"files":["linux-x64","osx-x64-tar","win-x64-zip","win-x86-zip", "linux-arm64"]}
+]
+"#;
+
 cfg_if::cfg_if! {
     if #[cfg(target_os = "macos")] {
         const NODE_VERSION_FIXTURES: [DistroMetadata; 4] = [
@@ -220,6 +242,23 @@ const NPM_VERSION_INFO: &str = r#"
 }
 "#;
 
+const PNPM_VERSION_FIXTURES: [DistroMetadata; 1] = [DistroMetadata {
+    version: "5.18.9",
+    compressed_size: 265,
+    uncompressed_size: Some(0x0028_0000),
+}];
+
+const PNPM_VERSION_INFO: &str = r#"
+{
+    "name":"pnpm",
+    "dist-tags": { "latest":"5.18.9" },
+    "versions": {
+        "4.14.4": { "version":"4.14.4", "dist": { "shasum":"", "tarball":"" }},
+        "5.18.9": { "version":"5.18.9", "dist": { "shasum":"", "tarball":"" }}
+    }
+}
+"#;
+
 const VOLTA_LOGLEVEL: &str = "VOLTA_LOGLEVEL";
 
 #[test]
@@ -296,6 +335,48 @@ fn pin_node_no_version() {
     )
 }
 
+#[test]
+fn pin_node_codename() {
+    let s = sandbox()
+        .package_json(BASIC_PACKAGE_JSON)
+        .node_available_versions(NODE_VERSION_INFO)
+        .distro_mocks::<NodeFixture>(&NODE_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("pin node@dubnium"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert_eq!(
+        s.read_package_json(),
+        package_json_with_pinned_node("10.99.1040"),
+    )
+}
+
+#[test]
+fn pin_node_rc_requires_allow_prerelease() {
+    let _rc_index = mock("GET", "/node-dist/rc/index.json")
+        .with_status(200)
+        .with_header("Content-Type", "application/json")
+        .with_body(NODE_RC_VERSION_INFO)
+        .create();
+    let s = sandbox().package_json(BASIC_PACKAGE_JSON).build();
+
+    assert_that!(
+        s.volta("pin node@rc"),
+        execs()
+            .with_status(ExitCode::InvalidArguments as i32)
+            .with_stderr_contains(
+                "[..]Could not pin node@11.0.0-rc.2, because it is a prerelease."
+            )
+    );
+
+    assert_eq!(s.read_package_json(), BASIC_PACKAGE_JSON);
+    // Channel indexes aren't cached, so they can't replace the cache of the main index
+    assert!(!Sandbox::node_index_cache_exists());
+}
+
 #[test]
 fn pin_node_informs_newer_npm() {
     let s = sandbox()
@@ -612,6 +693,105 @@ fn pin_npm_bundled_reports_info() {
     );
 }
 
+#[test]
+fn pin_pnpm_no_node() {
+    let s = sandbox()
+        .package_json(BASIC_PACKAGE_JSON)
+        .pnpm_available_versions(PNPM_VERSION_INFO)
+        .distro_mocks::<PnpmFixture>(&PNPM_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("pin pnpm@5"),
+        execs()
+            .with_status(ExitCode::ConfigurationError as i32)
+            .with_stderr_contains(
+                "[..]Cannot pin pnpm because the Node version is not pinned in this project."
+            )
+    );
+
+    assert_eq!(s.read_package_json(), BASIC_PACKAGE_JSON)
+}
+
+#[test]
+fn pin_pnpm() {
+    let s = sandbox()
+        .package_json(&package_json_with_pinned_node("1.2.3"))
+        .pnpm_available_versions(PNPM_VERSION_INFO)
+        .distro_mocks::<PnpmFixture>(&PNPM_VERSION_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("pin pnpm@5"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert_eq!(
+        s.read_package_json(),
+        package_json_with_pinned_node_pnpm("1.2.3", "5.18.9"),
+    );
+    assert!(Sandbox::pnpm_image_exists("5.18.9"));
+}
+
+#[test]
+fn pin_pnpm_missing_release() {
+    let s = sandbox()
+        .package_json(&package_json_with_pinned_node("1.2.3"))
+        .mock_not_found()
+        .build();
+
+    assert_that!(
+        s.volta("pin pnpm@5.18.9"),
+        execs()
+            .with_status(ExitCode::NetworkError as i32)
+            .with_stderr_contains("[..]Could not download pnpm@5.18.9")
+    );
+
+    assert_eq!(
+        s.read_package_json(),
+        package_json_with_pinned_node("1.2.3"),
+    );
+}
+
+#[test]
+fn pin_pnpm_from_npmrc_registry() {
+    let mirror = format!("{}/mirror", mockito::SERVER_URL);
+    let _mirror_index = mock("GET", "/mirror/pnpm")
+        .match_header("Authorization", "Bearer mirror-token")
+        .with_status(200)
+        .with_header("Content-Type", "application/json")
+        .with_body(PNPM_VERSION_INFO)
+        .create();
+    let mirror_tarball = mock("GET", "/mirror/pnpm/-/pnpm-5.18.9.tgz")
+        .match_header("Authorization", "Bearer mirror-token")
+        .with_status(200)
+        .with_body_from_file("tests/fixtures/pnpm-5.18.9.tgz")
+        .create();
+
+    // Tokens are configured by the registry URL without its protocol
+    let npmrc = format!(
+        "registry={}/\n{}/:_authToken=mirror-token\n",
+        mirror,
+        &mirror["http:".len()..]
+    );
+    let s = sandbox()
+        .package_json(&package_json_with_pinned_node("1.2.3"))
+        .project_file(".npmrc", &npmrc)
+        .build();
+
+    assert_that!(
+        s.volta("pin pnpm@5"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert_eq!(
+        s.read_package_json(),
+        package_json_with_pinned_node_pnpm("1.2.3", "5.18.9"),
+    );
+    mirror_tarball.assert();
+    assert!(Sandbox::pnpm_image_exists("5.18.9"));
+}
+
 #[test]
 fn pin_node_and_yarn() {
     let s = sandbox()
@@ -0,0 +1,147 @@
+use crate::support::sandbox::{sandbox, Sandbox};
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use test_support::matchers::execs;
+
+const PLATFORM: &str = r#"{
+  "node": {
+    "runtime": "10.99.1040",
+    "npm": null
+  },
+  "yarn": "1.2.42"
+}"#;
+
+const PKG_CONFIG_COWSAY: &str = r#"{
+  "name": "cowsay",
+  "version": "1.4.0",
+  "platform": {
+    "node": {
+      "runtime": "11.10.1",
+      "npm": "6.7.0"
+    },
+    "yarn": null
+  },
+  "bins": [
+    "cowsay"
+  ]
+}"#;
+
+const PROJECT_PACKAGE_JSON: &str = r#"
+{
+    "name": "test-package",
+    "volta": {
+        "node": "9.27.6"
+    }
+}"#;
+
+const VOLTA_LOGLEVEL: &str = "VOLTA_LOGLEVEL";
+
+fn sandbox_with_unused_tools() -> Sandbox {
+    sandbox()
+        .platform(PLATFORM)
+        .package_config("cowsay", PKG_CONFIG_COWSAY)
+        .package_json(PROJECT_PACKAGE_JSON)
+        .node_image("10.99.1040")
+        .node_image("11.10.1")
+        .node_image("9.27.6")
+        .node_image("8.9.10")
+        .node_inventory("8.9.10")
+        .node_npm_version_file("8.9.10", "5.6.7")
+        .yarn_image("1.2.42")
+        .yarn_image("1.7.71")
+        .package_image("cowsay", "1.4.0")
+        .package_image("cowsay", "1.0.0")
+        .package_inventory("cowsay", "1.0.0")
+        .env(VOLTA_LOGLEVEL, "info")
+        .build()
+}
+
+#[test]
+fn prune_dry_run_removes_nothing() {
+    let s = sandbox_with_unused_tools();
+
+    assert_that!(
+        s.volta("prune --dry-run"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("Would remove node@8.9.10 ([..])")
+            .with_stdout_contains("Would remove yarn@1.7.71 ([..])")
+            .with_stdout_contains("Would remove cowsay@1.0.0 ([..])")
+            .with_stdout_contains("Pruning would reclaim [..]")
+    );
+
+    assert!(Sandbox::node_image_exists("8.9.10"));
+    assert!(s.node_inventory_archive_exists("8.9.10"));
+    assert!(Sandbox::yarn_image_exists("1.7.71"));
+    assert!(Sandbox::package_image_exists("cowsay", "1.0.0"));
+}
+
+#[test]
+fn prune_keeps_reachable_tools() {
+    let s = sandbox_with_unused_tools();
+
+    assert_that!(
+        s.volta("prune"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("Removed node@8.9.10 ([..])")
+            .with_stdout_contains("[..]pruned unused tools, reclaiming [..]")
+    );
+
+    // Unreachable tools are removed along with their cached archives
+    assert!(!Sandbox::node_image_exists("8.9.10"));
+    assert!(!s.node_inventory_archive_exists("8.9.10"));
+    assert!(!Sandbox::node_npm_version_file_exists("8.9.10"));
+    assert!(!Sandbox::yarn_image_exists("1.7.71"));
+    assert!(!Sandbox::package_image_exists("cowsay", "1.0.0"));
+    assert!(!Sandbox::pkg_inventory_tarball_exists("cowsay", "1.0.0"));
+
+    // The default platform, the installed package and its platform, and the project are kept
+    assert!(Sandbox::node_image_exists("10.99.1040"));
+    assert!(Sandbox::yarn_image_exists("1.2.42"));
+    assert!(Sandbox::node_image_exists("11.10.1"));
+    assert!(Sandbox::package_image_exists("cowsay", "1.4.0"));
+    assert!(Sandbox::node_image_exists("9.27.6"));
+}
+
+#[test]
+fn prune_removes_archives_without_images() {
+    let s = sandbox()
+        .platform(PLATFORM)
+        .node_image("10.99.1040")
+        .node_inventory("10.99.1040")
+        .node_inventory("6.19.62")
+        .yarn_image("1.2.42")
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("prune"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("Removed node@6.19.62 ([..])")
+    );
+
+    assert!(!s.node_inventory_archive_exists("6.19.62"));
+    assert!(s.node_inventory_archive_exists("10.99.1040"));
+}
+
+#[test]
+fn prune_with_nothing_to_remove() {
+    let s = sandbox()
+        .platform(PLATFORM)
+        .node_image("10.99.1040")
+        .yarn_image("1.2.42")
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("prune"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("Nothing to prune, all fetched tools are in use")
+    );
+
+    assert!(Sandbox::node_image_exists("10.99.1040"));
+    assert!(Sandbox::yarn_image_exists("1.2.42"));
+}
@@ -3,6 +3,8 @@ use hamcrest2::assert_that;
 use hamcrest2::prelude::*;
 use test_support::matchers::execs;
 
+use volta_core::error::ExitCode;
+
 const PKG_CONFIG_BASIC: &str = r#"{
   "name": "cowsay",
   "version": "1.4.0",
@@ -51,6 +53,56 @@ fn bin_config(name: &str) -> String {
     )
 }
 
+const PKG_CONFIG_PNPM: &str = r#"{
+  "name": "pnpm",
+  "version": "7.7.1",
+  "platform": {
+    "node": {
+      "runtime": "16.11.1",
+      "npm": null
+    },
+    "yarn": null
+  },
+  "bins": [
+    "pnpm",
+    "pnpx"
+  ]
+}"#;
+
+fn pnpm_bin_config(name: &str) -> String {
+    format!(
+        r#"{{
+  "name": "{}",
+  "package": "pnpm",
+  "version": "7.7.1",
+  "path": "./bin/{}.cjs",
+  "platform": {{
+    "node": {{
+      "runtime": "16.11.1",
+      "npm": null
+    }},
+    "yarn": null
+  }}
+}}"#,
+        name, name
+    )
+}
+
+const PLATFORM_NODE_ONLY: &str = r#"{
+  "node": {
+    "runtime": "10.99.1040",
+    "npm": null
+  }
+}"#;
+
+const PLATFORM_WITH_YARN: &str = r#"{
+  "node": {
+    "runtime": "10.99.1040",
+    "npm": null
+  },
+  "yarn": "1.2.42"
+}"#;
+
 const VOLTA_LOGLEVEL: &str = "VOLTA_LOGLEVEL";
 
 #[test]
@@ -194,3 +246,158 @@ fn uninstall_package_orphaned_bins() {
     assert!(!Sandbox::shim_exists("cowsay"));
     assert!(!Sandbox::shim_exists("cowthink"));
 }
+
+#[test]
+fn uninstall_pnpm_package_without_version() {
+    // pnpm installed as a package, before it was a supported tool, is removed by a bare `pnpm`
+    let s = sandbox()
+        .package_config("pnpm", PKG_CONFIG_PNPM)
+        .binary_config("pnpm", &pnpm_bin_config("pnpm"))
+        .binary_config("pnpx", &pnpm_bin_config("pnpx"))
+        .shim("pnpm")
+        .shim("pnpx")
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("uninstall pnpm"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("Removed executable 'pnpm' installed by 'pnpm'")
+            .with_stdout_contains("Removed executable 'pnpx' installed by 'pnpm'")
+            .with_stdout_contains("[..]package 'pnpm' uninstalled")
+    );
+
+    assert!(!Sandbox::package_config_exists("pnpm"));
+    assert!(!Sandbox::bin_config_exists("pnpm"));
+    assert!(!Sandbox::bin_config_exists("pnpx"));
+    assert!(!Sandbox::shim_exists("pnpx"));
+    // the default pnpm shim is restored on Unix
+    #[cfg(unix)]
+    assert!(Sandbox::shim_exists("pnpm"));
+}
+
+#[test]
+fn uninstall_pnpm_requires_version() {
+    let s = sandbox().platform(PLATFORM_NODE_ONLY).build();
+
+    assert_that!(
+        s.volta("uninstall pnpm"),
+        execs()
+            .with_status(ExitCode::InvalidArguments as i32)
+            .with_stderr_contains("[..]Could not determine which version of pnpm to uninstall.")
+    );
+}
+
+#[test]
+fn uninstall_node_basic() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .node_image("8.9.10")
+        .node_inventory("8.9.10")
+        .node_npm_version_file("8.9.10", "5.6.7")
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("uninstall node@8.9.10"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("[..]node@8.9.10 uninstalled")
+    );
+
+    assert!(!Sandbox::node_image_exists("8.9.10"));
+    assert!(!s.node_inventory_archive_exists("8.9.10"));
+    assert!(!Sandbox::node_npm_version_file_exists("8.9.10"));
+}
+
+#[test]
+fn uninstall_node_warns_about_packages() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .package_config("cowsay", PKG_CONFIG_BASIC)
+        .node_image("11.10.1")
+        .node_npm_version_file("11.10.1", "6.7.0")
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("uninstall node@11.10.1"),
+        execs()
+            .with_status(0)
+            .with_stderr_contains("[..]node@11.10.1 was used to install cowsay[..]")
+            .with_stdout_contains("[..]node@11.10.1 uninstalled")
+    );
+
+    assert!(!Sandbox::node_image_exists("11.10.1"));
+}
+
+#[test]
+fn uninstall_default_node_fails() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .node_image("10.99.1040")
+        .node_inventory("10.99.1040")
+        .build();
+
+    assert_that!(
+        s.volta("uninstall node@10.99.1040"),
+        execs()
+            .with_status(ExitCode::InvalidArguments as i32)
+            .with_stderr_contains(
+                "[..]Cannot uninstall node@10.99.1040 because it is your default Node version."
+            )
+    );
+
+    assert!(Sandbox::node_image_exists("10.99.1040"));
+    assert!(s.node_inventory_archive_exists("10.99.1040"));
+}
+
+#[test]
+fn uninstall_node_requires_version() {
+    let s = sandbox().platform(PLATFORM_NODE_ONLY).build();
+
+    assert_that!(
+        s.volta("uninstall node"),
+        execs()
+            .with_status(ExitCode::InvalidArguments as i32)
+            .with_stderr_contains("[..]Could not determine which version of node to uninstall.")
+    );
+}
+
+#[test]
+fn uninstall_nonexistent_node() {
+    let s = sandbox()
+        .platform(PLATFORM_NODE_ONLY)
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("uninstall node@8.9.10"),
+        execs()
+            .with_status(0)
+            .with_stderr_contains("[..]No node@8.9.10 found to uninstall")
+    );
+}
+
+#[test]
+fn uninstall_default_yarn_clears_default() {
+    let s = sandbox()
+        .platform(PLATFORM_WITH_YARN)
+        .yarn_image("1.2.42")
+        .yarn_inventory("1.2.42")
+        .env(VOLTA_LOGLEVEL, "info")
+        .build();
+
+    assert_that!(
+        s.volta("uninstall yarn@1.2.42"),
+        execs()
+            .with_status(0)
+            .with_stdout_contains("[..]yarn@1.2.42 was your default Yarn[..]")
+            .with_stdout_contains("[..]yarn@1.2.42 uninstalled")
+    );
+
+    assert!(!Sandbox::yarn_image_exists("1.2.42"));
+    assert!(!s.yarn_inventory_archive_exists("1.2.42"));
+    assert!(!Sandbox::read_default_platform().contains("1.2.42"));
+}
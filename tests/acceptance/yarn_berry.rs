@@ -0,0 +1,215 @@
+use crate::support::sandbox::{sandbox, DistroMetadata, Sandbox, YarnFixture};
+use hamcrest2::assert_that;
+use hamcrest2::prelude::*;
+use mockito::mock;
+use test_support::matchers::execs;
+
+use volta_core::error::ExitCode;
+
+fn package_json_with_pinned_node(node: &str) -> String {
+    format!(
+        r#"{{
+  "name": "test-package",
+  "volta": {{
+    "node": "{}"
+  }}
+}}"#,
+        node
+    )
+}
+
+fn package_json_with_pinned_node_yarn(node: &str, yarn: &str) -> String {
+    format!(
+        r#"{{
+  "name": "test-package",
+  "volta": {{
+    "node": "{}",
+    "yarn": "{}"
+  }}
+}}"#,
+        node, yarn
+    )
+}
+
+fn package_json_with_package_manager(node: &str, package_manager: &str) -> String {
+    format!(
+        r#"{{
+  "name": "test-package",
+  "packageManager": "{}",
+  "volta": {{
+    "node": "{}"
+  }}
+}}"#,
+        package_manager, node
+    )
+}
+
+const YARN_VERSION_INFO: &str = r#"[
+{"tag_name":"v1.2.42","assets":[{"name":"yarn-v1.2.42.tar.gz"}]},
+{"tag_name":"v1.4.159","assets":[{"name":"yarn-v1.4.159.tar.gz"}]}
+]"#;
+
+/// Registry metadata for `@yarnpkg/cli-dist`, with the tarballs hosted by the given registry
+fn yarn_berry_version_info(registry: &str) -> String {
+    format!(
+        r#"
+{{
+    "name":"@yarnpkg/cli-dist",
+    "dist-tags": {{ "latest":"3.2.1" }},
+    "versions": {{
+        "2.4.3": {{ "version":"2.4.3", "dist": {{ "shasum":"", "tarball":"{0}/@yarnpkg/cli-dist/-/cli-dist-2.4.3.tgz" }}}},
+        "3.2.1": {{ "version":"3.2.1", "dist": {{ "shasum":"", "tarball":"{0}/@yarnpkg/cli-dist/-/cli-dist-3.2.1.tgz" }}}}
+    }}
+}}
+"#,
+        registry
+    )
+}
+
+const YARN_BERRY_FIXTURES: [DistroMetadata; 1] = [DistroMetadata {
+    version: "3.2.1",
+    compressed_size: 257,
+    uncompressed_size: Some(0x0028_0000),
+}];
+
+const VOLTA_LOGLEVEL: &str = "VOLTA_LOGLEVEL";
+
+#[test]
+fn pin_yarn_berry_semver() {
+    let s = sandbox()
+        .package_json(&package_json_with_pinned_node("1.2.3"))
+        .yarn_available_versions(YARN_VERSION_INFO)
+        .yarn_berry_available_versions(&yarn_berry_version_info(mockito::SERVER_URL))
+        .distro_mocks::<YarnFixture>(&YARN_BERRY_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("pin yarn@3"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert_eq!(
+        s.read_package_json(),
+        package_json_with_pinned_node_yarn("1.2.3", "3.2.1"),
+    );
+    assert!(Sandbox::yarn_image_exists("3.2.1"));
+    assert!(s.yarn_inventory_archive_exists("3.2.1"));
+}
+
+#[test]
+fn pin_yarn_berry_tag() {
+    let s = sandbox()
+        .package_json(&package_json_with_pinned_node("1.2.3"))
+        .yarn_berry_available_versions(&yarn_berry_version_info(mockito::SERVER_URL))
+        .distro_mocks::<YarnFixture>(&YARN_BERRY_FIXTURES)
+        .build();
+
+    assert_that!(
+        s.volta("pin yarn@berry"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert_eq!(
+        s.read_package_json(),
+        package_json_with_pinned_node_yarn("1.2.3", "3.2.1"),
+    );
+}
+
+#[test]
+fn pin_yarn_berry_from_npmrc_registry() {
+    let mirror = format!("{}/mirror", mockito::SERVER_URL);
+    let _mirror_index = mock("GET", "/mirror/@yarnpkg%2fcli-dist")
+        .match_header("Authorization", "Bearer mirror-token")
+        .with_status(200)
+        .with_header("Content-Type", "application/json")
+        .with_body(&yarn_berry_version_info(&mirror))
+        .create();
+    let mirror_tarball = mock("GET", "/mirror/@yarnpkg/cli-dist/-/cli-dist-3.2.1.tgz")
+        .match_header("Authorization", "Bearer mirror-token")
+        .with_status(200)
+        .with_body_from_file("tests/fixtures/cli-dist-3.2.1.tgz")
+        .create();
+
+    // Tokens are configured by the registry URL without its protocol
+    let npmrc = format!(
+        "registry={}/\n{}/:_authToken=mirror-token\n",
+        mirror,
+        &mirror["http:".len()..]
+    );
+    let s = sandbox()
+        .package_json(&package_json_with_pinned_node("1.2.3"))
+        .project_file(".npmrc", &npmrc)
+        .build();
+
+    assert_that!(
+        s.volta("pin yarn@berry"),
+        execs().with_status(ExitCode::Success as i32)
+    );
+
+    assert_eq!(
+        s.read_package_json(),
+        package_json_with_pinned_node_yarn("1.2.3", "3.2.1"),
+    );
+    mirror_tarball.assert();
+    assert!(Sandbox::yarn_image_exists("3.2.1"));
+}
+
+#[test]
+fn package_manager_field_pins_yarn() {
+    let s = sandbox()
+        .package_json(&package_json_with_package_manager(
+            "10.99.1040",
+            "yarn@3.2.1+sha224.953c8233f7a92884eee2de69a1b92d1f2ec1655e66d08071ba9a02fa",
+        ))
+        .build();
+
+    assert_that!(
+        s.volta("list --format plain"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("package-manager yarn@3.2.1 (current @ [..]package.json)")
+    );
+}
+
+#[test]
+fn volta_pin_overrides_package_manager_field() {
+    let s = sandbox()
+        .package_json(
+            r#"{
+  "name": "test-package",
+  "packageManager": "yarn@3.2.1",
+  "volta": {
+    "node": "10.99.1040",
+    "yarn": "1.2.42"
+  }
+}"#,
+        )
+        .build();
+
+    assert_that!(
+        s.volta("list --format plain"),
+        execs()
+            .with_status(ExitCode::Success as i32)
+            .with_stdout_contains("package-manager yarn@1.2.42 (current @ [..]package.json)")
+            .with_stdout_does_not_contain("[..]yarn@3.2.1[..]")
+    );
+}
+
+#[test]
+fn runs_yarn_path_with_project_node() {
+    let s = sandbox()
+        .package_json(&package_json_with_pinned_node("10.99.1040"))
+        .node_image("10.99.1040")
+        .node_npm_version_file("10.99.1040", "6.2.26")
+        .project_file(".yarnrc.yml", "yarnPath: .yarn/releases/yarn-3.2.1.cjs\n")
+        .project_file(".yarn/releases/yarn-3.2.1.cjs", "console.log('3.2.1');\n")
+        .env(VOLTA_LOGLEVEL, "debug")
+        .build();
+
+    assert_that!(
+        s.volta("run yarn --version"),
+        execs()
+            .with_stderr_contains("[..]Using yarn from `yarnPath` at '[..]yarn-3.2.1.cjs'")
+            .with_stderr_does_not_contain("[..]No Yarn version found in this project.")
+    );
+}